@@ -30,7 +30,8 @@ pub(crate) fn build_something_when_mouse_released(
 
             let game_id = game_state.game_id();
 
-            let command = selected_mode.build_something_command(player_id, *hovered_tile);
+            let command =
+                selected_mode.build_something_command(player_id, *hovered_tile, game_state);
 
             // Later: Check we can build this "something"? And do it invoking the real 'GameState'.
 
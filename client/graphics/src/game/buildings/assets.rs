@@ -12,6 +12,12 @@ pub struct BuildingAssets {
     industry_building_meshes: HashMap<IndustryType, Handle<Mesh>>,
     military_building_meshes: HashMap<MilitaryBuildingType, Handle<Mesh>>,
     station_meshes:           HashMap<StationType, Handle<Mesh>>,
+    waypoint_mesh:            Handle<Mesh>,
+    power_line_mesh:          Handle<Mesh>,
+    // Surrounds the industry buildings while they are being built
+    scaffolding_mesh:         Handle<Mesh>,
+    // A unit cube, scaled to the length of each bridge
+    bridge_mesh:              Handle<Mesh>,
 }
 
 impl BuildingAssets {
@@ -40,7 +46,7 @@ impl BuildingAssets {
         let mut station_meshes = HashMap::new();
 
         const STATION_HEIGHT: f32 = 0.01;
-        for station_type in StationType::all() {
+        for station_type in StationType::all_sizes() {
             let (x, z) = match station_type.orientation {
                 StationOrientation::NorthToSouth => {
                     (
@@ -59,11 +65,41 @@ impl BuildingAssets {
             station_meshes.insert(station_type, mesh);
         }
 
+        const WAYPOINT_HEIGHT: f32 = 0.6;
+        let mut waypoint_mesh = Mesh::from(Cuboid::new(0.1, WAYPOINT_HEIGHT, 0.1));
+        shift_mesh(
+            &mut waypoint_mesh,
+            Vec3::new(0.0, WAYPOINT_HEIGHT / 2.0, 0.0),
+        );
+        let waypoint_mesh = meshes.add(waypoint_mesh);
+
+        const PYLON_HEIGHT: f32 = 1.2;
+        let mut power_line_mesh = Mesh::from(Cuboid::new(0.05, PYLON_HEIGHT, 0.05));
+        shift_mesh(
+            &mut power_line_mesh,
+            Vec3::new(0.0, PYLON_HEIGHT / 2.0, 0.0),
+        );
+        let power_line_mesh = meshes.add(power_line_mesh);
+
+        const SCAFFOLDING_HEIGHT: f32 = PRODUCTION_HEIGHT * 1.5;
+        let mut scaffolding_mesh = Mesh::from(Cuboid::new(3.2, SCAFFOLDING_HEIGHT, 3.2));
+        shift_mesh(
+            &mut scaffolding_mesh,
+            Vec3::new(0.0, SCAFFOLDING_HEIGHT / 2.0, 0.0),
+        );
+        let scaffolding_mesh = meshes.add(scaffolding_mesh);
+
+        let bridge_mesh = meshes.add(Mesh::from(Cuboid::new(1.0, 1.0, 1.0)));
+
         Self {
             fallback,
             industry_building_meshes,
             military_building_meshes,
             station_meshes,
+            waypoint_mesh,
+            power_line_mesh,
+            scaffolding_mesh,
+            bridge_mesh,
         }
     }
 
@@ -83,6 +119,26 @@ impl BuildingAssets {
         }
     }
 
+    #[must_use]
+    pub fn waypoint_mesh(&self) -> Handle<Mesh> {
+        self.waypoint_mesh.clone()
+    }
+
+    #[must_use]
+    pub fn power_line_mesh(&self) -> Handle<Mesh> {
+        self.power_line_mesh.clone()
+    }
+
+    #[must_use]
+    pub fn scaffolding_mesh(&self) -> Handle<Mesh> {
+        self.scaffolding_mesh.clone()
+    }
+
+    #[must_use]
+    pub fn bridge_mesh(&self) -> Handle<Mesh> {
+        self.bridge_mesh.clone()
+    }
+
     #[must_use]
     pub fn military_building_mesh_for(
         &self,
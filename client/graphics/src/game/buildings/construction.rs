@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use bevy::color::Color;
+use bevy::core::Name;
+use bevy::pbr::{MeshMaterial3d, StandardMaterial};
+use bevy::prelude::{
+    AlphaMode, Assets, Commands, Component, Entity, Mesh3d, Query, Res, ResMut, Transform, default,
+};
+use shared_domain::IndustryBuildingId;
+
+use crate::assets::GameAssets;
+use crate::game::GameStateResource;
+use crate::game::buildings::IndustryBuildingIdComponent;
+
+const SCAFFOLDING_COLOUR: Color = Color::srgba(0.6, 0.5, 0.3, 0.4);
+// So that the sites that were just started are still visible
+const MIN_VISIBLE_PROGRESS: f32 = 0.05;
+
+#[derive(Component)]
+pub(super) struct ScaffoldingComponent(IndustryBuildingId);
+
+// Industry buildings under construction rise from the ground as they progress, surrounded by
+// scaffolding until they are finished
+#[expect(clippy::needless_pass_by_value)]
+pub(super) fn update_construction_sites(
+    game_state_resource: Res<GameStateResource>,
+    game_assets: Res<GameAssets>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut industry_building_query: Query<(&IndustryBuildingIdComponent, &mut Transform)>,
+    scaffolding_query: Query<(Entity, &ScaffoldingComponent)>,
+) {
+    let GameStateResource(game_state) = game_state_resource.as_ref();
+    let buildings = game_state.building_state();
+    let scaffoldings = scaffolding_query
+        .iter()
+        .map(|(entity, ScaffoldingComponent(industry_building_id))| (*industry_building_id, entity))
+        .collect::<HashMap<_, _>>();
+
+    for (IndustryBuildingIdComponent(industry_building_id), mut transform) in
+        &mut industry_building_query
+    {
+        let Some(building) = buildings.find_industry_building(*industry_building_id) else {
+            continue;
+        };
+        let scaffolding = scaffoldings.get(industry_building_id);
+        if let Some(construction) = building.construction() {
+            transform.scale.y = construction.progress().max(MIN_VISIBLE_PROGRESS);
+            if scaffolding.is_none() {
+                commands.spawn((
+                    Transform {
+                        translation: transform.translation,
+                        ..default()
+                    },
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: SCAFFOLDING_COLOUR,
+                        alpha_mode: AlphaMode::Blend,
+                        ..default()
+                    })),
+                    Mesh3d(game_assets.building_assets.scaffolding_mesh()),
+                    Name::new(format!("Scaffolding {industry_building_id:?}")),
+                    ScaffoldingComponent(*industry_building_id),
+                ));
+            }
+        } else {
+            transform.scale.y = 1.0;
+            if let Some(entity) = scaffolding {
+                commands.entity(*entity).despawn();
+            }
+        }
+    }
+
+    // The sites that were demolished before being finished
+    for (industry_building_id, entity) in scaffoldings {
+        if buildings
+            .find_industry_building(industry_building_id)
+            .is_none()
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
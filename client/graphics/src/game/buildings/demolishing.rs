@@ -70,6 +70,18 @@ fn demolish_command(
             let military_building = building_state.military_building_at(hovered_tile)?;
             GameCommand::Demolish(DemolishSelector::MilitaryBuilding(military_building.id()))
         },
+        DemolishType::Waypoint => {
+            let waypoint = building_state.waypoint_at(hovered_tile)?;
+            GameCommand::Demolish(DemolishSelector::Waypoint(waypoint.id()))
+        },
+        DemolishType::PowerLine => {
+            let power_line = building_state.power_line_at(hovered_tile)?;
+            GameCommand::Demolish(DemolishSelector::PowerLine(power_line.id()))
+        },
+        DemolishType::Bridge => {
+            let bridge = building_state.bridge_at(hovered_tile)?;
+            GameCommand::Demolish(DemolishSelector::Bridge(bridge.id()))
+        },
         DemolishType::Tracks => {
             // TODO: We should let the user to drag the mouse to select which tracks to demolish
             let tracks = building_state.tracks_at(hovered_tile);
@@ -1,33 +1,48 @@
 #![allow(clippy::needless_pass_by_value, clippy::collapsible_match)]
 
+use bevy::color::Color;
+use bevy::core::Name;
+use bevy::log::warn;
+use bevy::math::{Quat, Vec3};
+use bevy::pbr::MeshMaterial3d;
 use bevy::prelude::{
-    Assets, Commands, Component, Entity, EventReader, FixedUpdate, IntoSystemConfigs, Plugin,
-    Query, Res, ResMut, StandardMaterial, Update,
+    Assets, Commands, Component, Entity, EventReader, FixedUpdate, IntoSystemConfigs, Mesh3d,
+    Plugin, Query, Res, ResMut, StandardMaterial, Transform, Update,
 };
 use bevy::state::condition::in_state;
+use shared_domain::building::bridge_info::{BridgeInfo, BridgeType};
 use shared_domain::building::building_info::WithOwner;
 use shared_domain::building::industry_building_info::IndustryBuildingInfo;
 use shared_domain::building::military_building_info::MilitaryBuildingInfo;
+use shared_domain::building::power_line_info::PowerLineInfo;
 use shared_domain::building::station_info::StationInfo;
+use shared_domain::building::waypoint_info::WaypointInfo;
 use shared_domain::map_level::map_level::MapLevel;
 use shared_domain::players::player_state::PlayerState;
 use shared_domain::server_response::{Colour, GameResponse, ServerResponse};
-use shared_domain::{IndustryBuildingId, MilitaryBuildingId, StationId, TrackId};
+use shared_domain::{
+    BridgeId, IndustryBuildingId, MilitaryBuildingId, PowerLineId, StationId, TrackId, WaypointId,
+};
 
 use crate::assets::GameAssets;
 use crate::communication::domain::ServerMessageEvent;
+use crate::game::buildings::construction::update_construction_sites;
 use crate::game::buildings::demolishing::demolish_when_mouse_released;
 use crate::game::buildings::tracks::build::build_tracks_when_mouse_released;
 use crate::game::buildings::tracks::preview::{
-    TrackPreviewResource, draw_track_preview, select_track_start, update_track_preview,
+    TrackPreviewResource, draw_track_preview, poll_track_preview, select_track_start,
+    update_track_preview,
 };
 use crate::game::buildings::tracks::spawn::{create_rails, create_track, remove_track_entities};
+use crate::game::buildings::upgrading::upgrade_tracks_when_mouse_released;
 use crate::game::{GameStateResource, create_object_entity, player_colour};
 use crate::states::ClientState;
 
 pub mod assets;
+mod construction;
 mod demolishing;
 pub mod tracks;
+mod upgrading;
 
 #[derive(Component)]
 struct StationIdComponent(StationId);
@@ -38,6 +53,15 @@ struct IndustryBuildingIdComponent(IndustryBuildingId);
 #[derive(Component)]
 struct MilitaryBuildingIdComponent(MilitaryBuildingId);
 
+#[derive(Component)]
+struct WaypointIdComponent(WaypointId);
+
+#[derive(Component)]
+struct BridgeIdComponent(BridgeId);
+
+#[derive(Component)]
+struct PowerLineIdComponent(PowerLineId);
+
 #[derive(Component)]
 pub(crate) struct TrackIdComponent(TrackId);
 
@@ -59,10 +83,22 @@ impl Plugin for BuildingsPlugin {
             Update,
             update_track_preview.run_if(in_state(ClientState::Playing)),
         );
+        app.add_systems(
+            Update,
+            poll_track_preview.run_if(in_state(ClientState::Playing)),
+        );
+        app.add_systems(
+            Update,
+            update_construction_sites.run_if(in_state(ClientState::Playing)),
+        );
         app.add_systems(
             Update,
             demolish_when_mouse_released.run_if(in_state(ClientState::Playing)),
         );
+        app.add_systems(
+            Update,
+            upgrade_tracks_when_mouse_released.run_if(in_state(ClientState::Playing)),
+        );
         app.add_systems(
             Update,
             select_track_start.run_if(in_state(ClientState::Playing)),
@@ -120,6 +156,39 @@ fn handle_game_state_snapshot(
                         );
                     }
 
+                    for waypoint in game_state.building_state().all_waypoints() {
+                        create_waypoint(
+                            waypoint,
+                            &mut commands,
+                            &mut materials,
+                            game_assets.as_ref(),
+                            game_state.map_level(),
+                            game_state.players(),
+                        );
+                    }
+
+                    for power_line in game_state.building_state().all_power_lines() {
+                        create_power_line(
+                            power_line,
+                            &mut commands,
+                            &mut materials,
+                            game_assets.as_ref(),
+                            game_state.map_level(),
+                            game_state.players(),
+                        );
+                    }
+
+                    for bridge in game_state.building_state().all_bridges() {
+                        create_bridge(
+                            bridge,
+                            &mut commands,
+                            &mut materials,
+                            game_assets.as_ref(),
+                            game_state.map_level(),
+                            game_state.players(),
+                        );
+                    }
+
                     for military_building in game_state.building_state().all_military_buildings() {
                         create_military_building(
                             military_building,
@@ -152,12 +221,27 @@ fn handle_buildings_or_tracks_changed(
     industry_building_query: Query<(Entity, &IndustryBuildingIdComponent)>,
     military_building_query: Query<(Entity, &MilitaryBuildingIdComponent)>,
     station_query: Query<(Entity, &StationIdComponent)>,
+    waypoint_query: Query<(Entity, &WaypointIdComponent)>,
+    bridge_query: Query<(Entity, &BridgeIdComponent)>,
+    power_line_query: Query<(Entity, &PowerLineIdComponent)>,
+    mut track_preview: ResMut<TrackPreviewResource>,
 ) {
     let GameStateResource(ref mut game_state) = game_state_resource.as_mut();
 
     let map_level = game_state.map_level().clone();
     for message in server_messages.read() {
         if let ServerResponse::Game(_game_id, game_response) = &message.response {
+            let changes_buildings = !matches!(
+                game_response,
+                GameResponse::DynamicInfosSync(..)
+                    | GameResponse::ProjectilesAdded(_)
+                    | GameResponse::ProjectilesRemoved(_)
+                    | GameResponse::Statistics(_)
+            );
+            if changes_buildings {
+                track_preview.invalidate_snapshot();
+            }
+
             match game_response {
                 GameResponse::GameStateSnapshot(_) => {},
                 GameResponse::PlayersUpdated(_) => {},
@@ -219,6 +303,38 @@ fn handle_buildings_or_tracks_changed(
                         );
                     }
                 },
+                GameResponse::TracksUpgraded(track_infos) => {
+                    game_state
+                        .building_state_mut()
+                        .set_track_grades(track_infos);
+
+                    for track_info in track_infos {
+                        remove_track_entities(track_info.id(), &mut commands, &track_query);
+                        create_track(
+                            track_info,
+                            &mut commands,
+                            &mut materials,
+                            game_assets.as_ref(),
+                            &map_level,
+                            game_state.players(),
+                        );
+                    }
+                },
+                GameResponse::IndustryBuildingUpgraded(building_info) => {
+                    game_state
+                        .building_state_mut()
+                        .replace_industry_building(building_info.clone());
+                },
+                GameResponse::PreferredSupplierSet(industry_building_id) => {
+                    if let Err(error) = game_state
+                        .building_state_mut()
+                        .set_preferred_supplier(*industry_building_id)
+                    {
+                        warn!(
+                            "Failed to set preferred supplier {industry_building_id:?}: {error:?}"
+                        );
+                    }
+                },
                 GameResponse::IndustryBuildingRemoved(industry_building_id) => {
                     game_state
                         .building_state_mut()
@@ -241,20 +357,124 @@ fn handle_buildings_or_tracks_changed(
                         &military_building_query,
                     );
                 },
+                GameResponse::StationExpanded(station_info) => {
+                    game_state
+                        .building_state_mut()
+                        .remove_station(station_info.id());
+                    game_state
+                        .building_state_mut()
+                        .append_station(station_info.clone());
+
+                    remove_station_entities(station_info.id(), &mut commands, &station_query);
+                    create_station(
+                        station_info,
+                        &mut commands,
+                        &mut materials,
+                        game_assets.as_ref(),
+                        &map_level,
+                        game_state.players(),
+                    );
+                },
                 GameResponse::StationRemoved(station_id) => {
                     game_state.building_state_mut().remove_station(*station_id);
                     remove_station_entities(*station_id, &mut commands, &station_query);
                 },
                 GameResponse::TracksRemoved(track_ids) => {
                     for track_id in track_ids {
+                        // Waypoints are removed together with the tracks under them
+                        let waypoint_id = game_state
+                            .building_state()
+                            .waypoint_at(track_id.tile)
+                            .map(WaypointInfo::id);
                         game_state.building_state_mut().remove_track(*track_id);
                         remove_track_entities(*track_id, &mut commands, &track_query);
+                        if let Some(waypoint_id) = waypoint_id {
+                            if game_state
+                                .building_state()
+                                .find_waypoint(waypoint_id)
+                                .is_none()
+                            {
+                                remove_waypoint_entities(
+                                    waypoint_id,
+                                    &mut commands,
+                                    &waypoint_query,
+                                );
+                            }
+                        }
                     }
                 },
+                GameResponse::WaypointAdded(waypoint) => {
+                    game_state
+                        .building_state_mut()
+                        .append_waypoint(waypoint.clone());
+
+                    create_waypoint(
+                        waypoint,
+                        &mut commands,
+                        &mut materials,
+                        game_assets.as_ref(),
+                        &map_level,
+                        game_state.players(),
+                    );
+                },
+                GameResponse::WaypointRemoved(waypoint_id) => {
+                    game_state
+                        .building_state_mut()
+                        .remove_waypoint(*waypoint_id);
+                    remove_waypoint_entities(*waypoint_id, &mut commands, &waypoint_query);
+                },
+                GameResponse::BridgeAdded(bridge) => {
+                    game_state
+                        .building_state_mut()
+                        .append_bridge(bridge.clone());
+
+                    create_bridge(
+                        bridge,
+                        &mut commands,
+                        &mut materials,
+                        game_assets.as_ref(),
+                        &map_level,
+                        game_state.players(),
+                    );
+                },
+                GameResponse::BridgeRemoved(bridge_id) => {
+                    game_state.building_state_mut().remove_bridge(*bridge_id);
+                    remove_bridge_entities(*bridge_id, &mut commands, &bridge_query);
+                },
+                GameResponse::PowerLineAdded(power_line) => {
+                    game_state
+                        .building_state_mut()
+                        .append_power_line(power_line.clone());
+
+                    create_power_line(
+                        power_line,
+                        &mut commands,
+                        &mut materials,
+                        game_assets.as_ref(),
+                        &map_level,
+                        game_state.players(),
+                    );
+                },
+                GameResponse::PowerLineRemoved(power_line_id) => {
+                    game_state
+                        .building_state_mut()
+                        .remove_power_line(*power_line_id);
+                    remove_power_line_entities(*power_line_id, &mut commands, &power_line_query);
+                },
                 GameResponse::TransportsAdded(_) => {},
+                GameResponse::TransportsRemoved(_) => {},
+                GameResponse::TransportGroupsUpdated(_) => {},
                 GameResponse::ProjectilesAdded(_) => {},
                 GameResponse::ProjectilesRemoved(_) => {},
                 GameResponse::DynamicInfosSync(..) => {},
+                GameResponse::Statistics(_) => {},
+                GameResponse::TradeOffered(trade_offer) => {
+                    game_state.upsert_trade_offer(trade_offer.clone());
+                },
+                GameResponse::TradeOfferAccepted(trade_offer_id)
+                | GameResponse::TradeOfferCancelled(trade_offer_id) => {
+                    game_state.remove_trade_offer(*trade_offer_id);
+                },
                 GameResponse::GameJoined(..) => {},
                 GameResponse::GameLeft => {},
                 GameResponse::Error(_) => {},
@@ -392,3 +612,123 @@ fn remove_station_entities(
         }
     }
 }
+
+fn create_waypoint(
+    waypoint: &WaypointInfo,
+    commands: &mut Commands,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    game_assets: &GameAssets,
+    map_level: &MapLevel,
+    players: &PlayerState,
+) {
+    let colour = player_colour(players, waypoint.owner_id());
+    create_object_entity(
+        waypoint,
+        format!("Waypoint {:?}", waypoint.tile()),
+        colour,
+        game_assets.building_assets.waypoint_mesh(),
+        materials,
+        commands,
+        map_level,
+        WaypointIdComponent(waypoint.id()),
+    );
+}
+
+fn remove_waypoint_entities(
+    waypoint_id: WaypointId,
+    commands: &mut Commands,
+    query: &Query<(Entity, &WaypointIdComponent)>,
+) {
+    for (entity, waypoint_id_component) in query {
+        let WaypointIdComponent(this_waypoint_id) = waypoint_id_component;
+        if *this_waypoint_id == waypoint_id {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn create_power_line(
+    power_line: &PowerLineInfo,
+    commands: &mut Commands,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    game_assets: &GameAssets,
+    map_level: &MapLevel,
+    players: &PlayerState,
+) {
+    let colour = player_colour(players, power_line.owner_id());
+    create_object_entity(
+        power_line,
+        format!("Power Line {:?}", power_line.tile()),
+        colour,
+        game_assets.building_assets.power_line_mesh(),
+        materials,
+        commands,
+        map_level,
+        PowerLineIdComponent(power_line.id()),
+    );
+}
+
+fn remove_power_line_entities(
+    power_line_id: PowerLineId,
+    commands: &mut Commands,
+    query: &Query<(Entity, &PowerLineIdComponent)>,
+) {
+    for (entity, power_line_id_component) in query {
+        let PowerLineIdComponent(this_power_line_id) = power_line_id_component;
+        if *this_power_line_id == power_line_id {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+const BRIDGE_WIDTH: f32 = 0.6;
+const BRIDGE_THICKNESS: f32 = 0.1;
+const TUNNEL_COLOUR: Colour = Colour::rgb(64, 48, 32);
+
+// Later: The transports still follow the terrain height when crossing bridges and tunnels, rather
+// than the bridge deck or the tunnel's straight line between the portals
+fn create_bridge(
+    bridge: &BridgeInfo,
+    commands: &mut Commands,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    game_assets: &GameAssets,
+    map_level: &MapLevel,
+    players: &PlayerState,
+) {
+    let colour = match bridge.bridge_type() {
+        BridgeType::Bridge => player_colour(players, bridge.owner_id()),
+        BridgeType::Tunnel => TUNNEL_COLOUR,
+    };
+    let color = Color::srgb_u8(colour.r, colour.g, colour.b);
+
+    let terrain = map_level.terrain();
+    let direction = bridge.direction();
+    let entry = terrain.edge_center_coordinate(direction.reverse(), bridge.start_tile());
+    let exit = terrain.edge_center_coordinate(direction, bridge.end_tile());
+    let span = exit - entry;
+
+    commands.spawn((
+        Transform {
+            translation: entry + span / 2.0,
+            rotation:    Quat::from_rotation_arc(Vec3::Z, span.normalize()),
+            scale:       Vec3::new(BRIDGE_WIDTH, BRIDGE_THICKNESS, span.length()),
+        },
+        MeshMaterial3d(materials.add(color)),
+        Mesh3d(game_assets.building_assets.bridge_mesh()),
+        Name::new(format!("{bridge:?}")),
+        BridgeIdComponent(bridge.id()),
+    ));
+}
+
+fn remove_bridge_entities(
+    bridge_id: BridgeId,
+    commands: &mut Commands,
+    query: &Query<(Entity, &BridgeIdComponent)>,
+) {
+    for (entity, bridge_id_component) in query {
+        let BridgeIdComponent(this_bridge_id) = bridge_id_component;
+        if *this_bridge_id == bridge_id {
+            commands.entity(entity).despawn();
+        }
+    }
+}
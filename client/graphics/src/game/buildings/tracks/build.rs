@@ -26,15 +26,22 @@ pub(crate) fn build_tracks_when_mouse_released(
             let _ordered_selected_edges = selected_edges.take();
             *selected_mode_resource = SelectedMode::Tracks(TracksBuildingType::SelectStart);
 
-            let GameStateResource(game_state) = game_state_resource.as_ref();
-            let track_preview = track_preview_resource.take();
+            track_preview_resource.request_build();
+        }
+    }
 
-            if let Some(track_preview) = track_preview {
-                client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
-                    game_state.game_id(),
-                    GameCommand::BuildTracks(track_preview.tracks),
-                )));
-            }
+    if let Some(track_preview) = track_preview_resource.take_requested_build() {
+        let GameStateResource(game_state) = game_state_resource.as_ref();
+        // Bridges go first, so that the tracks connecting to them are not built in vain
+        for bridge in track_preview.bridges {
+            client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+                game_state.game_id(),
+                GameCommand::BuildBridge(bridge),
+            )));
         }
+        client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+            game_state.game_id(),
+            GameCommand::BuildTracks(track_preview.tracks),
+        )));
     }
 }
@@ -1,35 +1,84 @@
-use bevy::prelude::Res;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use shared_domain::PlayerId;
 use shared_domain::directional_edge::DirectionalEdge;
 use shared_domain::edge_xz::EdgeXZ;
 use shared_domain::game_state::GameState;
 use shared_domain::metrics::NoopMetrics;
 use shared_domain::tile_coords_xz::TileCoordsXZ;
-use shared_domain::transport::track_planner::{DEFAULT_ALREADY_EXISTS_COEF, plan_tracks};
+use shared_domain::transport::track_planner::{
+    DEFAULT_ALREADY_EXISTS_COEF, plan_tracks_cancellable,
+};
 
-use crate::game::PlayerIdResource;
 use crate::game::buildings::tracks::preview::TrackPreview;
 
-// TODO HIGH: If the selected first/last tile is a station, we should snap to the station's edge
-// TODO: We could improve the snapping logic, e.g. by passing in multiple tail `DirectionalEdge`-s into the pathfinding logic
-pub(crate) fn try_plan_tracks(
-    player_id_resource: Res<PlayerIdResource>,
-    game_state: &GameState,
-    head: DirectionalEdge,
-    tail_tile: Option<TileCoordsXZ>,
-    tail_edge: Option<EdgeXZ>,
-) -> Option<TrackPreview> {
-    let tail = resolve_tail(tail_tile, tail_edge)?;
-
-    let PlayerIdResource(player_id) = *player_id_resource;
-    plan_tracks(
-        player_id,
-        head,
-        &[tail],
-        game_state,
-        DEFAULT_ALREADY_EXISTS_COEF,
-        &NoopMetrics::default(),
-    )
-    .map(|(tracks, _)| TrackPreview { head, tracks, tail })
+// Planning can take hundreds of milliseconds on large maps, so it is done on a background task.
+// Dropping the task cancels the planning.
+pub(crate) struct TrackPlanningTask {
+    head:      DirectionalEdge,
+    tail:      DirectionalEdge,
+    cancelled: Arc<AtomicBool>,
+    task:      Task<Option<TrackPreview>>,
+}
+
+impl TrackPlanningTask {
+    // TODO HIGH: If the selected first/last tile is a station, we should snap to the station's edge
+    // TODO: We could improve the snapping logic, e.g. by passing in multiple tail `DirectionalEdge`-s into the pathfinding logic
+    pub(crate) fn spawn(
+        player_id: PlayerId,
+        game_state: Arc<GameState>,
+        head: DirectionalEdge,
+        tail: DirectionalEdge,
+    ) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let task_cancelled = Arc::clone(&cancelled);
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            plan_tracks_cancellable(
+                player_id,
+                head,
+                &[tail],
+                &game_state,
+                DEFAULT_ALREADY_EXISTS_COEF,
+                &NoopMetrics::default(),
+                &|| task_cancelled.load(Ordering::Relaxed),
+            )
+            .map(|(planned, _)| {
+                TrackPreview {
+                    head,
+                    tracks: planned.tracks,
+                    bridges: planned.bridges,
+                    tail,
+                }
+            })
+        });
+
+        Self {
+            head,
+            tail,
+            cancelled,
+            task,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn is_for(&self, head: DirectionalEdge, tail: DirectionalEdge) -> bool {
+        self.head == head && self.tail == tail
+    }
+
+    // `None` if still planning, `Some(None)` if no tracks could be planned
+    pub(crate) fn poll(&mut self) -> Option<Option<TrackPreview>> {
+        block_on(future::poll_once(&mut self.task))
+    }
+}
+
+impl Drop for TrackPlanningTask {
+    fn drop(&mut self) {
+        // Dropping the `Task` does not interrupt the planning if it has already started
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
 }
 
 pub(crate) fn resolve_head(
@@ -44,7 +93,7 @@ pub(crate) fn resolve_head(
     Some(head)
 }
 
-fn resolve_tail(
+pub(crate) fn resolve_tail(
     tail_tile: Option<TileCoordsXZ>,
     tail_edge: Option<EdgeXZ>,
 ) -> Option<DirectionalEdge> {
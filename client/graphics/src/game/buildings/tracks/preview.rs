@@ -1,14 +1,20 @@
+use std::sync::Arc;
+
 use bevy::color::palettes::basic::{BLUE, RED};
 use bevy::math::Vec3;
 use bevy::prelude::{
     ButtonInput, CubicCardinalSpline, CubicGenerator, DetectChanges, Gizmos, MouseButton, Res,
     ResMut, Resource,
 };
+use shared_domain::PlayerId;
+use shared_domain::building::bridge_info::BridgeInfo;
 use shared_domain::building::track_info::TrackInfo;
 use shared_domain::directional_edge::DirectionalEdge;
+use shared_domain::game_state::GameState;
 use shared_domain::map_level::terrain::Terrain;
+use shared_util::bool_ops::BoolOptionOps;
 
-use crate::game::buildings::tracks::plan::{resolve_head, try_plan_tracks};
+use crate::game::buildings::tracks::plan::{TrackPlanningTask, resolve_head, resolve_tail};
 use crate::game::buildings::tracks::positions::rail_positions;
 use crate::game::{GameStateResource, PlayerIdResource};
 use crate::hud::domain::{SelectedMode, TracksBuildingType};
@@ -16,25 +22,90 @@ use crate::selection::{HoveredEdge, HoveredTile};
 
 #[derive(Eq, PartialEq)]
 pub(crate) struct TrackPreview {
-    pub head:   DirectionalEdge,
-    pub tracks: Vec<TrackInfo>,
-    pub tail:   DirectionalEdge,
+    pub head:    DirectionalEdge,
+    pub tracks:  Vec<TrackInfo>,
+    pub bridges: Vec<BridgeInfo>,
+    pub tail:    DirectionalEdge,
 }
 
 #[derive(Resource, Default)]
-pub(crate) struct TrackPreviewResource(pub Option<TrackPreview>);
+pub(crate) struct TrackPreviewResource {
+    // The latest finished plan, which is kept as a best-effort preview while a newer one is planned
+    preview:         Option<TrackPreview>,
+    planning:        Option<TrackPlanningTask>,
+    build_requested: bool,
+    // Shared with the planning tasks, so that the game state is not cloned on every hover change
+    snapshot:        Option<Arc<GameState>>,
+}
 
 impl TrackPreviewResource {
-    pub fn take(&mut self) -> Option<TrackPreview> {
-        std::mem::take(&mut self.0)
+    #[must_use]
+    pub fn preview(&self) -> Option<&TrackPreview> {
+        self.preview.as_ref()
+    }
+
+    pub fn plan(
+        &mut self,
+        player_id: PlayerId,
+        game_state: &GameState,
+        head: DirectionalEdge,
+        tail: Option<DirectionalEdge>,
+    ) {
+        match tail {
+            None => {
+                self.planning = None;
+                self.preview = None;
+            },
+            Some(tail) => {
+                let already_planning = self
+                    .planning
+                    .as_ref()
+                    .is_some_and(|planning| planning.is_for(head, tail));
+                let already_planned = self.planning.is_none()
+                    && self
+                        .preview
+                        .as_ref()
+                        .is_some_and(|preview| preview.head == head && preview.tail == tail);
+                if !already_planning && !already_planned {
+                    let snapshot = self
+                        .snapshot
+                        .get_or_insert_with(|| Arc::new(game_state.clone()));
+                    // Replacing the previous task cancels it
+                    self.planning = Some(TrackPlanningTask::spawn(
+                        player_id,
+                        Arc::clone(snapshot),
+                        head,
+                        tail,
+                    ));
+                }
+            },
+        }
+    }
+
+    // Planning only needs the map and the buildings, so the snapshot is refreshed when those change
+    pub fn invalidate_snapshot(&mut self) {
+        self.snapshot = None;
+    }
+
+    pub fn poll(&mut self) {
+        if let Some(planning) = self.planning.as_mut() {
+            if let Some(planned) = planning.poll() {
+                self.preview = planned;
+                self.planning = None;
+            }
+        }
     }
 
-    pub fn should_update(&self, new: Option<&TrackPreview>) -> bool {
-        self.0.as_ref() != new
+    // The tracks will be built once the planning for the latest preview finishes
+    pub fn request_build(&mut self) {
+        self.build_requested = true;
     }
 
-    pub fn update(&mut self, planned: Option<TrackPreview>) {
-        self.0 = planned;
+    pub fn take_requested_build(&mut self) -> Option<TrackPreview> {
+        self.build_requested.then_some_unit()?;
+        self.planning.is_some().then_none()?;
+        self.build_requested = false;
+        self.preview.take()
     }
 }
 
@@ -56,7 +127,6 @@ pub(crate) fn select_track_start(
     }
 }
 
-// Later: Do the planning for preview `async` using https://github.com/loopystudios/bevy_async_task
 // Later: Don't instantly plan when mouse is being rapidly moved, instead wait for a small delay
 pub(crate) fn update_track_preview(
     hovered_tile: Res<HoveredTile>,
@@ -66,42 +136,43 @@ pub(crate) fn update_track_preview(
     selected_mode_resource: Res<SelectedMode>,
     mut track_preview: ResMut<TrackPreviewResource>,
 ) {
+    if game_state_resource.is_added() {
+        track_preview.invalidate_snapshot();
+    }
+
     let selected_mode = selected_mode_resource.as_ref();
     if let SelectedMode::Tracks(TracksBuildingType::SelectEnd { start }) = selected_mode {
         let changed = hovered_tile.is_changed() || hovered_edge.is_changed();
         if changed {
             let GameStateResource(game_state) = game_state_resource.as_ref();
-
-            let planned = try_plan_tracks(
-                player_id_resource,
-                game_state,
-                *start,
-                hovered_tile.0,
-                hovered_edge.0,
-            );
-
-            if track_preview.should_update(planned.as_ref()) {
-                track_preview.update(planned);
-            }
+            let PlayerIdResource(player_id) = *player_id_resource;
+            let tail = resolve_tail(hovered_tile.0, hovered_edge.0);
+            track_preview.plan(player_id, game_state, *start, tail);
         }
     }
 }
 
+pub(crate) fn poll_track_preview(mut track_preview: ResMut<TrackPreviewResource>) {
+    track_preview.poll();
+}
+
 pub(crate) fn draw_track_preview(
     track_preview_resource: Res<TrackPreviewResource>,
     mut gizmos: Gizmos,
     game_state_resource: Res<GameStateResource>,
 ) {
     let GameStateResource(game_state) = game_state_resource.as_ref();
-    let TrackPreviewResource(track_preview) = track_preview_resource.as_ref();
-
-    if let Some(track_preview) = track_preview {
+    if let Some(track_preview) = track_preview_resource.preview() {
         let terrain = game_state.map_level().terrain();
         debug_draw_track_spline(track_preview, &mut gizmos, terrain);
 
         for track_info in &track_preview.tracks {
             debug_draw_track(track_info, &mut gizmos, terrain);
         }
+
+        for bridge in &track_preview.bridges {
+            debug_draw_bridge(bridge, &mut gizmos, terrain);
+        }
     }
 }
 
@@ -130,3 +201,11 @@ fn debug_draw_track(track_info: &TrackInfo, gizmos: &mut Gizmos, terrain: &Terra
     gizmos.line(a1, b2, color);
     gizmos.line(a2, b1, color);
 }
+
+fn debug_draw_bridge(bridge: &BridgeInfo, gizmos: &mut Gizmos, terrain: &Terrain) {
+    let direction = bridge.direction();
+    let entry = terrain.edge_center_coordinate(direction.reverse(), bridge.start_tile());
+    let exit = terrain.edge_center_coordinate(direction, bridge.end_tile());
+
+    gizmos.line(entry, exit, BLUE);
+}
@@ -10,6 +10,7 @@ use shared_domain::map_level::map_level::MapLevel;
 use shared_domain::players::player_state::PlayerState;
 use shared_domain::server_response::Colour;
 use shared_domain::tile_coords_xz::TileCoordsXZ;
+use shared_domain::transport::track_grade::TrackGrade;
 use shared_domain::transport::track_type::TrackType;
 use shared_domain::{StationId, TrackId};
 
@@ -27,7 +28,10 @@ pub(crate) fn create_track(
     map_level: &MapLevel,
     players: &PlayerState,
 ) {
-    let colour = player_colour(players, track_info.owner_id());
+    let colour = grade_colour(
+        player_colour(players, track_info.owner_id()),
+        track_info.grade,
+    );
     create_rails(
         colour,
         commands,
@@ -41,6 +45,17 @@ pub(crate) fn create_track(
     );
 }
 
+// Faster tracks are drawn in a lighter shade of the owner's colour, so they stand out
+fn grade_colour(colour: Colour, grade: TrackGrade) -> Colour {
+    let lighten = |component: u8| component.saturating_add((255 - component) / 2);
+    match grade {
+        TrackGrade::LightRail => colour,
+        TrackGrade::Mainline => {
+            Colour::rgb(lighten(colour.r), lighten(colour.g), lighten(colour.b))
+        },
+    }
+}
+
 // Later: Consider what to do with the rails that right now go through the terrain.
 // Either prohibit such, or make them render better.
 #[expect(clippy::similar_names, clippy::too_many_arguments)]
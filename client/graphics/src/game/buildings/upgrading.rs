@@ -0,0 +1,67 @@
+use bevy::input::ButtonInput;
+use bevy::prelude::{EventWriter, MouseButton, Res};
+use shared_domain::TrackId;
+use shared_domain::client_command::{ClientCommand, GameCommand};
+use shared_util::bool_ops::BoolOptionOps;
+
+use crate::communication::domain::ClientMessageEvent;
+use crate::game::GameStateResource;
+use crate::hud::PointerOverHud;
+use crate::hud::domain::SelectedMode;
+use crate::selection::HoveredTile;
+
+pub(crate) fn upgrade_tracks_when_mouse_released(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    selected_mode_resource: Res<SelectedMode>,
+    game_state_resource: Res<GameStateResource>,
+    hovered_tile: Res<HoveredTile>,
+    pointer_over_hud: Res<PointerOverHud>,
+    mut client_messages: EventWriter<ClientMessageEvent>,
+) {
+    if let Some(upgrade_command) = upgrade_tracks_command(
+        mouse_buttons,
+        selected_mode_resource,
+        game_state_resource,
+        hovered_tile,
+        pointer_over_hud,
+    ) {
+        client_messages.send(upgrade_command);
+    }
+}
+
+fn upgrade_tracks_command(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    selected_mode_resource: Res<SelectedMode>,
+    game_state_resource: Res<GameStateResource>,
+    hovered_tile: Res<HoveredTile>,
+    pointer_over_hud: Res<PointerOverHud>,
+) -> Option<ClientMessageEvent> {
+    pointer_over_hud.get().then_none()?;
+    mouse_buttons
+        .just_released(MouseButton::Left)
+        .then_some_unit()?;
+
+    let SelectedMode::UpgradeTracks(grade) = selected_mode_resource.as_ref() else {
+        return None;
+    };
+    let HoveredTile(hovered_tile) = hovered_tile.as_ref();
+    let hovered_tile = hovered_tile.as_ref().map(|hovered_tile| *hovered_tile)?;
+
+    let GameStateResource(game_state) = game_state_resource.as_ref();
+
+    // TODO: We should let the user to drag the mouse to select which tracks to upgrade
+    let track_ids = game_state
+        .building_state()
+        .tracks_at(hovered_tile)
+        .track_types()
+        .into_iter()
+        .map(|track_type| TrackId::new(hovered_tile, track_type))
+        .collect::<Vec<_>>();
+    track_ids.is_empty().then_none()?;
+
+    let command = GameCommand::UpgradeTracks(track_ids, *grade);
+    Some(ClientMessageEvent::new(ClientCommand::Game(
+        game_state.game_id(),
+        command,
+    )))
+}
@@ -78,14 +78,26 @@ fn handle_projectile_added_or_removed(
                 GameResponse::GameStateSnapshot(_) => {},
                 GameResponse::PlayersUpdated(_) => {},
                 GameResponse::IndustryBuildingAdded(_) => {},
+                GameResponse::IndustryBuildingUpgraded(_) => {},
+                GameResponse::PreferredSupplierSet(_) => {},
                 GameResponse::IndustryBuildingRemoved(_) => {},
                 GameResponse::MilitaryBuildingAdded(_) => {},
                 GameResponse::MilitaryBuildingRemoved(_) => {},
                 GameResponse::StationAdded(_) => {},
+                GameResponse::StationExpanded(_) => {},
                 GameResponse::StationRemoved(_) => {},
                 GameResponse::TracksAdded(_) => {},
+                GameResponse::TracksUpgraded(_) => {},
                 GameResponse::TracksRemoved(_) => {},
+                GameResponse::WaypointAdded(_) => {},
+                GameResponse::WaypointRemoved(_) => {},
+                GameResponse::BridgeAdded(_) => {},
+                GameResponse::BridgeRemoved(_) => {},
+                GameResponse::PowerLineAdded(_) => {},
+                GameResponse::PowerLineRemoved(_) => {},
                 GameResponse::TransportsAdded(_) => {},
+                GameResponse::TransportsRemoved(_) => {},
+                GameResponse::TransportGroupsUpdated(_) => {},
                 GameResponse::ProjectilesAdded(projectiles) => {
                     // The tricky part is that we can receive the same projectile multiple times - once from the client side game state, once from the server side game state...
                     // Is there a better way? Not sure.
@@ -100,6 +112,10 @@ fn handle_projectile_added_or_removed(
                     }
                 },
                 GameResponse::DynamicInfosSync(..) => {},
+                GameResponse::Statistics(_) => {},
+                GameResponse::TradeOffered(_) => {},
+                GameResponse::TradeOfferAccepted(_) => {},
+                GameResponse::TradeOfferCancelled(_) => {},
                 GameResponse::GameJoined(_player_id, _game_state) => {},
                 GameResponse::GameLeft => {},
                 GameResponse::Error(_) => {},
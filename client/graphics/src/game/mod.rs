@@ -209,17 +209,33 @@ fn handle_game_joining_and_game_state_snapshot(
                 },
                 GameResponse::PlayersUpdated(_) => {},
                 GameResponse::IndustryBuildingAdded(_) => {},
+                GameResponse::IndustryBuildingUpgraded(_) => {},
+                GameResponse::PreferredSupplierSet(_) => {},
                 GameResponse::MilitaryBuildingAdded(_) => {},
                 GameResponse::IndustryBuildingRemoved(_) => {},
                 GameResponse::MilitaryBuildingRemoved(_) => {},
                 GameResponse::StationAdded(_) => {},
+                GameResponse::StationExpanded(_) => {},
                 GameResponse::StationRemoved(_) => {},
                 GameResponse::TracksAdded(_) => {},
+                GameResponse::TracksUpgraded(_) => {},
                 GameResponse::TracksRemoved(_) => {},
+                GameResponse::WaypointAdded(_) => {},
+                GameResponse::WaypointRemoved(_) => {},
+                GameResponse::BridgeAdded(_) => {},
+                GameResponse::BridgeRemoved(_) => {},
+                GameResponse::PowerLineAdded(_) => {},
+                GameResponse::PowerLineRemoved(_) => {},
                 GameResponse::TransportsAdded(_) => {},
+                GameResponse::TransportsRemoved(_) => {},
+                GameResponse::TransportGroupsUpdated(_) => {},
                 GameResponse::ProjectilesAdded(_) => {},
                 GameResponse::ProjectilesRemoved(_) => {},
                 GameResponse::DynamicInfosSync(..) => {},
+                GameResponse::Statistics(_) => {},
+                GameResponse::TradeOffered(_) => {},
+                GameResponse::TradeOfferAccepted(_) => {},
+                GameResponse::TradeOfferCancelled(_) => {},
                 GameResponse::GameJoined(player_id, snapshot) => {
                     commands.insert_resource(GameStateResource(snapshot.clone()));
                     commands.insert_resource(PlayerIdResource(*player_id));
@@ -325,8 +341,10 @@ fn handle_dynamic_infos_sync(
                 industry_building_infos,
                 station_building_infos,
                 military_building_infos,
+                construction_sites,
                 transport_infos,
                 projectile_infos,
+                treasuries,
             ) = game_response
             {
                 if let Some(time_factor) = time_factor {
@@ -337,8 +355,10 @@ fn handle_dynamic_infos_sync(
                     industry_building_infos,
                     station_building_infos,
                     military_building_infos,
+                    construction_sites,
                     transport_infos,
                     projectile_infos,
+                    treasuries,
                 );
             }
         }
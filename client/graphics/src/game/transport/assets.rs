@@ -51,6 +51,27 @@ impl TransportAssets {
                 TRAIN_EXTRA_HEIGHT,
             ),
         );
+        map.insert(
+            TrainComponentType::ElectricEngine,
+            // The pantograph makes it a bit higher than the diesel one
+            adjusted_cuboid(
+                GAP_BETWEEN_TRAIN_COMPONENTS,
+                TRAIN_WIDTH,
+                TRAIN_WIDTH * 1.8,
+                TrainComponentType::ElectricEngine.length_in_tiles(),
+                TRAIN_EXTRA_HEIGHT,
+            ),
+        );
+        map.insert(
+            TrainComponentType::RailwayGun,
+            adjusted_cuboid(
+                GAP_BETWEEN_TRAIN_COMPONENTS,
+                TRAIN_WIDTH,
+                TRAIN_WIDTH * 1.2,
+                TrainComponentType::RailwayGun.length_in_tiles(),
+                TRAIN_EXTRA_HEIGHT,
+            ),
+        );
         for resource_type in ResourceType::all() {
             let train_component_type = TrainComponentType::Car(resource_type);
             map.insert(
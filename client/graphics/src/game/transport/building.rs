@@ -46,6 +46,9 @@ fn build_transport_command(
         location,
         movement_orders,
     );
+    game_state
+        .can_purchase_transport(*player_id, building.id(), &transport_info)
+        .map_err(|error| format!("Cannot purchase transport at {tile:?}: {error:?}"))?;
     let result = ClientCommand::Game(
         game_id,
         GameCommand::PurchaseTransport(building.id(), transport_info),
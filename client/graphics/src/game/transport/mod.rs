@@ -8,8 +8,8 @@ use bevy::app::App;
 use bevy::asset::Assets;
 use bevy::pbr::StandardMaterial;
 use bevy::prelude::{
-    Children, Commands, Component, Entity, EventReader, FixedUpdate, IntoSystemConfigs, Plugin,
-    Query, Res, ResMut, Transform, Update, Visibility, in_state, warn,
+    Children, Commands, Component, DespawnRecursiveExt, Entity, EventReader, FixedUpdate,
+    IntoSystemConfigs, Plugin, Query, Res, ResMut, Transform, Update, Visibility, in_state, warn,
 };
 use shared_domain::TransportId;
 use shared_domain::building::building_info::WithOwner;
@@ -44,7 +44,7 @@ impl Plugin for TransportPlugin {
         app.add_systems(FixedUpdate, handle_game_state_snapshot);
         app.add_systems(
             FixedUpdate,
-            handle_transport_created.run_if(in_state(ClientState::Playing)),
+            handle_transports_added_or_removed.run_if(in_state(ClientState::Playing)),
         );
         app.add_systems(
             Update,
@@ -139,17 +139,14 @@ fn handle_game_state_snapshot(
     }
 }
 
-#[expect(
-    clippy::collapsible_match,
-    clippy::needless_pass_by_value,
-    clippy::single_match
-)]
-fn handle_transport_created(
+#[expect(clippy::needless_pass_by_value)]
+fn handle_transports_added_or_removed(
     mut server_messages: EventReader<ServerMessageEvent>,
     mut commands: Commands,
     game_assets: Res<GameAssets>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut game_state_resource: ResMut<GameStateResource>,
+    transport_query: Query<(Entity, &TransportIdComponent)>,
 ) {
     let GameStateResource(game_state) = game_state_resource.as_mut();
     for message in server_messages.read() {
@@ -168,12 +165,41 @@ fn handle_transport_created(
                         );
                     }
                 },
+                GameResponse::TransportsRemoved(transport_ids) => {
+                    for transport_id in transport_ids {
+                        if game_state.remove_transport(*transport_id).is_err() {
+                            warn!(
+                                "Tried to remove transport {transport_id:?} but it was not found"
+                            );
+                        }
+                        remove_transport_entities(*transport_id, &mut commands, &transport_query);
+                    }
+                },
+                GameResponse::TransportGroupsUpdated(groups) => {
+                    for group in groups {
+                        game_state.upsert_transport_group(group.clone());
+                    }
+                },
                 _ => {},
             }
         }
     }
 }
 
+fn remove_transport_entities(
+    transport_id: TransportId,
+    commands: &mut Commands,
+    query: &Query<(Entity, &TransportIdComponent)>,
+) {
+    for (entity, transport_id_component) in query {
+        let TransportIdComponent(this_transport_id) = transport_id_component;
+        if *this_transport_id == transport_id {
+            // The train components are children of the transport entity
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
 fn create_transport(
     transport_info: &TransportInfo,
     commands: &mut Commands,
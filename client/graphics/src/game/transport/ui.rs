@@ -3,19 +3,18 @@ use std::collections::HashSet;
 use bevy::input::ButtonInput;
 use bevy::prelude::{EventWriter, MouseButton, Res, ResMut, Resource, info};
 use bevy_egui::EguiContexts;
-use shared_domain::TransportId;
 use shared_domain::building::building_info::WithOwner;
 use shared_domain::cargo_map::WithCargo;
-use shared_domain::client_command::{ClientCommand, GameCommand};
-use shared_domain::transport::movement_orders::{
-    LoadAction, MovementOrder, MovementOrderAction, MovementOrderLocation, UnloadAction,
-};
+use shared_domain::client_command::{ClientCommand, DemolishSelector, GameCommand};
+use shared_domain::transport::movement_orders::{MovementOrder, MovementOrderLocation};
+use shared_domain::transport::transport_group::TransportGroupInfo;
+use shared_domain::{TransportGroupId, TransportId};
 
 use crate::cameras::CameraControlEvent;
 use crate::communication::domain::ClientMessageEvent;
 use crate::game::{GameStateResource, PlayerIdResource};
 use crate::hud::domain::{SelectType, SelectedMode};
-use crate::hud::{PointerOverHud, player_layout_job};
+use crate::hud::{PointerOverHud, ammunition_status_label, player_layout_job};
 use crate::selection::HoveredTile;
 
 #[derive(Resource, Default)]
@@ -62,7 +61,7 @@ pub(crate) fn select_station_to_add_to_movement_orders(
     if let Some(game_state_resource) = game_state_resource {
         if mouse_buttons.just_released(MouseButton::Left) {
             if let SelectedMode::Select(
-                SelectType::StationToAppendToTransportMovementInstructions(transport_id),
+                SelectType::LocationToAppendToTransportMovementInstructions(transport_id),
             ) = *selected_mode
             {
                 let HoveredTile(hovered_tile) = hovered_tile.as_ref();
@@ -71,21 +70,36 @@ pub(crate) fn select_station_to_add_to_movement_orders(
 
                     if let Some(transport_info) = game_state.get_transport_info(transport_id) {
                         let mut new_movement_orders = transport_info.movement_orders().clone();
+                        let building_state = game_state.building_state();
 
-                        if let Some(station) = game_state.building_state().station_at(*hovered_tile)
-                        {
-                            let station_id = station.id();
-
-                            let movement_order = MovementOrder {
-                                go_to:  MovementOrderLocation::Station(station_id),
-                                action: MovementOrderAction::UnloadAndLoad(
-                                    UnloadAction::Unload,
-                                    LoadAction::Load,
-                                ),
+                        // Military units deploy at waypoints, while other transports pass through them
+                        let is_military_unit = transport_info.transport_type().is_military_unit();
+                        let movement_order =
+                            if let Some(waypoint) = building_state.waypoint_at(*hovered_tile) {
+                                if is_military_unit {
+                                    Some(MovementOrder::deploy_at(
+                                        MovementOrderLocation::Waypoint(waypoint.id()),
+                                        None,
+                                    ))
+                                } else {
+                                    Some(MovementOrder::pass_through_waypoint(waypoint.id()))
+                                }
+                            } else {
+                                building_state
+                                    .station_at(*hovered_tile)
+                                    .map(|station| MovementOrder::stop_at_station(station.id()))
                             };
 
+                        let changed = if let Some(movement_order) = movement_order {
                             new_movement_orders.push(movement_order);
+                            true
+                        } else {
+                            // Any other tile becomes the target of the military unit's last
+                            // deploy order
+                            is_military_unit && new_movement_orders.aim_last_order(*hovered_tile)
+                        };
 
+                        if changed {
                             client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
                                 game_state.game_id(),
                                 GameCommand::UpdateTransportMovementOrders(
@@ -94,7 +108,10 @@ pub(crate) fn select_station_to_add_to_movement_orders(
                                 ),
                             )));
                         } else {
-                            info!("No station found at hovered tile {:?}", hovered_tile,);
+                            info!(
+                                "No station, waypoint or target found at hovered tile {:?}",
+                                hovered_tile,
+                            );
                         }
                     } else {
                         info!("Transport {:?} not found in game state", transport_id,);
@@ -135,6 +152,19 @@ pub(crate) fn show_transport_details(
                         if ui.button("Close").clicked() {
                             show_transport_details.remove(transport.transport_id());
                         }
+                        // Transports can only be sold when stopped at a station
+                        let can_sell = game_state
+                            .station_where_transport_is_stopped(transport.transport_id())
+                            .is_some();
+                        if ui.add_enabled(can_sell, egui::Button::new("💰 Sell")).clicked() {
+                            client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+                                game_state.game_id(),
+                                GameCommand::Demolish(DemolishSelector::Transport(
+                                    transport.transport_id(),
+                                )),
+                            )));
+                            show_transport_details.remove(transport.transport_id());
+                        }
                         egui::Grid::new("transport_details")
                             .num_columns(2)
                             .striped(true)
@@ -162,6 +192,20 @@ pub(crate) fn show_transport_details(
                                 ui.label("Cargo Processing");
                                 ui.label(format!("{:?}", transport.cargo_processing()));
                                 ui.end_row();
+                                if let Some(military_unit) = transport.military_unit() {
+                                    ui.label("Military Unit");
+                                    ui.label(format!(
+                                        "{}, {}",
+                                        if military_unit.is_deployed() { "Deployed" } else { "Not deployed" },
+                                        if military_unit.is_supplied() { "supplied" } else { "not supplied" },
+                                    ));
+                                    ui.end_row();
+                                }
+                                if let Some(ammunition_status) = transport.ammunition_status(game_state.building_state()) {
+                                    ui.label("Ammunition");
+                                    ui.label(ammunition_status_label(game_state, ammunition_status));
+                                    ui.end_row();
+                                }
                                 ui.label("Force Stopped");
                                 if ui.button(format!("{:?}", movement_orders.is_force_stopped())).clicked() {
                                     let mut new_movement_orders = movement_orders.clone();
@@ -177,6 +221,38 @@ pub(crate) fn show_transport_details(
                                     ));
                                 };
                                 ui.end_row();
+                                ui.label("Line");
+                                ui.horizontal(|ui| {
+                                    let transport_state = game_state.transport_state();
+                                    let mut send = |command| {
+                                        client_messages.send(ClientMessageEvent::new(
+                                            ClientCommand::Game(game_state.game_id(), command),
+                                        ));
+                                    };
+                                    let transport_id = transport.transport_id();
+                                    if ui.selectable_label(transport.group_id().is_none(), "―").clicked() {
+                                        send(GameCommand::AssignTransportToGroup(transport_id, None));
+                                    }
+                                    let groups = transport_state.find_players_groups(transport.owner_id());
+                                    for group in &groups {
+                                        let selected = transport.group_id() == Some(group.group_id());
+                                        if ui.selectable_label(selected, group.name()).clicked() && !selected {
+                                            send(GameCommand::AssignTransportToGroup(transport_id, Some(group.group_id())));
+                                        }
+                                    }
+                                    if ui.button("➕ New Line").clicked() {
+                                        let group = TransportGroupInfo::new(
+                                            TransportGroupId::random(),
+                                            transport.owner_id(),
+                                            format!("Line {}", groups.len() + 1),
+                                            movement_orders.clone(),
+                                        );
+                                        let group_id = group.group_id();
+                                        send(GameCommand::CreateTransportGroup(group));
+                                        send(GameCommand::AssignTransportToGroup(transport_id, Some(group_id)));
+                                    }
+                                });
+                                ui.end_row();
                             });
                         egui::Grid::new("transport_movement_orders")
                             .num_columns(5)
@@ -190,9 +266,18 @@ pub(crate) fn show_transport_details(
                                 ui.label("");
                                 ui.end_row();
                                 for (idx, movement_order) in movement_orders.into_iter().enumerate() {
-                                    let MovementOrderLocation::Station(station_id) = movement_order.go_to;
-                                    let station = game_state.building_state().find_station(station_id).unwrap();
-                                    let reference_tile = station.reference_tile();
+                                    let (reference_tile, cargo) = match movement_order.go_to {
+                                        MovementOrderLocation::Station(station_id) => {
+                                            let station = game_state.building_state().find_station(station_id).unwrap();
+                                            (Some(station.reference_tile()), format!("{:?}", station.cargo()))
+                                        },
+                                        MovementOrderLocation::Waypoint(waypoint_id) => {
+                                            match game_state.building_state().find_waypoint(waypoint_id) {
+                                                Some(waypoint) => (Some(waypoint.tile()), String::new()),
+                                                None => (None, "Waypoint removed".to_string()),
+                                            }
+                                        },
+                                    };
 
                                     let current_order = if idx == movement_orders.next_index() {
                                         "➡ "
@@ -202,10 +287,14 @@ pub(crate) fn show_transport_details(
                                     ui.label(format!("{current_order} {idx}"));
                                     ui.label(format!("{:?}", movement_order.go_to));
                                     ui.label(format!("{:?}", movement_order.action));
-                                    ui.label(format!("{:?}", station.cargo()));
+                                    ui.label(cargo);
 
-                                    if ui.button(format!("🔍 {reference_tile:?}")).clicked() {
-                                        camera_control_events.send(CameraControlEvent::FocusOnTile(reference_tile));
+                                    if let Some(reference_tile) = reference_tile {
+                                        if ui.button(format!("🔍 {reference_tile:?}")).clicked() {
+                                            camera_control_events.send(CameraControlEvent::FocusOnTile(reference_tile));
+                                        }
+                                    } else {
+                                        ui.label("");
                                     }
 
                                     // Later: Remove is disabled if there is only one movement order, as you cannot remove the last one
@@ -230,10 +319,10 @@ pub(crate) fn show_transport_details(
                                 }
                                 if ui.button("➕ Add").clicked() {
                                     info!(
-                                        "Transport {:?}: Switching to station selection in order to add to movement orders",
+                                        "Transport {:?}: Switching to station or waypoint selection in order to add to movement orders",
                                         transport.transport_id(),
                                     );
-                                    *selected_mode = SelectedMode::Select(SelectType::StationToAppendToTransportMovementInstructions(transport.transport_id()));
+                                    *selected_mode = SelectedMode::Select(SelectType::LocationToAppendToTransportMovementInstructions(transport.transport_id()));
                                 };
                                 ui.end_row();
                             });
@@ -1,18 +1,25 @@
 use bevy::prelude::Resource;
-use shared_domain::building::building_info::WithTileCoverage;
+use shared_domain::building::building_info::{WithCostToBuild, WithTileCoverage};
 use shared_domain::building::industry_building_info::IndustryBuildingInfo;
 use shared_domain::building::industry_type::IndustryType;
 use shared_domain::building::military_building_info::MilitaryBuildingInfo;
 use shared_domain::building::military_building_type::MilitaryBuildingType;
+use shared_domain::building::power_line_info::PowerLineInfo;
 use shared_domain::building::station_info::StationInfo;
 use shared_domain::building::station_type::StationType;
+use shared_domain::building::waypoint_info::WaypointInfo;
 use shared_domain::client_command::GameCommand;
 use shared_domain::directional_edge::DirectionalEdge;
 use shared_domain::game_state::GameState;
 use shared_domain::tile_coords_xz::TileCoordsXZ;
 use shared_domain::tile_coverage::TileCoverage;
+use shared_domain::transport::track_grade::TrackGrade;
+use shared_domain::transport::track_type::TrackType;
 use shared_domain::transport::transport_type::TransportType;
-use shared_domain::{IndustryBuildingId, MilitaryBuildingId, PlayerId, StationId, TransportId};
+use shared_domain::{
+    IndustryBuildingId, MilitaryBuildingId, PlayerId, PowerLineId, StationId, TransportId,
+    WaypointId,
+};
 
 #[derive(Resource, Eq, PartialEq, Debug, Clone, Copy)]
 pub enum DemolishType {
@@ -20,11 +27,14 @@ pub enum DemolishType {
     Station,
     Tracks,
     MilitaryBuilding,
+    Waypoint,
+    Bridge,
+    PowerLine,
 }
 
 #[derive(Resource, Eq, PartialEq, Debug, Clone, Copy)]
 pub enum SelectType {
-    StationToAppendToTransportMovementInstructions(TransportId),
+    LocationToAppendToTransportMovementInstructions(TransportId),
 }
 
 #[derive(Resource, Eq, PartialEq, Debug, Clone)]
@@ -38,11 +48,14 @@ pub enum TracksBuildingType {
 pub enum SelectedMode {
     Info,
     Tracks(TracksBuildingType),
+    Waypoints,
+    PowerLines,
     Stations(StationType),
     Industry(IndustryType),
     MilitaryBuilding(MilitaryBuildingType),
     Transport(TransportType),
     Demolish(DemolishType),
+    UpgradeTracks(TrackGrade),
     // Later: This feels like a hack, this is very much not like the others
     Select(SelectType),
 }
@@ -75,15 +88,20 @@ impl SelectedMode {
         &self,
         player_id: PlayerId,
         tile: TileCoordsXZ,
+        game_state: &GameState,
     ) -> Option<GameCommand> {
         match self {
             SelectedMode::Stations(station_type) => {
-                Some(GameCommand::BuildStation(StationInfo::new(
-                    player_id,
-                    StationId::random(),
-                    tile,
-                    *station_type,
-                )))
+                let station_info =
+                    StationInfo::new(player_id, StationId::random(), tile, *station_type);
+                // Placing a station next to one of our own stations extends it, if they line up
+                match game_state
+                    .building_state()
+                    .find_station_expansion(&station_info)
+                {
+                    Some(expanded) => Some(GameCommand::ExpandStation(expanded)),
+                    None => Some(GameCommand::BuildStation(station_info)),
+                }
             },
             SelectedMode::Industry(industry_type) => {
                 Some(GameCommand::BuildIndustryBuilding(
@@ -97,6 +115,20 @@ impl SelectedMode {
             },
             SelectedMode::Info => None,
             SelectedMode::Tracks(_) => None,
+            SelectedMode::Waypoints => {
+                Some(GameCommand::BuildWaypoint(WaypointInfo::new(
+                    WaypointId::random(),
+                    player_id,
+                    tile,
+                )))
+            },
+            SelectedMode::PowerLines => {
+                Some(GameCommand::BuildPowerLine(PowerLineInfo::new(
+                    PowerLineId::random(),
+                    player_id,
+                    tile,
+                )))
+            },
             SelectedMode::MilitaryBuilding(military_building_type) => {
                 Some(GameCommand::BuildMilitaryBuilding(
                     MilitaryBuildingInfo::new(
@@ -109,6 +141,7 @@ impl SelectedMode {
             },
             SelectedMode::Transport(_) => None,
             SelectedMode::Demolish(_) => None,
+            SelectedMode::UpgradeTracks(_) => None,
             SelectedMode::Select(_) => None,
         }
     }
@@ -120,7 +153,7 @@ impl SelectedMode {
         player_id: PlayerId,
         game_state: &GameState,
     ) -> Option<(TileCoverage, bool)> {
-        match self.build_something_command(player_id, reference_tile) {
+        match self.build_something_command(player_id, reference_tile, game_state) {
             Some(GameCommand::BuildStation(station_info)) => {
                 Some((
                     station_info.covers_tiles(),
@@ -129,6 +162,14 @@ impl SelectedMode {
                         .is_ok(),
                 ))
             },
+            Some(GameCommand::ExpandStation(station_info)) => {
+                Some((
+                    station_info.covers_tiles(),
+                    game_state
+                        .can_expand_station(player_id, &station_info)
+                        .is_ok(),
+                ))
+            },
             Some(GameCommand::BuildIndustryBuilding(industry_info)) => {
                 Some((
                     industry_info.covers_tiles(),
@@ -137,6 +178,61 @@ impl SelectedMode {
                         .is_ok(),
                 ))
             },
+            Some(GameCommand::BuildPowerLine(power_line)) => {
+                Some((
+                    power_line.covers_tiles(),
+                    game_state
+                        .can_build_power_line(player_id, &power_line)
+                        .is_ok(),
+                ))
+            },
+            _ => None,
+        }
+    }
+
+    // The type of the player's industries that would pay for what is being placed, so that we can
+    // show their supply ranges
+    #[must_use]
+    pub fn supplying_industry_type(
+        &self,
+        reference_tile: TileCoordsXZ,
+        player_id: PlayerId,
+        game_state: &GameState,
+    ) -> Option<IndustryType> {
+        let (industry_type, _) = match self {
+            SelectedMode::Tracks(_) => TrackType::NorthSouth.cost_to_build(),
+            SelectedMode::Transport(transport_type) => transport_type.cost_to_build(),
+            _ => {
+                match self.build_something_command(player_id, reference_tile, game_state)? {
+                    GameCommand::BuildStation(station_info)
+                    | GameCommand::ExpandStation(station_info) => station_info.cost_to_build(),
+                    GameCommand::BuildIndustryBuilding(industry_info) => {
+                        industry_info.cost_to_build()
+                    },
+                    GameCommand::BuildMilitaryBuilding(military_info) => {
+                        military_info.cost_to_build()
+                    },
+                    GameCommand::BuildWaypoint(waypoint) => waypoint.cost_to_build(),
+                    GameCommand::BuildPowerLine(power_line) => power_line.cost_to_build(),
+                    _ => return None,
+                }
+            },
+        };
+        Some(industry_type)
+    }
+
+    // The area around the station being placed whose industries it would serve
+    #[must_use]
+    pub fn catchment_tiles(
+        &self,
+        reference_tile: TileCoordsXZ,
+        player_id: PlayerId,
+        game_state: &GameState,
+    ) -> Option<TileCoverage> {
+        match self.build_something_command(player_id, reference_tile, game_state) {
+            Some(
+                GameCommand::BuildStation(station_info) | GameCommand::ExpandStation(station_info),
+            ) => Some(station_info.catchment_area()),
             _ => None,
         }
     }
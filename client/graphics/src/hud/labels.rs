@@ -3,17 +3,18 @@ use std::collections::BTreeMap;
 use bevy::prelude::{Camera, EventWriter, GlobalTransform, Query, Res, ResMut, Vec3};
 use bevy_egui::EguiContexts;
 use egui::{Button, CentralPanel, Color32, Context, Frame, Label, Pos2, Rect, RichText, Ui};
-use shared_domain::building::building_info::WithTileCoverage;
+use shared_domain::building::building_info::{WithBuildingDynamicInfo, WithTileCoverage};
 use shared_domain::building::industry_building_info::IndustryBuildingInfo;
 use shared_domain::building::industry_type::IndustryType;
-use shared_domain::cargo_map::WithCargo;
 use shared_domain::client_command::{ClientCommand, GameCommand};
 use shared_domain::game_state::GameState;
+use shared_domain::military::AmmunitionStatus;
 use shared_domain::{GameId, IndustryBuildingId, PlayerId};
 
 use crate::communication::domain::ClientMessageEvent;
 use crate::game::transport::ui::TransportsToShow;
 use crate::game::{GameStateResource, PlayerIdResource, center_vec3};
+use crate::hud::ammunition_status_label;
 use crate::hud::helpers::primary_menu;
 
 // TODO HIGH: This looks ugly and breaks layout. And the mouse cursor changes, but it should not. Consider using https://docs.rs/egui/latest/egui/struct.Painter.html instead? Or https://bevyengine.org/examples/2d-rendering/text2d/ or https://github.com/kulkalkul/bevy_mod_billboard?
@@ -148,7 +149,7 @@ fn draw_industry_building_labels(
         let label = format!(
             "{:?} {:?}",
             industry_building.industry_type(),
-            industry_building.cargo(),
+            industry_building.dynamic_info(),
         );
 
         with_tile_coverage_label(
@@ -171,11 +172,16 @@ fn draw_military_building_labels(
     camera_transform: &GlobalTransform,
 ) {
     for military_building in game_state.building_state().all_military_buildings() {
-        let label = if game_state.time() >= military_building.ready_to_fire_at() {
-            "Ready".to_string()
+        let ammunition_status = military_building.ammunition_status(game_state.building_state());
+        let label = if let AmmunitionStatus::Supplied(_) = ammunition_status {
+            if game_state.time() >= military_building.ready_to_fire_at() {
+                "Ready".to_string()
+            } else {
+                let time_to_ready = military_building.ready_to_fire_at() - game_state.time();
+                format!("Ready in {time_to_ready:?}")
+            }
         } else {
-            let time_to_ready = military_building.ready_to_fire_at() - game_state.time();
-            format!("Ready in {time_to_ready:?}")
+            ammunition_status_label(game_state, ammunition_status)
         };
 
         with_tile_coverage_label(
@@ -198,7 +204,11 @@ fn draw_station_labels(
     camera_transform: &GlobalTransform,
 ) {
     for station in game_state.building_state().all_stations() {
-        let label = format!("{:?} {:?}", station.reference_tile(), station.cargo());
+        let label = format!(
+            "{:?} {:?}",
+            station.reference_tile(),
+            station.dynamic_info()
+        );
 
         with_tile_coverage_label(
             label,
@@ -2,17 +2,19 @@ use bevy::prelude::{EventWriter, Res, ResMut, info};
 use bevy_egui::EguiContexts;
 use egui::Ui;
 use shared_domain::PlayerId;
-use shared_domain::building::building_info::WithOwner;
-use shared_domain::building::building_state::BuildingState;
+use shared_domain::building::building_info::{WithBuildingDynamicInfo, WithOwner};
 use shared_domain::cargo_map::WithCargo;
+use shared_domain::client_command::{ClientCommand, GameCommand};
+use shared_domain::game_state::GameState;
 use shared_domain::military::projectile_info::ProjectileInfo;
 use shared_domain::players::player_state::PlayerState;
 use shared_domain::transport::transport_info::TransportInfo;
 
 use crate::cameras::CameraControlEvent;
+use crate::communication::domain::ClientMessageEvent;
 use crate::game::transport::ui::TransportsToShow;
 use crate::game::{GameStateResource, PlayerIdResource};
-use crate::hud::{PointerOverHud, player_layout_job};
+use crate::hud::{PointerOverHud, ammunition_status_label, player_layout_job};
 
 pub(crate) fn show_left_panel(
     mut contexts: EguiContexts,
@@ -20,6 +22,7 @@ pub(crate) fn show_left_panel(
     mut transport_to_show: ResMut<TransportsToShow>,
     player_id_resource: Option<Res<PlayerIdResource>>,
     mut camera_control_events: EventWriter<CameraControlEvent>,
+    mut client_messages: EventWriter<ClientMessageEvent>,
     mut pointer_over_hud: ResMut<PointerOverHud>,
 ) {
     if let Some(player_id_resource) = player_id_resource {
@@ -35,9 +38,11 @@ pub(crate) fn show_left_panel(
                     buildings_info_panel(
                         ui,
                         *player_id,
-                        game_state.building_state(),
+                        game_state,
                         &mut camera_control_events,
+                        &mut client_messages,
                     );
+                    transport_groups_panel(ui, *player_id, game_state, &mut client_messages);
                     transport_info_panel(
                         ui,
                         *player_id,
@@ -59,23 +64,69 @@ pub(crate) fn show_left_panel(
 fn buildings_info_panel(
     ui: &mut Ui,
     player_id: PlayerId,
-    buildings: &BuildingState,
+    game_state: &GameState,
     camera_control_events: &mut EventWriter<CameraControlEvent>,
+    client_messages: &mut EventWriter<ClientMessageEvent>,
 ) {
+    let buildings = game_state.building_state();
     ui.heading("Industry");
     for building in buildings.find_industry_buildings_by_owner(player_id) {
-        let label = format!(
-            "{:?} {:?}",
+        let mut label = format!(
+            "{:?} {:?} L{}",
             building.reference_tile(),
-            building.industry_type()
+            building.industry_type(),
+            building.level(),
         );
-        if ui.button(label).clicked() {
-            camera_control_events.send(CameraControlEvent::FocusOnTile(building.reference_tile()));
+        if let Some(deposit) = building.deposit() {
+            label.push_str(&format!(" Reserves: {deposit:?}"));
         }
+        if let Some(construction) = building.construction() {
+            label.push_str(&format!(" {construction:?}"));
+        }
+        if let Some(grid) = buildings.power_grids().grid_of_industry(building.id()) {
+            label.push_str(&format!(" ⚡ {grid:?}"));
+        }
+        ui.horizontal(|ui| {
+            if ui.button(label).clicked() {
+                camera_control_events
+                    .send(CameraControlEvent::FocusOnTile(building.reference_tile()));
+            }
+            let can_upgrade = game_state
+                .can_upgrade_industry_building(player_id, building.id())
+                .is_ok();
+            if ui
+                .add_enabled(can_upgrade, egui::Button::new("⏫ Upgrade"))
+                .clicked()
+            {
+                client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+                    game_state.game_id(),
+                    GameCommand::UpgradeIndustryBuilding(building.id()),
+                )));
+            }
+            // When several of these are in range, the preferred one pays for the construction
+            if building.industry_type().supply_range_in_tiles().is_some() {
+                let preferred = buildings.is_preferred_supplier(building.id());
+                if ui
+                    .add(egui::Button::new("⭐ Preferred").selected(preferred))
+                    .clicked()
+                    && !preferred
+                {
+                    client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+                        game_state.game_id(),
+                        GameCommand::SetPreferredSupplier(building.id()),
+                    )));
+                }
+            }
+        });
     }
     ui.heading("Stations");
     for building in buildings.find_stations_by_owner(player_id) {
-        let label = format!("{:?} {:?}", building.reference_tile(), building.cargo());
+        let mut label = format!("{:?} {:?}", building.reference_tile(), building.cargo());
+        // Perishable cargo loses value the longer it waits
+        let cargo_age = format!("{:?}", building.dynamic_info().cargo_age());
+        if !cargo_age.is_empty() {
+            label = format!("{label} (aged {cargo_age})");
+        }
         if ui.button(label).clicked() {
             camera_control_events.send(CameraControlEvent::FocusOnTile(building.reference_tile()));
         }
@@ -83,9 +134,13 @@ fn buildings_info_panel(
     ui.heading("Military");
     for building in buildings.find_military_buildings_by_owner(player_id) {
         let label = format!(
-            "{:?} {:?}",
+            "{:?} {:?} ({})",
             building.reference_tile(),
             building.military_building_type(),
+            ammunition_status_label(
+                game_state,
+                building.ammunition_status(game_state.building_state())
+            ),
         );
         if ui.button(label).clicked() {
             camera_control_events.send(CameraControlEvent::FocusOnTile(building.reference_tile()));
@@ -93,6 +148,37 @@ fn buildings_info_panel(
     }
 }
 
+fn transport_groups_panel(
+    ui: &mut Ui,
+    player_id: PlayerId,
+    game_state: &GameState,
+    client_messages: &mut EventWriter<ClientMessageEvent>,
+) {
+    let transport_state = game_state.transport_state();
+    ui.heading("Lines");
+    for group in transport_state.find_players_groups(player_id) {
+        let group_id = group.group_id();
+        let statistics = transport_state.group_statistics(group_id);
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} ({} transports, {} stopped) {:?}",
+                group.name(),
+                statistics.transports,
+                statistics.force_stopped,
+                statistics.cargo_loaded,
+            ));
+            for (label, force_stop) in [("▶ Start", false), ("⏹ Stop", true)] {
+                if ui.button(label).clicked() {
+                    client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+                        game_state.game_id(),
+                        GameCommand::SetTransportGroupForceStop(group_id, force_stop),
+                    )));
+                }
+            }
+        });
+    }
+}
+
 fn transport_info_panel(
     ui: &mut Ui,
     player_id: PlayerId,
@@ -5,11 +5,16 @@ use bevy::prelude::{IntoSystemConfigs, ResMut, Resource, default, in_state};
 use bevy_egui::EguiPlugin;
 use egui::text::LayoutJob;
 use egui::{Color32, TextFormat, Ui};
-use shared_domain::PlayerId;
+use shared_domain::game_state::GameState;
+use shared_domain::military::AmmunitionStatus;
 use shared_domain::server_response::PlayerInfo;
+use shared_domain::{IndustryBuildingId, PlayerId};
 
 use crate::hud::domain::SelectedMode;
 use crate::hud::labels::draw_labels;
+use crate::hud::statistics::StatisticsResource;
+use crate::hud::throughput::ThroughputResource;
+use crate::hud::trade::TradeResource;
 use crate::states::ClientState;
 
 pub mod bottom_panel;
@@ -17,7 +22,10 @@ pub mod domain;
 mod helpers;
 pub mod labels;
 pub mod left_panel;
+mod statistics;
+mod throughput;
 pub mod top_panel;
+mod trade;
 
 #[derive(Resource, Default)]
 pub struct PointerOverHud {
@@ -54,6 +62,9 @@ impl Plugin for HudPlugin {
         }
         app.insert_resource(SelectedMode::Info);
         app.insert_resource(PointerOverHud::default());
+        app.insert_resource(StatisticsResource::default());
+        app.insert_resource(ThroughputResource::default());
+        app.insert_resource(TradeResource::default());
 
         app.add_systems(
             Update,
@@ -77,6 +88,28 @@ impl Plugin for HudPlugin {
                 .after(top_panel::show_top_panel)
                 .run_if(in_state(ClientState::Playing)),
         );
+        app.add_systems(
+            Update,
+            statistics::show_statistics_window
+                .after(left_panel::show_left_panel)
+                .run_if(in_state(ClientState::Playing)),
+        );
+        app.add_systems(
+            Update,
+            throughput::show_throughput_window
+                .after(statistics::show_statistics_window)
+                .run_if(in_state(ClientState::Playing)),
+        );
+        app.add_systems(
+            Update,
+            trade::show_trade_window
+                .after(throughput::show_throughput_window)
+                .run_if(in_state(ClientState::Playing)),
+        );
+        app.add_systems(
+            Update,
+            statistics::handle_statistics_received.run_if(in_state(ClientState::Playing)),
+        );
         app.add_systems(
             Update,
             draw_labels
@@ -111,3 +144,29 @@ pub fn player_layout_job(own_player_id: PlayerId, player_info: &PlayerInfo) -> L
 
     job
 }
+
+// Shows which military base the artillery draws its shots from
+#[must_use]
+pub fn ammunition_status_label(game_state: &GameState, status: AmmunitionStatus) -> String {
+    let base_label = |industry_building_id: IndustryBuildingId| {
+        game_state
+            .building_state()
+            .find_industry_building(industry_building_id)
+            .map_or_else(
+                || format!("{industry_building_id:?}"),
+                |building| format!("{:?}", building.reference_tile()),
+            )
+    };
+    match status {
+        AmmunitionStatus::NoSupplier => "No military base in range".to_string(),
+        AmmunitionStatus::OutOfAmmunition(industry_building_id) => {
+            format!(
+                "Out of ammunition at {}, shot queued",
+                base_label(industry_building_id)
+            )
+        },
+        AmmunitionStatus::Supplied(industry_building_id) => {
+            format!("Ammunition from {}", base_label(industry_building_id))
+        },
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::prelude::{EventReader, EventWriter, Res, ResMut, Resource, Time, Timer, TimerMode};
+use bevy_egui::EguiContexts;
+use egui::{Color32, Pos2, Sense, Shape, Stroke, Ui};
+use shared_domain::client_command::{ClientCommand, GameCommand};
+use shared_domain::game_state::GameState;
+use shared_domain::resource_type::ResourceType;
+use shared_domain::server_response::{GameResponse, ServerResponse};
+use shared_domain::statistics::{StatisticsSample, StatisticsState};
+
+use crate::communication::domain::{ClientMessageEvent, ServerMessageEvent};
+use crate::game::GameStateResource;
+use crate::hud::PointerOverHud;
+
+// How often we ask the server for the statistics while the window is expanded
+const REFRESH_EVERY_SECONDS: f32 = 2.0;
+const CHART_WIDTH: f32 = 480.0;
+const CHART_HEIGHT: f32 = 240.0;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum StatisticsMetric {
+    Produced(ResourceType),
+    Consumed(ResourceType),
+    CargoDelivered,
+    BuildingsBuilt,
+    ProjectilesFired,
+    Treasury,
+}
+
+impl StatisticsMetric {
+    fn all() -> Vec<Self> {
+        let mut result = vec![
+            StatisticsMetric::Treasury,
+            StatisticsMetric::CargoDelivered,
+            StatisticsMetric::BuildingsBuilt,
+            StatisticsMetric::ProjectilesFired,
+        ];
+        for resource in ResourceType::all() {
+            result.push(StatisticsMetric::Produced(resource));
+        }
+        for resource in ResourceType::all() {
+            result.push(StatisticsMetric::Consumed(resource));
+        }
+        result
+    }
+
+    fn name(self) -> String {
+        match self {
+            StatisticsMetric::Produced(resource) => format!("Produced {resource:?}"),
+            StatisticsMetric::Consumed(resource) => format!("Consumed {resource:?}"),
+            StatisticsMetric::CargoDelivered => "Cargo Delivered".to_string(),
+            StatisticsMetric::BuildingsBuilt => "Buildings Built".to_string(),
+            StatisticsMetric::ProjectilesFired => "Projectiles Fired".to_string(),
+            StatisticsMetric::Treasury => "Treasury".to_string(),
+        }
+    }
+
+    #[expect(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn value(self, sample: &StatisticsSample) -> f32 {
+        match self {
+            StatisticsMetric::Produced(resource) => sample.produced().get(resource).as_f32(),
+            StatisticsMetric::Consumed(resource) => sample.consumed().get(resource).as_f32(),
+            StatisticsMetric::CargoDelivered => sample.cargo_delivered().as_f32(),
+            StatisticsMetric::BuildingsBuilt => sample.buildings_built() as f32,
+            StatisticsMetric::ProjectilesFired => sample.projectiles_fired() as f32,
+            StatisticsMetric::Treasury => sample.treasury().as_f64() as f32,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct StatisticsResource {
+    statistics:    StatisticsState,
+    metric:        StatisticsMetric,
+    refresh_timer: Timer,
+}
+
+impl Default for StatisticsResource {
+    fn default() -> Self {
+        Self {
+            statistics:    StatisticsState::default(),
+            metric:        StatisticsMetric::Treasury,
+            refresh_timer: Timer::from_seconds(REFRESH_EVERY_SECONDS, TimerMode::Repeating),
+        }
+    }
+}
+
+pub(crate) fn handle_statistics_received(
+    mut server_messages: EventReader<ServerMessageEvent>,
+    mut statistics_resource: ResMut<StatisticsResource>,
+) {
+    for message in server_messages.read() {
+        if let ServerResponse::Game(_game_id, GameResponse::Statistics(statistics)) =
+            &message.response
+        {
+            statistics_resource.statistics = statistics.clone();
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value)]
+pub(crate) fn show_statistics_window(
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    game_state_resource: Res<GameStateResource>,
+    mut statistics_resource: ResMut<StatisticsResource>,
+    mut pointer_over_hud: ResMut<PointerOverHud>,
+    mut client_messages: EventWriter<ClientMessageEvent>,
+) {
+    let GameStateResource(game_state) = game_state_resource.as_ref();
+    let StatisticsResource {
+        statistics,
+        metric,
+        refresh_timer,
+    } = statistics_resource.as_mut();
+
+    let expanded = egui::Window::new("📈 Statistics")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            pointer_over_hud.apply(ui);
+            egui::ComboBox::from_label("Metric")
+                .selected_text(metric.name())
+                .show_ui(ui, |ui| {
+                    for option in StatisticsMetric::all() {
+                        ui.selectable_value(metric, option, option.name());
+                    }
+                });
+            draw_chart(ui, game_state, statistics, *metric);
+        })
+        .is_some_and(|response| response.inner.is_some());
+
+    // We only keep asking while someone is looking at the charts
+    if expanded {
+        refresh_timer.tick(time.delta());
+        if refresh_timer.just_finished() {
+            client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+                game_state.game_id,
+                GameCommand::RequestStatistics,
+            )));
+        }
+    }
+}
+
+// One line per player, in the player's colour, with the time on the horizontal axis
+fn draw_chart(
+    ui: &mut Ui,
+    game_state: &GameState,
+    statistics: &StatisticsState,
+    metric: StatisticsMetric,
+) {
+    let series = game_state
+        .players()
+        .infos()
+        .into_iter()
+        .map(|player_info| {
+            let colour = player_info.colour;
+            let points = statistics
+                .history(player_info.id)
+                .into_iter()
+                .map(|sample| (sample.at().to_seconds(), metric.value(sample)))
+                .collect::<Vec<_>>();
+            (Color32::from_rgb(colour.r, colour.g, colour.b), points)
+        })
+        .collect::<Vec<_>>();
+
+    let all_points = series.iter().flat_map(|(_, points)| points);
+    let (min_x, max_x, min_y, max_y) = all_points.fold(
+        (f32::MAX, f32::MIN, 0.0f32, 0.0f32),
+        |(min_x, max_x, min_y, max_y), (x, y)| {
+            (min_x.min(*x), max_x.max(*x), min_y.min(*y), max_y.max(*y))
+        },
+    );
+
+    let (response, painter) =
+        ui.allocate_painter(egui::vec2(CHART_WIDTH, CHART_HEIGHT), Sense::hover());
+    let rect = response.rect;
+    painter.rect_stroke(rect, 0.0, Stroke::new(1.0, Color32::GRAY));
+
+    if min_x >= max_x {
+        ui.label("Not enough data yet");
+        return;
+    }
+
+    let span_y = if max_y > min_y { max_y - min_y } else { 1.0 };
+    let to_screen = |(x, y): &(f32, f32)| {
+        Pos2::new(
+            rect.left() + (x - min_x) / (max_x - min_x) * rect.width(),
+            rect.bottom() - (y - min_y) / span_y * rect.height(),
+        )
+    };
+
+    for (colour, points) in &series {
+        let points = points.iter().map(to_screen).collect::<Vec<_>>();
+        painter.add(Shape::line(points, Stroke::new(2.0, *colour)));
+    }
+
+    let text_colour = ui.visuals().text_color();
+    let font = egui::FontId::proportional(14.0);
+    painter.text(
+        rect.left_top(),
+        egui::Align2::LEFT_TOP,
+        format!("{max_y:.0}"),
+        font.clone(),
+        text_colour,
+    );
+    painter.text(
+        rect.left_bottom(),
+        egui::Align2::LEFT_BOTTOM,
+        format!("{min_y:.0}"),
+        font.clone(),
+        text_colour,
+    );
+    painter.text(
+        rect.right_bottom(),
+        egui::Align2::RIGHT_BOTTOM,
+        format!("{:.0}s", max_x - min_x),
+        font,
+        text_colour,
+    );
+}
@@ -0,0 +1,112 @@
+use bevy::prelude::{Res, ResMut, Resource, Time, Timer, TimerMode};
+use bevy_egui::EguiContexts;
+use egui::{Color32, RichText, Ui};
+use shared_domain::supply_chain::throughput::{Bottleneck, LinkThroughput, ThroughputAnalysis};
+
+use crate::game::{GameStateResource, PlayerIdResource};
+use crate::hud::PointerOverHud;
+
+// The analysis involves pathfinding, so we do not redo it every frame
+const REFRESH_EVERY_SECONDS: f32 = 1.0;
+
+#[derive(Resource)]
+pub(crate) struct ThroughputResource {
+    analysis:      ThroughputAnalysis,
+    refresh_timer: Timer,
+}
+
+impl Default for ThroughputResource {
+    fn default() -> Self {
+        Self {
+            analysis:      ThroughputAnalysis::default(),
+            refresh_timer: Timer::from_seconds(REFRESH_EVERY_SECONDS, TimerMode::Repeating),
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value)]
+pub(crate) fn show_throughput_window(
+    mut contexts: EguiContexts,
+    time: Res<Time>,
+    game_state_resource: Res<GameStateResource>,
+    player_id_resource: Res<PlayerIdResource>,
+    mut throughput_resource: ResMut<ThroughputResource>,
+    mut pointer_over_hud: ResMut<PointerOverHud>,
+) {
+    let GameStateResource(game_state) = game_state_resource.as_ref();
+    let PlayerIdResource(player_id) = player_id_resource.as_ref();
+    let ThroughputResource {
+        analysis,
+        refresh_timer,
+    } = throughput_resource.as_mut();
+
+    let expanded = egui::Window::new("🚂 Throughput")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            pointer_over_hud.apply(ui);
+            links_grid(ui, analysis);
+        })
+        .is_some_and(|response| response.inner.is_some());
+
+    if expanded {
+        refresh_timer.tick(time.delta());
+        if refresh_timer.just_finished() {
+            *analysis = ThroughputAnalysis::analyse(*player_id, game_state);
+        }
+    }
+}
+
+fn links_grid(ui: &mut Ui, analysis: &ThroughputAnalysis) {
+    if analysis.links().is_empty() {
+        ui.label("No trains are carrying cargo between industries yet");
+        return;
+    }
+
+    egui::Grid::new("throughput_grid")
+        .striped(true)
+        .show(ui, |ui| {
+            for header in [
+                "Resource",
+                "From",
+                "To",
+                "Carried / s",
+                "Supply / s",
+                "Demand / s",
+                "Trains",
+                "Bottleneck",
+            ] {
+                ui.label(RichText::new(header).strong());
+            }
+            ui.end_row();
+
+            for link in analysis.links() {
+                link_row(ui, link);
+                ui.end_row();
+            }
+        });
+}
+
+fn link_row(ui: &mut Ui, link: &LinkThroughput) {
+    ui.label(format!("{:?}", link.resource()));
+    ui.label(format!("{:?}", link.from_station_id()));
+    ui.label(format!("{:?}", link.to_station_id()));
+    ui.label(format!("{:.2}", link.throughput_per_second()));
+    ui.label(format!("{:.2}", link.supply_per_second()));
+    if link.demand_per_second().is_finite() {
+        ui.label(format!("{:.2}", link.demand_per_second()));
+    } else {
+        ui.label("∞");
+    }
+    ui.label(format!(
+        "{} of {}",
+        link.trains(),
+        link.recommended_trains()
+    ));
+    let (text, colour) = match link.bottleneck() {
+        Bottleneck::Supply => ("Supply", Color32::GRAY),
+        Bottleneck::Demand => ("Demand", Color32::GRAY),
+        Bottleneck::Trains => ("Trains", Color32::YELLOW),
+        Bottleneck::NoRoute => ("No Route", Color32::RED),
+    };
+    ui.label(RichText::new(text).color(colour));
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::{AppExit, EventWriter, Res, ResMut};
 use bevy_egui::EguiContexts;
 use egui::{Ui, menu};
+use shared_domain::PlayerId;
 use shared_domain::building::industry_type::IndustryType;
 use shared_domain::building::military_building_type::MilitaryBuildingType;
 use shared_domain::building::station_type::{StationOrientation, StationType};
@@ -8,11 +9,12 @@ use shared_domain::client_command::{ClientCommand, GameCommand};
 use shared_domain::game_state::GameState;
 use shared_domain::game_time::TimeFactor;
 use shared_domain::resource_type::ResourceType;
+use shared_domain::transport::track_grade::TrackGrade;
 use shared_domain::transport::transport_type::TransportType;
 
 use crate::ai::ArtificialIntelligenceResource;
 use crate::communication::domain::ClientMessageEvent;
-use crate::game::GameStateResource;
+use crate::game::{GameStateResource, PlayerIdResource};
 use crate::hud::PointerOverHud;
 use crate::hud::domain::{DemolishType, SelectedMode, TracksBuildingType};
 
@@ -26,11 +28,13 @@ pub(crate) fn show_top_panel(
     mut selected_mode: ResMut<SelectedMode>,
     mut ai_resource: ResMut<ArtificialIntelligenceResource>,
     mut pointer_over_hud: ResMut<PointerOverHud>,
+    player_id_resource: Res<PlayerIdResource>,
     mut exit: EventWriter<AppExit>,
     mut client_messages: EventWriter<ClientMessageEvent>,
 ) {
     // Later: We need to better depict the current building mode in the main menu, in case it's a sub-menu item that is selected
     let GameStateResource(game_state) = game_state.as_ref();
+    let PlayerIdResource(player_id) = player_id_resource.as_ref();
 
     egui::TopBottomPanel::top("hud_top_panel").show(contexts.ctx_mut(), |ui| {
         pointer_over_hud.apply(ui);
@@ -43,6 +47,9 @@ pub(crate) fn show_top_panel(
             // Later: Landscaping for terrain modification
             info_menu(&mut selected_mode, ui);
             tracks_menu(&mut selected_mode, ui);
+            upgrade_tracks_menu(&mut selected_mode, &mut pointer_over_hud, ui);
+            waypoints_menu(&mut selected_mode, ui);
+            power_lines_menu(&mut selected_mode, ui);
             stations_menu(&mut selected_mode, &mut pointer_over_hud, ui);
             industry_menu(&mut selected_mode, &mut pointer_over_hud, ui);
             military_menu(&mut selected_mode, &mut pointer_over_hud, ui);
@@ -56,10 +63,16 @@ pub(crate) fn show_top_panel(
                 &mut pointer_over_hud,
                 ui,
             );
+            treasury_label(game_state, *player_id, ui);
         });
     });
 }
 
+fn treasury_label(game_state: &GameState, player_id: PlayerId, ui: &mut Ui) {
+    let treasury = game_state.players().treasury(player_id);
+    ui.label(egui::RichText::new(format!("💰 {treasury}")).size(24.0));
+}
+
 fn set_font_size(ui: &mut Ui, size: f32) {
     ui.style_mut().text_styles.insert(
         egui::TextStyle::Button,
@@ -95,6 +108,63 @@ fn tracks_menu(selected_mode: &mut ResMut<SelectedMode>, ui: &mut Ui) {
     }
 }
 
+fn upgrade_tracks_menu(
+    selected_mode: &mut ResMut<SelectedMode>,
+    pointer_over_hud: &mut ResMut<PointerOverHud>,
+    ui: &mut Ui,
+) {
+    menu::menu_button(ui, "⏫ Upgrade", |ui| {
+        pointer_over_hud.apply(ui);
+        set_font_size(ui, 24.0);
+
+        for grade in TrackGrade::all() {
+            if grade == TrackGrade::default() {
+                continue;
+            }
+            let mode = SelectedMode::UpgradeTracks(grade);
+            if ui
+                .add(
+                    egui::Button::new(format!("🚆 {grade:?}"))
+                        .selected(*selected_mode.as_ref() == mode)
+                        .min_size(egui::vec2(MIN_X, MIN_Y)),
+                )
+                .clicked()
+            {
+                *selected_mode.as_mut() = mode;
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+fn waypoints_menu(selected_mode: &mut ResMut<SelectedMode>, ui: &mut Ui) {
+    if ui
+        .add(
+            egui::Button::new("📍 Waypoints")
+                .selected(matches!(*selected_mode.as_ref(), SelectedMode::Waypoints))
+                .min_size(egui::vec2(MIN_X, MIN_Y)),
+        )
+        .clicked()
+    {
+        *selected_mode.as_mut() = SelectedMode::Waypoints;
+        ui.close_menu();
+    }
+}
+
+fn power_lines_menu(selected_mode: &mut ResMut<SelectedMode>, ui: &mut Ui) {
+    if ui
+        .add(
+            egui::Button::new("⚡ Power Lines")
+                .selected(matches!(*selected_mode.as_ref(), SelectedMode::PowerLines))
+                .min_size(egui::vec2(MIN_X, MIN_Y)),
+        )
+        .clicked()
+    {
+        *selected_mode.as_mut() = SelectedMode::PowerLines;
+        ui.close_menu();
+    }
+}
+
 fn stations_menu(
     selected_mode: &mut ResMut<SelectedMode>,
     pointer_over_hud: &mut ResMut<PointerOverHud>,
@@ -222,20 +292,50 @@ fn trains_menu(
         set_font_size(ui, 24.0);
 
         for resource_type in ResourceType::all() {
-            let transport_type = TransportType::cargo_train(resource_type);
-            let selected_mode = selected_mode_res.as_ref();
-            if ui
-                .add(
-                    egui::Button::new(format!("🚆 {resource_type:?} Train"))
-                        .selected(*selected_mode == SelectedMode::Transport(transport_type.clone()))
-                        .min_size(egui::vec2(MIN_X, MIN_Y)),
-                )
-                .clicked()
-            {
-                *selected_mode_res.as_mut() = SelectedMode::Transport(transport_type);
-                ui.close_menu();
+            if !resource_type.is_transportable() {
+                continue;
+            }
+            for (name, transport_type) in [
+                (
+                    format!("🚆 {resource_type:?} Train"),
+                    TransportType::cargo_train(resource_type),
+                ),
+                (
+                    format!("⚡ {resource_type:?} Electric Train"),
+                    TransportType::electric_cargo_train(resource_type),
+                ),
+            ] {
+                let selected_mode = selected_mode_res.as_ref();
+                if ui
+                    .add(
+                        egui::Button::new(name)
+                            .selected(
+                                *selected_mode == SelectedMode::Transport(transport_type.clone()),
+                            )
+                            .min_size(egui::vec2(MIN_X, MIN_Y)),
+                    )
+                    .clicked()
+                {
+                    *selected_mode_res.as_mut() = SelectedMode::Transport(transport_type);
+                    ui.close_menu();
+                }
             }
         }
+
+        ui.separator();
+        let transport_type = TransportType::railway_artillery();
+        let selected_mode = selected_mode_res.as_ref();
+        if ui
+            .add(
+                egui::Button::new("💥 Railway Artillery")
+                    .selected(*selected_mode == SelectedMode::Transport(transport_type.clone()))
+                    .min_size(egui::vec2(MIN_X, MIN_Y)),
+            )
+            .clicked()
+        {
+            *selected_mode_res.as_mut() = SelectedMode::Transport(transport_type);
+            ui.close_menu();
+        }
     });
 }
 
@@ -257,6 +357,15 @@ fn demolish_menu(
                 "⚔ Military Building",
                 SelectedMode::Demolish(DemolishType::MilitaryBuilding),
             ),
+            (
+                "📍 Waypoint",
+                SelectedMode::Demolish(DemolishType::Waypoint),
+            ),
+            ("🌉 Bridge", SelectedMode::Demolish(DemolishType::Bridge)),
+            (
+                "⚡ Power Line",
+                SelectedMode::Demolish(DemolishType::PowerLine),
+            ),
         ] {
             if ui
                 .add(
@@ -0,0 +1,264 @@
+use bevy::prelude::{EventWriter, Res, ResMut, Resource};
+use bevy_egui::EguiContexts;
+use egui::{RichText, Ui};
+use shared_domain::cargo_map::CargoMap;
+use shared_domain::client_command::{ClientCommand, GameCommand};
+use shared_domain::game_state::GameState;
+use shared_domain::players::trade_offer::TradeOffer;
+use shared_domain::resource_type::ResourceType;
+use shared_domain::{PlayerId, StationId, TradeOfferId};
+
+use crate::communication::domain::ClientMessageEvent;
+use crate::game::{GameStateResource, PlayerIdResource};
+use crate::hud::PointerOverHud;
+
+// The offer being put together, and the station to receive the cargo of the offers we accept
+#[derive(Resource)]
+pub(crate) struct TradeResource {
+    from_station_id:    Option<StationId>,
+    offered_resource:   ResourceType,
+    offered_amount:     f32,
+    to_player_id:       Option<PlayerId>,
+    requested_resource: ResourceType,
+    requested_amount:   f32,
+    accepting_station:  Option<StationId>,
+}
+
+impl Default for TradeResource {
+    fn default() -> Self {
+        Self {
+            from_station_id:    None,
+            offered_resource:   ResourceType::Coal,
+            offered_amount:     0.0,
+            to_player_id:       None,
+            requested_resource: ResourceType::Coal,
+            requested_amount:   0.0,
+            accepting_station:  None,
+        }
+    }
+}
+
+#[expect(clippy::needless_pass_by_value)]
+pub(crate) fn show_trade_window(
+    mut contexts: EguiContexts,
+    game_state_resource: Res<GameStateResource>,
+    player_id_resource: Res<PlayerIdResource>,
+    mut trade_resource: ResMut<TradeResource>,
+    mut pointer_over_hud: ResMut<PointerOverHud>,
+    mut client_messages: EventWriter<ClientMessageEvent>,
+) {
+    let GameStateResource(game_state) = game_state_resource.as_ref();
+    let PlayerIdResource(player_id) = player_id_resource.as_ref();
+
+    let mut commands = vec![];
+    egui::Window::new("🤝 Trade")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            pointer_over_hud.apply(ui);
+            offers_list(
+                ui,
+                game_state,
+                *player_id,
+                trade_resource.as_mut(),
+                &mut commands,
+            );
+            ui.separator();
+            new_offer_form(
+                ui,
+                game_state,
+                *player_id,
+                trade_resource.as_mut(),
+                &mut commands,
+            );
+        });
+
+    for command in commands {
+        client_messages.send(ClientMessageEvent::new(ClientCommand::Game(
+            game_state.game_id,
+            command,
+        )));
+    }
+}
+
+fn station_label(game_state: &GameState, station_id: StationId) -> String {
+    game_state
+        .building_state()
+        .find_station(station_id)
+        .map_or_else(
+            || format!("{station_id:?}"),
+            |station| format!("{station_id:?} {:?}", station.reference_tile()),
+        )
+}
+
+fn player_label(game_state: &GameState, player_id: PlayerId) -> String {
+    game_state
+        .players()
+        .get(player_id)
+        .map_or_else(|| format!("{player_id:?}"), |info| info.name.to_string())
+}
+
+fn cargo_label(cargo: &CargoMap) -> String {
+    if cargo.resource_types_present().is_empty() {
+        "nothing".to_string()
+    } else {
+        format!("{cargo:?}")
+    }
+}
+
+fn offers_list(
+    ui: &mut Ui,
+    game_state: &GameState,
+    player_id: PlayerId,
+    trade_resource: &mut TradeResource,
+    commands: &mut Vec<GameCommand>,
+) {
+    let offers = game_state
+        .players()
+        .trade_offers()
+        .into_iter()
+        .filter(|offer| offer.involves(player_id))
+        .collect::<Vec<_>>();
+
+    if offers.is_empty() {
+        ui.label("No trade offers");
+        return;
+    }
+
+    own_station_combo(
+        ui,
+        "Receive at",
+        game_state,
+        player_id,
+        &mut trade_resource.accepting_station,
+    );
+
+    for offer in offers {
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} offers {} from {} for {} from {}",
+                player_label(game_state, offer.from_player_id()),
+                cargo_label(offer.offered()),
+                station_label(game_state, offer.from_station_id()),
+                cargo_label(offer.requested()),
+                player_label(game_state, offer.to_player_id()),
+            ));
+            if offer.to_player_id() == player_id {
+                if let Some(station_id) = trade_resource.accepting_station {
+                    if ui.button("Accept").clicked() {
+                        commands.push(GameCommand::AcceptTradeOffer(offer.id(), station_id));
+                    }
+                }
+                if ui.button("Decline").clicked() {
+                    commands.push(GameCommand::CancelTradeOffer(offer.id()));
+                }
+            } else if ui.button("Withdraw").clicked() {
+                commands.push(GameCommand::CancelTradeOffer(offer.id()));
+            }
+        });
+    }
+}
+
+fn new_offer_form(
+    ui: &mut Ui,
+    game_state: &GameState,
+    player_id: PlayerId,
+    trade_resource: &mut TradeResource,
+    commands: &mut Vec<GameCommand>,
+) {
+    ui.label(RichText::new("New Offer").strong());
+
+    own_station_combo(
+        ui,
+        "From",
+        game_state,
+        player_id,
+        &mut trade_resource.from_station_id,
+    );
+    resource_amount(
+        ui,
+        "Offer",
+        &mut trade_resource.offered_resource,
+        &mut trade_resource.offered_amount,
+    );
+
+    egui::ComboBox::from_label("To")
+        .selected_text(
+            trade_resource
+                .to_player_id
+                .map_or_else(String::new, |id| player_label(game_state, id)),
+        )
+        .show_ui(ui, |ui| {
+            for info in game_state.players().infos() {
+                if info.id != player_id {
+                    ui.selectable_value(
+                        &mut trade_resource.to_player_id,
+                        Some(info.id),
+                        info.name.to_string(),
+                    );
+                }
+            }
+        });
+    resource_amount(
+        ui,
+        "For",
+        &mut trade_resource.requested_resource,
+        &mut trade_resource.requested_amount,
+    );
+
+    if let (Some(from_station_id), Some(to_player_id)) =
+        (trade_resource.from_station_id, trade_resource.to_player_id)
+    {
+        if ui.button("Offer").clicked() {
+            commands.push(GameCommand::OfferTrade(TradeOffer::new(
+                TradeOfferId::random(),
+                player_id,
+                from_station_id,
+                CargoMap::single(
+                    trade_resource.offered_resource,
+                    trade_resource.offered_amount,
+                ),
+                to_player_id,
+                CargoMap::single(
+                    trade_resource.requested_resource,
+                    trade_resource.requested_amount,
+                ),
+            )));
+        }
+    }
+}
+
+fn own_station_combo(
+    ui: &mut Ui,
+    label: &str,
+    game_state: &GameState,
+    player_id: PlayerId,
+    selected: &mut Option<StationId>,
+) {
+    egui::ComboBox::from_label(label)
+        .selected_text(selected.map_or_else(String::new, |id| station_label(game_state, id)))
+        .show_ui(ui, |ui| {
+            for station in game_state
+                .building_state()
+                .find_stations_by_owner(player_id)
+            {
+                ui.selectable_value(
+                    selected,
+                    Some(station.id()),
+                    station_label(game_state, station.id()),
+                );
+            }
+        });
+}
+
+fn resource_amount(ui: &mut Ui, label: &str, resource: &mut ResourceType, amount: &mut f32) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label(label)
+            .selected_text(format!("{resource:?}"))
+            .show_ui(ui, |ui| {
+                for option in ResourceType::all() {
+                    ui.selectable_value(resource, option, format!("{option:?}"));
+                }
+            });
+        ui.add(egui::DragValue::new(amount).range(0.0 ..= 1000.0));
+    });
+}
@@ -2,7 +2,7 @@
 
 use bevy::app::App;
 use bevy::color::palettes::basic::LIME;
-use bevy::color::palettes::css::{PINK, PURPLE, TOMATO};
+use bevy::color::palettes::css::{GOLD, LIGHT_SKY_BLUE, PINK, PURPLE, TOMATO};
 use bevy::input::ButtonInput;
 use bevy::picking::pointer::PointerInteraction;
 use bevy::prelude::{
@@ -202,6 +202,17 @@ fn highlight_selected_tiles(
                 debug_draw_tile(&mut gizmos, *hovered_tile, tiles, PINK);
             }
 
+            if let Some(industry_type) =
+                selected_mode.supplying_industry_type(*hovered_tile, player_id, game_state)
+            {
+                for tile in game_state
+                    .building_state()
+                    .supply_range_outlines(player_id, industry_type)
+                {
+                    debug_draw_tile(&mut gizmos, tile, tiles, GOLD);
+                }
+            }
+
             if let Some((coverage, valid)) =
                 selected_mode.building_tiles(*hovered_tile, player_id, game_state)
             {
@@ -209,6 +220,16 @@ fn highlight_selected_tiles(
                 for tile in coverage {
                     debug_draw_tile(&mut gizmos, tile, tiles, color);
                 }
+
+                if let Some(catchment) =
+                    selected_mode.catchment_tiles(*hovered_tile, player_id, game_state)
+                {
+                    for tile in catchment {
+                        if !coverage.contains(tile) {
+                            debug_draw_tile(&mut gizmos, tile, tiles, LIGHT_SKY_BLUE);
+                        }
+                    }
+                }
             }
         }
     }
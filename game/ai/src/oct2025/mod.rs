@@ -3,7 +3,9 @@ mod military;
 mod resource_links;
 mod stations;
 mod supply_chains;
+mod train_factories;
 mod transports;
+mod upgrades;
 
 use std::fmt::Debug;
 
@@ -18,6 +20,8 @@ use shared_domain::server_response::GameResponse;
 use crate::ArtificialIntelligenceState;
 use crate::oct2025::military::MilitaryBasesAI;
 use crate::oct2025::supply_chains::BuildSupplyChains;
+use crate::oct2025::train_factories::ConnectTrainFactoryAI;
+use crate::oct2025::upgrades::UpgradeBottlenecksAI;
 
 #[derive(PartialEq)]
 enum GoalResult {
@@ -117,15 +121,40 @@ impl Oct2025ArtificialIntelligenceState {
         let construction_yard = construction_yards[0];
         let construction_yard_location = construction_yard.reference_tile();
         let construction_yard_id = construction_yard.id();
-        let pending_goals: Vec<Box<dyn Goal + Send + Sync>> = vec![
-            Box::new(BuildSupplyChains::for_known_target(
+        let train_factories = game_state
+            .building_state()
+            .find_industry_buildings_by_owner_and_type(player_id, IndustryType::TrainFactory)
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        // Trains can only be bought at the train factories, so they are connected first
+        let mut pending_goals: Vec<Box<dyn Goal + Send + Sync>> = train_factories
+            .iter()
+            .map(|train_factory| {
+                Box::new(ConnectTrainFactoryAI::for_train_factory(train_factory))
+                    as Box<dyn Goal + Send + Sync>
+            })
+            .collect();
+
+        pending_goals.push(Box::new(BuildSupplyChains::for_known_target(
+            game_state.supply_chain(),
+            IndustryType::ConstructionYard,
+            construction_yard_location,
+            construction_yard_id,
+        )));
+
+        // New trains need rolling stock, so the train factories need to be kept supplied
+        for train_factory in train_factories {
+            pending_goals.push(Box::new(BuildSupplyChains::for_known_target(
                 game_state.supply_chain(),
-                IndustryType::ConstructionYard,
-                construction_yard_location,
-                construction_yard_id,
-            )) as Box<dyn Goal + Send + Sync>,
-            Box::new(MilitaryBasesAI::new()) as Box<dyn Goal + Send + Sync>,
-        ];
+                IndustryType::TrainFactory,
+                train_factory.reference_tile(),
+                train_factory.id(),
+            )));
+        }
+
+        pending_goals.push(Box::new(MilitaryBasesAI::new()));
+        pending_goals.push(Box::new(UpgradeBottlenecksAI::new()));
 
         Self {
             player_id,
@@ -8,6 +8,7 @@ use shared_domain::game_state::GameState;
 use shared_domain::metrics::Metrics;
 use shared_domain::resource_type::ResourceType;
 use shared_domain::server_response::{GameError, GameResponse};
+use shared_domain::supply_chain::throughput::{Bottleneck, LinkThroughput, ThroughputAnalysis};
 use shared_domain::transport::tile_track::TileTrack;
 use shared_domain::transport::track_length::TrackLength;
 use shared_domain::transport::track_planner::{DEFAULT_ALREADY_EXISTS_COEF, plan_tracks};
@@ -41,7 +42,7 @@ pub(crate) enum ResourceLinkState {
     TrainsPurchased,
 }
 
-fn track_pairs(
+pub(crate) fn track_pairs(
     game_state: &GameState,
     from_station_id: StationId,
     to_station_id: StationId,
@@ -60,6 +61,61 @@ fn track_pairs(
     Some(pairs)
 }
 
+// Builds the tracks for the next of the pending pairs of exits, or returns `None` once all of them
+// have been built
+pub(crate) fn build_next_tracks(
+    player_id: PlayerId,
+    game_state: &GameState,
+    tracks_pending: &mut Vec<(TileTrack, TileTrack)>,
+    tracks_built: &mut HashMap<(TileTrack, TileTrack), TrackLength>,
+    metrics: &dyn Metrics,
+) -> Option<GoalResult> {
+    let (source, target) = tracks_pending.pop()?;
+    // TODO HIGH: We still fail to sometimes build tracks... Even if we can build them later. Perhaps we should only consider the tracks as built when we have confirmed a route exists?
+    let Some((planned, length)) = plan_tracks(
+        player_id,
+        DirectionalEdge::exit_from(source),
+        &[DirectionalEdge::entrance_to(target)],
+        game_state,
+        DEFAULT_ALREADY_EXISTS_COEF,
+        metrics,
+    ) else {
+        // TODO HIGH: This is actually bad. This is possibly a blocked station or something else bad. And this current implementation will lead to an infinite loop.
+        error!("Failed building a route for {source:?} -> {target:?}");
+        // Returning the popped pair, we will try again...
+        tracks_pending.push((source, target));
+        return Some(GoalResult::TryAgainLater);
+    };
+
+    if planned.is_empty() {
+        // If it's empty, it means it's already built
+        return Some(GoalResult::RepeatInvocation);
+    }
+
+    let can_build_bridges = planned
+        .bridges
+        .iter()
+        .all(|bridge| game_state.can_build_bridge(player_id, bridge).is_ok());
+    let can_build_tracks = planned.tracks.is_empty()
+        || game_state
+            .can_build_tracks(player_id, &planned.tracks)
+            .is_ok();
+    if can_build_bridges && can_build_tracks {
+        tracks_built.insert((source, target), length);
+        let mut commands = planned
+            .bridges
+            .into_iter()
+            .map(GameCommand::BuildBridge)
+            .collect::<Vec<_>>();
+        if !planned.tracks.is_empty() {
+            commands.push(GameCommand::BuildTracks(planned.tracks));
+        }
+        Some(GoalResult::SendCommands(commands))
+    } else {
+        Some(GoalResult::SendCommands(vec![]))
+    }
+}
+
 // We wanted this to be a `Goal` but it was not trivial to achieve
 impl BuildResourceLink {
     pub(crate) fn notify_of_response(&mut self, response: &GameResponse) {
@@ -91,7 +147,7 @@ impl BuildResourceLink {
                             }
                         }
                     },
-                    GameError::CannotBuildTracks(..) => {
+                    GameError::CannotBuildTracks(..) | GameError::CannotBuildBridge(..) => {
                         if let ResourceLinkState::BuildingTracks { .. } = &self.state {
                             // This is somewhat questionable, as on any error we are going back to square one, and also we might be getting events unrelated to our particular resource link... but the alternative is adding some "TrackBuildingRequestId" and correlating that, and that is adding complexity.
                             self.state = ResourceLinkState::Pending;
@@ -136,49 +192,36 @@ impl BuildResourceLink {
                 tracks_pending,
                 tracks_built,
             } => {
-                if let Some((source, target)) = tracks_pending.pop() {
-                    // TODO HIGH: We still fail to sometimes build tracks... Even if we can build them later. Perhaps we should only consider the tracks as built when we have confirmed a route exists?
-                    if let Some((route, length)) = plan_tracks(
-                        player_id,
-                        DirectionalEdge::exit_from(source),
-                        &[DirectionalEdge::entrance_to(target)],
-                        game_state,
-                        DEFAULT_ALREADY_EXISTS_COEF,
-                        metrics,
-                    ) {
-                        if route.is_empty() {
-                            // If it's empty, it means it's already built
-                            GoalResult::RepeatInvocation
-                        } else {
-                            if game_state.can_build_tracks(player_id, &route).is_ok() {
-                                tracks_built.insert((source, target), length);
-                                GoalResult::SendCommands(vec![GameCommand::BuildTracks(route)])
-                            } else {
-                                GoalResult::SendCommands(vec![])
-                            }
-                        }
-                    } else {
-                        // TODO HIGH: This is actually bad. This is possibly a blocked station or something else bad. And this current implementation will lead to an infinite loop.
-                        error!("Failed building a route for {source:?} -> {target:?}");
-                        // Returning the popped pair, we will try again...
-                        tracks_pending.push((source, target));
-                        GoalResult::TryAgainLater
-                    }
+                if let Some(result) =
+                    build_next_tracks(player_id, game_state, tracks_pending, tracks_built, metrics)
+                {
+                    result
                 } else {
                     self.state = ResourceLinkState::TracksBuilt(tracks_built.clone());
                     GoalResult::RepeatInvocation
                 }
             },
             ResourceLinkState::TracksBuilt(tracks_built) => {
+                // Only used if the analyser cannot find a route between the stations yet
                 const TRAINS_PER_LENGTH_COEF: f32 = 0.01;
 
-                let total_length = tracks_built.values().copied().sum::<TrackLength>();
-
-                let target_trains = (total_length.to_f32() * TRAINS_PER_LENGTH_COEF)
-                    .ceil()
-                    .max(1f32) as usize;
+                let link = (self.from_station_id, self.resource, self.to_station_id);
+                let target_trains =
+                    ThroughputAnalysis::analyse_with_planned(player_id, game_state, [link])
+                        .find_link(self.from_station_id, self.resource, self.to_station_id)
+                        .filter(|link| link.bottleneck() != Bottleneck::NoRoute)
+                        .map_or_else(
+                            || {
+                                let total_length =
+                                    tracks_built.values().copied().sum::<TrackLength>();
+                                (total_length.to_f32() * TRAINS_PER_LENGTH_COEF)
+                                    .ceil()
+                                    .max(1f32) as usize
+                            },
+                            LinkThroughput::recommended_trains,
+                        );
 
-                trace!("Total length: {total_length:?}, target_trains: {target_trains:?}");
+                trace!("Link {link:?}, target_trains: {target_trains:?}");
 
                 self.state = ResourceLinkState::PurchasingTrains {
                     target_trains,
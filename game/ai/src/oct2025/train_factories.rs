@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use shared_domain::building::industry_building_info::IndustryBuildingInfo;
+use shared_domain::building::industry_type::IndustryType;
+use shared_domain::game_state::GameState;
+use shared_domain::metrics::Metrics;
+use shared_domain::server_response::{GameError, GameResponse};
+use shared_domain::transport::tile_track::TileTrack;
+use shared_domain::transport::track_length::TrackLength;
+use shared_domain::{PlayerId, StationId};
+
+use crate::oct2025::industries::{BuildIndustry, BuildIndustryState};
+use crate::oct2025::resource_links::{build_next_tracks, track_pairs};
+use crate::oct2025::{Goal, GoalResult, invoke_to_finished};
+
+// The industries that the supply chains are built for, so every station of a supply chain is
+// connected to the station of one of them
+const SUPPLY_CHAIN_TARGETS: [IndustryType; 2] =
+    [IndustryType::ConstructionYard, IndustryType::MilitaryBase];
+
+#[derive(Clone, Debug)]
+enum ConnectionState {
+    Pending,
+    BuildingTracks {
+        tracks_pending: Vec<(TileTrack, TileTrack)>,
+        tracks_built:   HashMap<(TileTrack, TileTrack), TrackLength>,
+    },
+    Connected,
+}
+
+// Trains are bought at a station linked to a train factory, so the train factory gets a station,
+// connected to the stations of the supply chain targets. The trains bought there can then reach
+// any of the supply chains.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectTrainFactoryAI {
+    train_factory: BuildIndustry,
+    connections:   HashMap<StationId, ConnectionState>,
+}
+
+impl ConnectTrainFactoryAI {
+    #[must_use]
+    pub(crate) fn for_train_factory(train_factory: &IndustryBuildingInfo) -> Self {
+        let location = train_factory.reference_tile();
+        Self {
+            train_factory: BuildIndustry {
+                industry_type:   IndustryType::TrainFactory,
+                target_location: location,
+                state:           BuildIndustryState::IndustryBuilt(train_factory.id(), location),
+            },
+            connections:   HashMap::new(),
+        }
+    }
+}
+
+fn connect(
+    state: &mut ConnectionState,
+    player_id: PlayerId,
+    game_state: &GameState,
+    from_station_id: StationId,
+    to_station_id: StationId,
+    metrics: &dyn Metrics,
+) -> GoalResult {
+    match state {
+        ConnectionState::Pending => {
+            if from_station_id == to_station_id {
+                *state = ConnectionState::Connected;
+                return GoalResult::Finished;
+            }
+            match track_pairs(game_state, from_station_id, to_station_id) {
+                Some(tracks_pending) => {
+                    *state = ConnectionState::BuildingTracks {
+                        tracks_pending,
+                        tracks_built: HashMap::new(),
+                    };
+                    GoalResult::RepeatInvocation
+                },
+                None => GoalResult::Finished,
+            }
+        },
+        ConnectionState::BuildingTracks {
+            tracks_pending,
+            tracks_built,
+        } => {
+            match build_next_tracks(player_id, game_state, tracks_pending, tracks_built, metrics) {
+                Some(GoalResult::TryAgainLater) => GoalResult::Finished,
+                Some(result) => result,
+                None => {
+                    *state = ConnectionState::Connected;
+                    GoalResult::Finished
+                },
+            }
+        },
+        ConnectionState::Connected => GoalResult::Finished,
+    }
+}
+
+impl Goal for ConnectTrainFactoryAI {
+    // Never holds back the other goals, as the stations it connects to are built by them
+    fn commands(
+        &mut self,
+        player_id: PlayerId,
+        game_state: &GameState,
+        metrics: &dyn Metrics,
+    ) -> GoalResult {
+        if let GoalResult::SendCommands(commands) =
+            invoke_to_finished(|| self.train_factory.commands(player_id, game_state, metrics))
+        {
+            return GoalResult::SendCommands(commands);
+        }
+        let BuildIndustryState::StationBuilt(_, _, train_factory_station_id) =
+            self.train_factory.state
+        else {
+            return GoalResult::Finished;
+        };
+
+        let building_state = game_state.building_state();
+        for industry_type in SUPPLY_CHAIN_TARGETS {
+            for target in
+                building_state.find_industry_buildings_by_owner_and_type(player_id, industry_type)
+            {
+                if let Some(station) = building_state.find_linked_station(target.id()) {
+                    self.connections
+                        .entry(station.id())
+                        .or_insert(ConnectionState::Pending);
+                }
+            }
+        }
+
+        for (station_id, state) in &mut self.connections {
+            let result = invoke_to_finished(|| {
+                connect(
+                    state,
+                    player_id,
+                    game_state,
+                    train_factory_station_id,
+                    *station_id,
+                    metrics,
+                )
+            });
+            if let GoalResult::SendCommands(commands) = result {
+                return GoalResult::SendCommands(commands);
+            }
+        }
+
+        GoalResult::Finished
+    }
+
+    fn notify_of_response(&mut self, response: &GameResponse) {
+        self.train_factory.notify_of_response(response);
+        if let GameResponse::Error(
+            GameError::CannotBuildTracks(..) | GameError::CannotBuildBridge(..),
+        ) = response
+        {
+            // Starting over, as in `BuildResourceLink`
+            for state in self.connections.values_mut() {
+                if let ConnectionState::BuildingTracks { .. } = state {
+                    *state = ConnectionState::Pending;
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,59 @@
 use log::{trace, warn};
+use shared_domain::building::BuildError;
+use shared_domain::building::industry_type::IndustryType;
 use shared_domain::game_state::GameState;
+use shared_domain::metrics::NoopMetrics;
 use shared_domain::resource_type::ResourceType;
 use shared_domain::transport::movement_orders::{MovementOrder, MovementOrders};
+use shared_domain::transport::track_pathfinding::find_route_to_tile_tracks;
 use shared_domain::transport::transport_info::TransportInfo;
 use shared_domain::transport::transport_type::TransportType;
 use shared_domain::{PlayerId, StationId, TransportId};
 use shared_util::tap::TapNone;
 
+// Trains are bought at the stations linked to a train factory - the first station of the link if
+// it is one, otherwise any from which the train can reach the first station of the link
+fn purchase_stations(
+    player_id: PlayerId,
+    game_state: &GameState,
+    from_station_id: StationId,
+) -> Vec<StationId> {
+    let building_state = game_state.building_state();
+    let mut results = building_state
+        .find_industry_buildings_by_owner_and_type(player_id, IndustryType::TrainFactory)
+        .into_iter()
+        .flat_map(|train_factory| building_state.find_linked_stations(train_factory.id()))
+        .map(|station| station.id())
+        .collect::<Vec<_>>();
+    results.sort_unstable();
+    results.dedup();
+    results.sort_by_key(|station_id| *station_id != from_station_id);
+
+    let Some(from_station_exits) = building_state
+        .find_station(from_station_id)
+        .map(|station| station.station_exit_tile_tracks())
+    else {
+        return vec![];
+    };
+    results.retain(|station_id| {
+        *station_id == from_station_id
+            || building_state
+                .find_station(*station_id)
+                .into_iter()
+                .flat_map(|station| station.station_exit_tile_tracks())
+                .any(|exit| {
+                    find_route_to_tile_tracks(
+                        exit,
+                        &from_station_exits,
+                        building_state,
+                        &NoopMetrics::default(),
+                    )
+                    .is_some()
+                })
+    });
+    results
+}
+
 pub(crate) fn purchase_transport(
     player_id: PlayerId,
     game_state: &GameState,
@@ -17,31 +64,44 @@ pub(crate) fn purchase_transport(
     let mut movement_orders = MovementOrders::one(MovementOrder::stop_at_station(from_station_id));
     movement_orders.push(MovementOrder::stop_at_station(to_station_id));
 
-    let from_station_info = game_state.building_state().find_station(from_station_id)?;
-    let tile_tracks = from_station_info.station_exit_tile_tracks();
-    let tile_track = tile_tracks.first()?;
-    let transport_location = from_station_info
-        .transport_location_at_station(tile_track.tile, tile_track.pointing_in)
-        .tap_none(|| {
-            warn!("Failed to find transport location for station {from_station_info:?}",);
-        })?;
-
-    let transport_info = TransportInfo::new(
-        TransportId::random(),
-        player_id,
-        TransportType::cargo_train(resource_type),
-        transport_location,
-        movement_orders,
-    );
-
-    match game_state.can_purchase_transport(player_id, from_station_id, &transport_info) {
-        Ok(_) => {
-            let result = (from_station_id, transport_info);
-            Some(result)
-        },
-        Err(error) => {
-            trace!("Failed to purchase transport for {resource_type:?}: {error:?}");
-            None
-        },
+    let transport_id = TransportId::random();
+
+    for station_id in purchase_stations(player_id, game_state, from_station_id) {
+        let station_info = game_state.building_state().find_station(station_id)?;
+
+        // Try the platforms one by one, as some of them may be occupied by other trains
+        for tile_track in station_info.station_exit_tile_tracks() {
+            let transport_location = station_info
+                .transport_location_at_station(tile_track.tile, tile_track.pointing_in)
+                .tap_none(|| {
+                    warn!("Failed to find transport location for station {station_info:?}",);
+                })?;
+
+            let transport_info = TransportInfo::new(
+                transport_id,
+                player_id,
+                TransportType::cargo_train(resource_type),
+                transport_location,
+                movement_orders.clone(),
+            );
+
+            match game_state.can_purchase_transport(player_id, station_id, &transport_info) {
+                Ok(_) => {
+                    let result = (station_id, transport_info);
+                    return Some(result);
+                },
+                Err(BuildError::PlatformOccupied) => {
+                    trace!("Platform at {tile_track:?} is occupied, trying the next one");
+                },
+                Err(error) => {
+                    trace!(
+                        "Failed to purchase transport for {resource_type:?} at {station_id:?}: {error:?}"
+                    );
+                    break;
+                },
+            }
+        }
     }
+
+    None
 }
@@ -0,0 +1,89 @@
+use log::{debug, error};
+use shared_domain::building::building_info::WithBuildingDynamicInfo;
+use shared_domain::building::industry_building_info::IndustryBuildingInfo;
+use shared_domain::client_command::GameCommand;
+use shared_domain::game_state::GameState;
+use shared_domain::metrics::Metrics;
+use shared_domain::server_response::{GameError, GameResponse};
+use shared_domain::{IndustryBuildingId, PlayerId};
+
+use crate::oct2025::{Goal, GoalResult};
+
+// An industry producing at (nearly) its full rate is holding back the rest of its supply chain
+const BOTTLENECK_UTILISATION: f32 = 0.95;
+
+#[derive(Clone, Debug)]
+pub(crate) struct UpgradeBottlenecksAI {
+    pending: Option<IndustryBuildingId>,
+}
+
+impl UpgradeBottlenecksAI {
+    #[must_use]
+    pub(crate) fn new() -> Self {
+        Self { pending: None }
+    }
+}
+
+impl Goal for UpgradeBottlenecksAI {
+    fn commands(
+        &mut self,
+        player_id: PlayerId,
+        game_state: &GameState,
+        _metrics: &dyn Metrics,
+    ) -> GoalResult {
+        if self.pending.is_some() {
+            return GoalResult::TryAgainLater;
+        }
+
+        match select_bottleneck(player_id, game_state) {
+            Some(industry_building_id) => {
+                debug!("Upgrading bottleneck industry {industry_building_id:?}");
+                self.pending = Some(industry_building_id);
+                GoalResult::SendCommands(vec![GameCommand::UpgradeIndustryBuilding(
+                    industry_building_id,
+                )])
+            },
+            None => GoalResult::Finished,
+        }
+    }
+
+    fn notify_of_response(&mut self, response: &GameResponse) {
+        match response {
+            GameResponse::IndustryBuildingUpgraded(building)
+                if self.pending == Some(building.id()) =>
+            {
+                self.pending = None;
+            },
+            GameResponse::Error(GameError::CannotUpgradeIndustryBuilding(
+                industry_building_id,
+                build_error,
+            )) if self.pending == Some(*industry_building_id) => {
+                error!("Failed to upgrade {industry_building_id:?}: {build_error:?}");
+                self.pending = None;
+            },
+            _ => {},
+        }
+    }
+}
+
+// Picks the busiest of the producing industries that are running flat out and can be afforded
+fn select_bottleneck(player_id: PlayerId, game_state: &GameState) -> Option<IndustryBuildingId> {
+    game_state
+        .building_state()
+        .find_industry_buildings_by_owner(player_id)
+        .into_iter()
+        .filter(|building| !building.transform_per_second().outputs.is_empty())
+        .filter(|building| building.dynamic_info().utilisation() >= BOTTLENECK_UTILISATION)
+        .filter(|building| {
+            game_state
+                .can_upgrade_industry_building(player_id, building.id())
+                .is_ok()
+        })
+        .max_by(|a, b| {
+            a.dynamic_info()
+                .utilisation()
+                .total_cmp(&b.dynamic_info().utilisation())
+                .then(b.level().cmp(&a.level()))
+        })
+        .map(IndustryBuildingInfo::id)
+}
@@ -0,0 +1,143 @@
+#![expect(clippy::unwrap_used)]
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use game_ai::ArtificialIntelligenceState;
+use game_ai::oct2025::Oct2025ArtificialIntelligenceState;
+use game_logic::games_service::GamesService;
+use shared_domain::building::building_info::WithOwner;
+use shared_domain::game_state::GameState;
+use shared_domain::game_time::GameTimeDiff;
+use shared_domain::metrics::{Metrics, NoopMetrics};
+use shared_domain::server_response::{GameResponse, ServerResponse, UserInfo};
+use shared_domain::transport::track_length::TrackLength;
+use shared_domain::transport::transport_info::TransportInfo;
+use shared_domain::{ScenarioId, TransportId, UserId, UserName};
+
+const AI_TRANSPORTS: usize = 5;
+const TRANSPORTS: usize = 100;
+const MAX_AI_STEPS: usize = 10_000;
+const STEPS_PER_ITERATION: usize = 10;
+
+#[derive(Default)]
+struct RouteCacheMetrics {
+    hits:   Cell<usize>,
+    misses: Cell<usize>,
+}
+
+impl Metrics for RouteCacheMetrics {
+    fn track_pathfinding_duration(
+        &self,
+        _duration: Duration,
+        _result: Option<(usize, TrackLength)>,
+    ) {
+    }
+
+    fn track_planning_duration(&self, _duration: Duration, _result: Option<(usize, TrackLength)>) {}
+
+    fn route_cache_hit(&self) {
+        self.hits.set(self.hits.get() + 1);
+    }
+
+    fn route_cache_miss(&self) {
+        self.misses.set(self.misses.get() + 1);
+    }
+}
+
+// Lets the AI build its network on the USA scenario until it has bought some trains, and then
+// copies those trains until there are `TRANSPORTS` of them
+fn game_state_with_transports() -> GameState {
+    let mut games_service = GamesService::new(false);
+    let user_info = UserInfo {
+        id:   UserId::random(),
+        name: UserName::new("AI".to_string()),
+    };
+    let responses = games_service
+        .create_and_join_game_by_scenario(&user_info, ScenarioId::all().first().unwrap(), None)
+        .unwrap();
+    let ServerResponse::Game(game_id, GameResponse::GameJoined(player_id, _)) =
+        responses.first().unwrap().response
+    else {
+        panic!("Expected to join game, got {responses:?}");
+    };
+
+    let game_service = games_service.get_game_service_mut(game_id).unwrap();
+    let mut ai = Oct2025ArtificialIntelligenceState::new(player_id, game_service.game_state());
+    let metrics = NoopMetrics::default();
+
+    for _ in 0 .. MAX_AI_STEPS {
+        if game_service
+            .game_state()
+            .transport_state()
+            .find_players_transports(player_id)
+            .len()
+            >= AI_TRANSPORTS
+        {
+            break;
+        }
+
+        if let Some(commands) = ai.ai_commands(game_service.game_state(), &metrics) {
+            for command in commands {
+                for response in game_service.process_command(player_id, &command).unwrap() {
+                    ai.notify_of_response(&response.response);
+                }
+            }
+        }
+
+        for response in game_service.advance_time_diff(GameTimeDiff::from_seconds(0.1), &metrics) {
+            ai.notify_of_response(&response.response);
+        }
+    }
+
+    let mut game_state = game_service.game_state().clone();
+    let existing: Vec<TransportInfo> = game_state.transport_infos().clone();
+    assert!(!existing.is_empty(), "AI did not buy any transports");
+
+    for transport in existing
+        .iter()
+        .cycle()
+        .take(TRANSPORTS.saturating_sub(existing.len()))
+    {
+        let copy = TransportInfo::new(
+            TransportId::random(),
+            transport.owner_id(),
+            transport.transport_type().clone(),
+            transport.location().clone(),
+            transport.movement_orders().clone(),
+        );
+        game_state.upsert_transport(copy);
+    }
+
+    game_state
+}
+
+fn advance(game_state: &mut GameState, metrics: &impl Metrics) {
+    for _ in 0 .. STEPS_PER_ITERATION {
+        let _ = game_state.advance_time_diff(GameTimeDiff::from_seconds(0.1), metrics);
+    }
+}
+
+fn advance_transports(c: &mut Criterion) {
+    let game_state = game_state_with_transports();
+
+    let metrics = RouteCacheMetrics::default();
+    advance(&mut game_state.clone(), &metrics);
+    println!(
+        "Route cache: {} hits, {} misses",
+        metrics.hits.get(),
+        metrics.misses.get()
+    );
+
+    c.bench_function("usa_100_transports", |b| {
+        b.iter_batched(
+            || game_state.clone(),
+            |mut game_state| advance(&mut game_state, &NoopMetrics::default()),
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+criterion_group!(benches, advance_transports);
+criterion_main!(benches);
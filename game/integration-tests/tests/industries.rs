@@ -0,0 +1,221 @@
+use shared_domain::building::BuildError;
+use shared_domain::building::industry_building_info::IndustryBuildingInfo;
+use shared_domain::building::industry_type::IndustryType;
+use shared_domain::cargo_amount::CargoAmount;
+use shared_domain::game_state::GameState;
+use shared_domain::game_time::GameTimeDiff;
+use shared_domain::map_level::zoning::{ZoningInfo, ZoningType};
+use shared_domain::metrics::NoopMetrics;
+use shared_domain::resource_type::ResourceType;
+use shared_domain::scenario::USA_SCENARIO_BINCODE;
+use shared_domain::supply_chain::SupplyChain;
+use shared_domain::{IndustryBuildingId, PlayerId};
+use shared_util::compression::{load_from_bytes, save_to_bytes};
+
+fn game_state_with_construction_yards() -> (GameState, PlayerId) {
+    let mut game_state =
+        GameState::from_scenario(load_from_bytes(USA_SCENARIO_BINCODE).unwrap(), false);
+    let player_id = game_state.players().ids()[0];
+
+    let industrials = game_state
+        .all_free_zonings()
+        .filter(|zoning| zoning.zoning_type() == ZoningType::Industrial)
+        .map(ZoningInfo::reference_tile)
+        .collect::<Vec<_>>();
+    for industrial_tile in industrials {
+        game_state
+            .building_state_mut()
+            .gift_initial_construction_yard(player_id, industrial_tile, &SupplyChain::new());
+    }
+    (game_state, player_id)
+}
+
+// Construction without any materials delivered takes 10 seconds
+fn finish_construction(game_state: &mut GameState) {
+    let _ = game_state.advance_time_diff(GameTimeDiff::from_seconds(15.0), &NoopMetrics::default());
+}
+
+fn build_coal_mine(game_state: &mut GameState, player_id: PlayerId) -> IndustryBuildingInfo {
+    let coal_zoning = game_state
+        .all_free_zonings()
+        .find(|zoning| zoning.zoning_type() == ZoningType::Source(ResourceType::Coal))
+        .unwrap()
+        .clone();
+    let mine = IndustryBuildingInfo::new(
+        player_id,
+        IndustryBuildingId::random(),
+        coal_zoning.reference_tile(),
+        IndustryType::CoalMine,
+    );
+    game_state
+        .build_industry_building(player_id, &mine)
+        .unwrap()
+}
+
+#[test]
+fn test_mine_depletes_deposit_which_survives_demolition() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+
+    let coal_zoning = game_state
+        .all_free_zonings()
+        .find(|zoning| zoning.zoning_type() == ZoningType::Source(ResourceType::Coal))
+        .unwrap()
+        .clone();
+    let initial_deposit = coal_zoning.deposit().unwrap();
+
+    let mine = IndustryBuildingInfo::new(
+        player_id,
+        IndustryBuildingId::random(),
+        coal_zoning.reference_tile(),
+        IndustryType::CoalMine,
+    );
+    let built = game_state
+        .build_industry_building(player_id, &mine)
+        .unwrap();
+    assert_eq!(built.deposit(), Some(initial_deposit));
+    assert!(built.is_under_construction());
+
+    finish_construction(&mut game_state);
+    let finished = game_state
+        .building_state()
+        .find_industry_building(mine.id())
+        .unwrap();
+    assert!(!finished.is_under_construction());
+    assert_eq!(finished.deposit(), Some(initial_deposit));
+
+    let _ = game_state.advance_time_diff(GameTimeDiff::from_seconds(10.0), &NoopMetrics::default());
+    let remaining = game_state
+        .building_state()
+        .find_industry_building(mine.id())
+        .unwrap()
+        .deposit()
+        .unwrap();
+    assert!(remaining.remaining() < initial_deposit.remaining());
+    assert_eq!(remaining.size(), initial_deposit.size());
+
+    game_state
+        .remove_industry_building(player_id, mine.id())
+        .unwrap();
+    let zoning_after = game_state
+        .all_free_zonings()
+        .find(|zoning| zoning.id() == coal_zoning.id())
+        .unwrap();
+    assert_eq!(zoning_after.deposit(), Some(remaining));
+}
+
+#[test]
+fn test_upgrade_industry_building() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+    let mine = build_coal_mine(&mut game_state, player_id);
+    assert_eq!(mine.level(), 1);
+    assert_eq!(
+        game_state.upgrade_industry_building(player_id, mine.id()),
+        Err(BuildError::UnderConstruction)
+    );
+    finish_construction(&mut game_state);
+
+    let treasury_before = game_state.players().treasury(player_id);
+    let upgraded = game_state
+        .upgrade_industry_building(player_id, mine.id())
+        .unwrap();
+    assert_eq!(upgraded.level(), 2);
+    assert_eq!(upgraded.storage_capacity(), mine.storage_capacity() * 2.0);
+    assert!(game_state.players().treasury(player_id) < treasury_before);
+
+    let upgraded = game_state
+        .upgrade_industry_building(player_id, mine.id())
+        .unwrap();
+    assert_eq!(upgraded.level(), IndustryBuildingInfo::MAX_LEVEL);
+    assert_eq!(
+        game_state.upgrade_industry_building(player_id, mine.id()),
+        Err(BuildError::AlreadyAtMaxLevel)
+    );
+}
+
+#[test]
+fn test_preferred_construction_yard_pays_when_in_range() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+    let coal_zoning = game_state
+        .all_free_zonings()
+        .find(|zoning| zoning.zoning_type() == ZoningType::Source(ResourceType::Coal))
+        .unwrap()
+        .clone();
+    let mine = IndustryBuildingInfo::new(
+        player_id,
+        IndustryBuildingId::random(),
+        coal_zoning.reference_tile(),
+        IndustryType::CoalMine,
+    );
+    let paying_yard = |game_state: &GameState| {
+        let costs = game_state
+            .can_build_industry_building(player_id, &mine)
+            .unwrap();
+        costs.costs.keys().copied().collect::<Vec<_>>()
+    };
+
+    // Without a preference, the closest one pays
+    let closest = paying_yard(&game_state);
+    assert_eq!(closest.len(), 1);
+
+    let yards = game_state
+        .building_state()
+        .find_industry_buildings_by_owner_and_type(player_id, IndustryType::ConstructionYard)
+        .into_iter()
+        .map(IndustryBuildingInfo::id)
+        .filter(|yard_id| !closest.contains(yard_id))
+        .collect::<Vec<_>>();
+    let preferred_in_range = yards.into_iter().find(|yard_id| {
+        game_state
+            .set_preferred_supplier(player_id, *yard_id)
+            .unwrap();
+        paying_yard(&game_state) == vec![*yard_id]
+    });
+    assert!(preferred_in_range.is_some());
+
+    let built = game_state
+        .build_industry_building(player_id, &mine)
+        .unwrap();
+    assert_eq!(
+        game_state.set_preferred_supplier(player_id, built.id()),
+        Err(BuildError::UnknownError)
+    );
+}
+
+#[test]
+fn test_statistics_record_building_and_production() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+    let _ = build_coal_mine(&mut game_state, player_id);
+    finish_construction(&mut game_state);
+    let _ = game_state.advance_time_diff(GameTimeDiff::from_seconds(10.0), &NoopMetrics::default());
+
+    let history = game_state
+        .statistics()
+        .history(player_id)
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].buildings_built(), 1);
+    assert!(history[1].produced().get(ResourceType::Coal) > CargoAmount::ZERO);
+    assert_eq!(
+        history[1].treasury(),
+        game_state.players().treasury(player_id)
+    );
+}
+
+#[test]
+fn test_statistics_survive_reloading_the_game_state() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+    let _ = build_coal_mine(&mut game_state, player_id);
+    finish_construction(&mut game_state);
+    assert!(
+        game_state
+            .statistics()
+            .history(player_id)
+            .into_iter()
+            .next()
+            .is_some()
+    );
+
+    let reloaded: GameState = load_from_bytes(&save_to_bytes(&game_state).unwrap()).unwrap();
+    assert_eq!(reloaded.statistics(), game_state.statistics());
+}
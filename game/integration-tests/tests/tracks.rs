@@ -1,28 +1,41 @@
-use shared_domain::PlayerId;
+use shared_domain::building::BuildError;
+use shared_domain::building::bridge_info::BridgeInfo;
+use shared_domain::building::track_info::TrackInfo;
+use shared_domain::building::waypoint_info::WaypointInfo;
 use shared_domain::directional_edge::DirectionalEdge;
 use shared_domain::game_state::GameState;
+use shared_domain::game_time::GameTimeDiff;
 use shared_domain::map_level::zoning::{ZoningInfo, ZoningType};
 use shared_domain::metrics::NoopMetrics;
+use shared_domain::resource_type::ResourceType;
 use shared_domain::scenario::USA_SCENARIO_BINCODE;
+use shared_domain::server_response::GameError;
+use shared_domain::statistics::StatisticsSample;
 use shared_domain::supply_chain::SupplyChain;
 use shared_domain::tile_coords_xz::TileCoordsXZ;
+use shared_domain::transport::movement_orders::{MovementOrder, MovementOrders};
+use shared_domain::transport::progress_within_tile::ProgressWithinTile;
 use shared_domain::transport::tile_track::TileTrack;
+use shared_domain::transport::track_grade::TrackGrade;
 use shared_domain::transport::track_length::TrackLength;
 use shared_domain::transport::track_pathfinding::find_route_to_tile_tracks;
 use shared_domain::transport::track_planner::{DEFAULT_ALREADY_EXISTS_COEF, plan_tracks};
 use shared_domain::transport::track_type::TrackType;
+use shared_domain::transport::transport_info::TransportInfo;
+use shared_domain::transport::transport_location::TransportLocation;
+use shared_domain::transport::transport_type::TransportType;
+use shared_domain::{PlayerId, StationId, TransportId, WaypointId};
 use shared_util::compression::load_from_bytes;
 use shared_util::direction_xz::DirectionXZ;
 
-#[test]
-fn test_plan_tracks() {
-    let player_id = PlayerId::random();
-
+fn game_state_with_construction_yards() -> (GameState, PlayerId) {
     let mut game_state =
         GameState::from_scenario(load_from_bytes(USA_SCENARIO_BINCODE).unwrap(), false);
+    // Only the scenario players have a treasury to pay for the construction
+    let player_id = game_state.players().ids()[0];
 
-    // We spawn construction yards in all free spots because this test is about testing track
-    // planning, not availability of resources
+    // We spawn construction yards in all free spots because these tests are about tracks, not
+    // availability of resources
     let industrials = game_state
         .all_free_zonings()
         .filter(|zoning| zoning.zoning_type() == ZoningType::Industrial)
@@ -33,6 +46,17 @@ fn test_plan_tracks() {
             .building_state_mut()
             .gift_initial_construction_yard(player_id, industrial_tile, &SupplyChain::new());
     }
+    (game_state, player_id)
+}
+
+// Construction without any materials delivered takes 10 seconds
+fn finish_construction(game_state: &mut GameState) {
+    let _ = game_state.advance_time_diff(GameTimeDiff::from_seconds(15.0), &NoopMetrics::default());
+}
+
+#[test]
+fn test_plan_tracks() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
 
     let from_tile = TileCoordsXZ::new(340, 350);
     let to_tile = TileCoordsXZ::new(280, 150);
@@ -40,7 +64,7 @@ fn test_plan_tracks() {
     let head = DirectionalEdge::new(from_tile, DirectionXZ::West);
     let tail = DirectionalEdge::new(to_tile, DirectionXZ::South);
 
-    let (tracks, length) = plan_tracks(
+    let (planned, length) = plan_tracks(
         player_id,
         head,
         &[tail],
@@ -50,6 +74,7 @@ fn test_plan_tracks() {
     )
     .expect("Failed to plan tracks");
 
+    let tracks = planned.tracks;
     let expected_min_tracks = 250;
     assert!(
         tracks.len() > expected_min_tracks,
@@ -61,10 +86,26 @@ fn test_plan_tracks() {
         length > TrackLength::new(expected_min_length),
         "Expected at least {expected_min_length} length, got {length:?}"
     );
+    for bridge in &planned.bridges {
+        game_state
+            .build_bridge(player_id, bridge)
+            .expect("Failed to build bridge");
+    }
     let result = game_state
         .build_tracks(player_id, &tracks)
         .expect("Failed to build tracks");
     assert_eq!(result.len(), tracks.len());
+    finish_construction(&mut game_state);
+    let buildings_built = game_state
+        .statistics()
+        .history(player_id)
+        .into_iter()
+        .map(StatisticsSample::buildings_built)
+        .sum::<u32>();
+    assert_eq!(
+        buildings_built as usize,
+        tracks.len() + planned.bridges.len()
+    );
 
     let first_tile = head.into_tile;
     let last_tile = tail.into_tile + tail.from_direction;
@@ -87,5 +128,221 @@ fn test_plan_tracks() {
         &NoopMetrics::default(),
     )
     .unwrap();
-    assert_eq!(route.len(), tracks.len());
+    let bridge_tiles = planned
+        .bridges
+        .iter()
+        .map(BridgeInfo::length_in_tiles)
+        .sum::<usize>();
+    assert_eq!(route.len(), tracks.len() + bridge_tiles);
+}
+
+#[test]
+fn test_cannot_remove_tracks_under_transport() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+
+    let head = DirectionalEdge::new(TileCoordsXZ::new(340, 350), DirectionXZ::West);
+    let tail = DirectionalEdge::new(TileCoordsXZ::new(330, 350), DirectionXZ::East);
+    let (planned, _) = plan_tracks(
+        player_id,
+        head,
+        &[tail],
+        &game_state,
+        DEFAULT_ALREADY_EXISTS_COEF,
+        &NoopMetrics::default(),
+    )
+    .expect("Failed to plan tracks");
+    let tracks = game_state
+        .build_tracks(player_id, &planned.tracks)
+        .expect("Failed to build tracks");
+    assert!(tracks.len() > 2);
+
+    let occupied = &tracks[tracks.len() / 2];
+    let free = &tracks[0];
+    let location = TransportLocation::new(
+        vec![TileTrack {
+            tile:        occupied.tile,
+            track_type:  occupied.track_type,
+            pointing_in: occupied.track_type.connections()[0],
+        }],
+        ProgressWithinTile::just_entering(),
+    );
+    let transport = TransportInfo::new(
+        TransportId::random(),
+        player_id,
+        TransportType::cargo_train(ResourceType::Coal),
+        location,
+        MovementOrders::one(MovementOrder::stop_at_station(StationId::random())),
+    );
+    let transport_id = transport.transport_id();
+    game_state.upsert_transport(transport);
+
+    assert_eq!(
+        game_state.transports_blocking_track_removal(&[occupied.id()]),
+        vec![transport_id]
+    );
+    assert_eq!(
+        game_state.remove_tracks(player_id, &[occupied.id()]),
+        Err(GameError::CannotDemolishOccupiedTracks(
+            vec![occupied.id()],
+            vec![transport_id]
+        ))
+    );
+    assert!(game_state.remove_tracks(player_id, &[free.id()]).is_ok());
+}
+
+#[test]
+fn test_cannot_remove_tracks_on_the_route_of_a_transport() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+
+    let head = DirectionalEdge::new(TileCoordsXZ::new(340, 350), DirectionXZ::West);
+    let tail = DirectionalEdge::new(TileCoordsXZ::new(330, 350), DirectionXZ::East);
+    let (planned, _) = plan_tracks(
+        player_id,
+        head,
+        &[tail],
+        &game_state,
+        DEFAULT_ALREADY_EXISTS_COEF,
+        &NoopMetrics::default(),
+    )
+    .expect("Failed to plan tracks");
+    let tracks = game_state
+        .build_tracks(player_id, &planned.tracks)
+        .expect("Failed to build tracks");
+    assert!(tracks.len() > 3);
+
+    // The transport is on the tracks, heading west to a waypoint at their end
+    let start = tracks
+        .iter()
+        .find(|track| track.tile == TileCoordsXZ::new(336, 350))
+        .expect("Failed to find the first track");
+    let end = tracks
+        .iter()
+        .find(|track| track.tile == TileCoordsXZ::new(331, 350))
+        .expect("Failed to find the last track");
+    let waypoint = WaypointInfo::new(WaypointId::random(), player_id, end.tile);
+    game_state
+        .build_waypoint(player_id, &waypoint)
+        .expect("Failed to build the waypoint");
+    finish_construction(&mut game_state);
+    let location = TransportLocation::new(
+        vec![TileTrack {
+            tile:        start.tile,
+            track_type:  start.track_type,
+            pointing_in: DirectionXZ::West,
+        }],
+        ProgressWithinTile::just_entering(),
+    );
+    let transport = TransportInfo::new(
+        TransportId::random(),
+        player_id,
+        TransportType::cargo_train(ResourceType::Coal),
+        location,
+        MovementOrders::one(MovementOrder::pass_through_waypoint(waypoint.id())),
+    );
+    let transport_id = transport.transport_id();
+    game_state.upsert_transport(transport);
+
+    // Further along the route than the next tile
+    let on_route = tracks
+        .iter()
+        .find(|track| track.tile == TileCoordsXZ::new(333, 350))
+        .expect("Failed to find a track on the route");
+    assert_eq!(
+        game_state.transports_blocking_track_removal(&[on_route.id()]),
+        vec![transport_id]
+    );
+
+    // Nor can the waypoint it is ordered to pass be removed, until it no longer is
+    assert_eq!(
+        game_state.remove_waypoint(player_id, waypoint.id()),
+        Err(())
+    );
+    game_state.remove_transport(transport_id).unwrap();
+    assert_eq!(game_state.remove_waypoint(player_id, waypoint.id()), Ok(()));
+}
+
+#[test]
+fn test_upgrade_tracks() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+
+    let head = DirectionalEdge::new(TileCoordsXZ::new(340, 350), DirectionXZ::West);
+    let tail = DirectionalEdge::new(TileCoordsXZ::new(330, 350), DirectionXZ::East);
+    let (planned, _) = plan_tracks(
+        player_id,
+        head,
+        &[tail],
+        &game_state,
+        DEFAULT_ALREADY_EXISTS_COEF,
+        &NoopMetrics::default(),
+    )
+    .expect("Failed to plan tracks");
+    let tracks = game_state
+        .build_tracks(player_id, &planned.tracks)
+        .expect("Failed to build tracks");
+    let track_ids = tracks.iter().map(TrackInfo::id).collect::<Vec<_>>();
+    let building_state = game_state.building_state();
+    for track in &tracks {
+        assert_eq!(
+            building_state.track_grade_at(track.tile, track.track_type),
+            TrackGrade::LightRail
+        );
+    }
+
+    assert_eq!(
+        game_state
+            .upgrade_tracks(PlayerId::random(), &track_ids, TrackGrade::Mainline)
+            .err(),
+        Some(BuildError::InvalidOwner)
+    );
+
+    let upgraded = game_state
+        .upgrade_tracks(player_id, &track_ids, TrackGrade::Mainline)
+        .expect("Failed to upgrade tracks");
+    assert_eq!(upgraded.len(), tracks.len());
+    let building_state = game_state.building_state();
+    for track in &tracks {
+        assert_eq!(
+            building_state.track_grade_at(track.tile, track.track_type),
+            TrackGrade::Mainline
+        );
+    }
+
+    // Already upgraded tracks are skipped, and downgrading is not possible
+    for grade in TrackGrade::all() {
+        let result = game_state
+            .upgrade_tracks(player_id, &track_ids, grade)
+            .expect("Failed to upgrade tracks");
+        assert!(result.is_empty());
+    }
+}
+
+#[test]
+fn test_tracks_cost_money_to_build_and_run() {
+    let (mut game_state, player_id) = game_state_with_construction_yards();
+    let initial_treasury = game_state.players().treasury(player_id);
+
+    let head = DirectionalEdge::new(TileCoordsXZ::new(340, 350), DirectionXZ::West);
+    let tail = DirectionalEdge::new(TileCoordsXZ::new(330, 350), DirectionXZ::East);
+    let (planned, _) = plan_tracks(
+        player_id,
+        head,
+        &[tail],
+        &game_state,
+        DEFAULT_ALREADY_EXISTS_COEF,
+        &NoopMetrics::default(),
+    )
+    .expect("Failed to plan tracks");
+    let (_, costs) = game_state
+        .can_build_tracks(player_id, &planned.tracks)
+        .expect("Failed to check the costs");
+    game_state
+        .build_tracks(player_id, &planned.tracks)
+        .expect("Failed to build tracks");
+
+    let after_building = game_state.players().treasury(player_id);
+    assert_eq!(after_building, initial_treasury - costs.money[&player_id]);
+    assert!(after_building < initial_treasury);
+
+    let _ = game_state.advance_time_diff(GameTimeDiff::from_seconds(1.5), &NoopMetrics::default());
+    assert!(game_state.players().treasury(player_id) < after_building);
 }
@@ -0,0 +1,551 @@
+use std::collections::HashMap;
+
+use shared_domain::building::BuildError;
+use shared_domain::building::building_info::{
+    BuildingDynamicInfo, WithCostToBuild, WithTileCoverage,
+};
+use shared_domain::building::industry_building_info::IndustryBuildingInfo;
+use shared_domain::building::industry_type::IndustryType;
+use shared_domain::building::station_info::StationInfo;
+use shared_domain::building::station_type::{StationOrientation, StationType};
+use shared_domain::cargo_amount::CargoAmount;
+use shared_domain::cargo_map::{CargoMap, CargoOps, WithCargo};
+use shared_domain::game_state::GameState;
+use shared_domain::game_time::GameTimeDiff;
+use shared_domain::map_level::zoning::{ZoningInfo, ZoningType};
+use shared_domain::metrics::NoopMetrics;
+use shared_domain::military::ProjectileSource;
+use shared_domain::players::trade_offer::TradeOffer;
+use shared_domain::resource_type::ResourceType;
+use shared_domain::scenario::USA_SCENARIO_BINCODE;
+use shared_domain::server_response::GameResponse;
+use shared_domain::supply_chain::SupplyChain;
+use shared_domain::tile_coords_xz::TileCoordsXZ;
+use shared_domain::transport::movement_orders::{
+    MovementOrder, MovementOrderLocation, MovementOrders,
+};
+use shared_domain::transport::transport_info::TransportInfo;
+use shared_domain::transport::transport_location::TransportLocation;
+use shared_domain::transport::transport_type::{TrainComponentType, TransportType};
+use shared_domain::{IndustryBuildingId, PlayerId, StationId, TradeOfferId, TransportId};
+use shared_util::compression::load_from_bytes;
+
+fn game_state_with_station() -> (GameState, PlayerId, StationInfo) {
+    game_state_with_station_linked_to_train_factory(true)
+}
+
+fn game_state_with_station_linked_to_train_factory(
+    linked: bool,
+) -> (GameState, PlayerId, StationInfo) {
+    let mut game_state =
+        GameState::from_scenario(load_from_bytes(USA_SCENARIO_BINCODE).unwrap(), false);
+    // Only the scenario players have a treasury to pay for the construction
+    let player_id = game_state.players().ids()[0];
+
+    let industrials = game_state
+        .all_free_zonings()
+        .filter(|zoning| zoning.zoning_type() == ZoningType::Industrial)
+        .map(ZoningInfo::reference_tile)
+        .collect::<Vec<_>>();
+    for industrial_tile in industrials {
+        game_state
+            .building_state_mut()
+            .gift_initial_construction_yard(player_id, industrial_tile, &SupplyChain::new());
+    }
+
+    let station_type = StationType {
+        orientation:     StationOrientation::NorthToSouth,
+        platforms:       2,
+        length_in_tiles: 4,
+    };
+    // The trains are paid for from the player's train factory, and the refunds for selling them go
+    // back to it, so only stations linked to it can have trains purchased at them
+    let train_factory = train_factory(&game_state, player_id);
+    let center = train_factory.reference_tile();
+    let train_factory = train_factory.covers_tiles();
+    for x in center.x - 20 .. center.x + 20 {
+        for z in center.z - 20 .. center.z + 20 {
+            let station = StationInfo::new(
+                player_id,
+                StationId::random(),
+                TileCoordsXZ::new(x, z),
+                station_type,
+            );
+            if station.catchment_area().intersects(&train_factory) == linked
+                && game_state.build_station(player_id, &station).is_ok()
+            {
+                // Construction without any materials delivered takes 10 seconds
+                let _ = game_state
+                    .advance_time_diff(GameTimeDiff::from_seconds(15.0), &NoopMetrics::default());
+                return (game_state, player_id, station);
+            }
+        }
+    }
+    panic!("Failed to find a place for the station");
+}
+
+fn train_factory(game_state: &GameState, player_id: PlayerId) -> &IndustryBuildingInfo {
+    game_state
+        .building_state()
+        .find_industry_buildings_by_owner(player_id)
+        .into_iter()
+        .find(|building| building.industry_type() == IndustryType::TrainFactory)
+        .unwrap()
+}
+
+fn transport_at(
+    player_id: PlayerId,
+    transport_type: TransportType,
+    location: TransportLocation,
+    station_id: StationId,
+) -> TransportInfo {
+    TransportInfo::new(
+        TransportId::random(),
+        player_id,
+        transport_type,
+        location,
+        MovementOrders::one(MovementOrder::stop_at_station(station_id)),
+    )
+}
+
+#[test]
+fn test_purchase_transport_validates_placement() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let train = TransportType::cargo_train(ResourceType::Coal);
+
+    let locations = station
+        .station_exit_tile_tracks()
+        .into_iter()
+        .filter_map(|exit| station.transport_location_at_station(exit.tile, exit.pointing_in))
+        .collect::<Vec<_>>();
+    let first = locations[0].clone();
+
+    // Not covering the whole platform
+    let mut shifted = first.clone();
+    shifted.tile_path.remove(0);
+    let result = game_state.can_purchase_transport(
+        player_id,
+        station.id(),
+        &transport_at(player_id, train.clone(), shifted, station.id()),
+    );
+    assert_eq!(result.err(), Some(BuildError::TransportNotOnPlatform));
+
+    // Too long for the platform
+    let too_long = TransportType::Train(vec![TrainComponentType::Car(ResourceType::Coal); 20]);
+    let result = game_state.can_purchase_transport(
+        player_id,
+        station.id(),
+        &transport_at(player_id, too_long, first.clone(), station.id()),
+    );
+    assert_eq!(result.err(), Some(BuildError::TransportTooLongForPlatform));
+
+    game_state
+        .purchase_transport(
+            player_id,
+            station.id(),
+            &transport_at(player_id, train.clone(), first.clone(), station.id()),
+        )
+        .expect("Failed to purchase the first transport");
+
+    // The same platform is now taken, but the other platform is still free
+    let result = game_state.can_purchase_transport(
+        player_id,
+        station.id(),
+        &transport_at(player_id, train.clone(), first.clone(), station.id()),
+    );
+    assert_eq!(result.err(), Some(BuildError::PlatformOccupied));
+
+    let other_platform = locations
+        .into_iter()
+        .find(|location| {
+            location
+                .tile_path
+                .iter()
+                .all(|tile_track| !first.tile_path.iter().any(|t| t.tile == tile_track.tile))
+        })
+        .expect("Failed to find the other platform");
+    let result = game_state.can_purchase_transport(
+        player_id,
+        station.id(),
+        &transport_at(player_id, train, other_platform, station.id()),
+    );
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_purchasing_a_train_uses_rolling_stock_from_the_train_factory() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let rolling_stock = |game_state: &GameState| {
+        train_factory(game_state, player_id)
+            .cargo()
+            .get(ResourceType::RollingStock)
+    };
+    let before = rolling_stock(&game_state);
+
+    let location = station
+        .station_exit_tile_tracks()
+        .into_iter()
+        .find_map(|exit| station.transport_location_at_station(exit.tile, exit.pointing_in))
+        .unwrap();
+    let train = transport_at(
+        player_id,
+        TransportType::cargo_train(ResourceType::Coal),
+        location,
+        station.id(),
+    );
+    assert_eq!(
+        train.cost_to_build(),
+        (
+            IndustryType::TrainFactory,
+            CargoMap::single(ResourceType::RollingStock, 1.0)
+        )
+    );
+    game_state
+        .purchase_transport(player_id, station.id(), &train)
+        .expect("Failed to purchase the train");
+
+    assert_eq!(rolling_stock(&game_state), before - CargoAmount::new(1.0));
+}
+
+#[test]
+fn test_purchasing_a_train_needs_a_train_factory_linked_to_the_station() {
+    let (game_state, player_id, station) = game_state_with_station_linked_to_train_factory(false);
+    let location = station
+        .station_exit_tile_tracks()
+        .into_iter()
+        .find_map(|exit| station.transport_location_at_station(exit.tile, exit.pointing_in))
+        .unwrap();
+    let train = transport_at(
+        player_id,
+        TransportType::cargo_train(ResourceType::Coal),
+        location,
+        station.id(),
+    );
+
+    // The train factory is in supply range, but not linked to the station
+    let result = game_state.can_purchase_transport(player_id, station.id(), &train);
+    assert_eq!(result.err(), Some(BuildError::NotEnoughResources));
+}
+
+#[test]
+fn test_military_units_are_paid_for_by_a_military_base() {
+    let (game_state, player_id, station) = game_state_with_station();
+    let location = station
+        .station_exit_tile_tracks()
+        .into_iter()
+        .find_map(|exit| station.transport_location_at_station(exit.tile, exit.pointing_in))
+        .unwrap();
+
+    let artillery = transport_at(
+        player_id,
+        TransportType::railway_artillery(),
+        location,
+        station.id(),
+    );
+    assert!(artillery.military_unit().is_some());
+    assert_eq!(artillery.transport_type().railway_guns(), 1);
+    assert_eq!(
+        artillery.cost_to_build().0,
+        IndustryType::MilitaryBase,
+        "{artillery:?}"
+    );
+
+    // The initial construction yard cannot provide the weapons, only a military base can
+    let result = game_state.can_purchase_transport(player_id, station.id(), &artillery);
+    assert_eq!(result.err(), Some(BuildError::NotEnoughResources));
+}
+
+// Deployed at the station, firing at `target` if there is one
+fn deployed_artillery(
+    player_id: PlayerId,
+    station: &StationInfo,
+    target: Option<TileCoordsXZ>,
+) -> TransportInfo {
+    let location = station
+        .station_exit_tile_tracks()
+        .into_iter()
+        .find_map(|exit| station.transport_location_at_station(exit.tile, exit.pointing_in))
+        .unwrap();
+    TransportInfo::new(
+        TransportId::random(),
+        player_id,
+        TransportType::railway_artillery(),
+        location,
+        MovementOrders::one(MovementOrder::deploy_at(
+            MovementOrderLocation::Station(station.id()),
+            target,
+        )),
+    )
+}
+
+// Close enough to the station to supply the military units there
+fn military_base_near(
+    game_state: &mut GameState,
+    player_id: PlayerId,
+    station: &StationInfo,
+    cargo: &CargoMap,
+) -> IndustryBuildingId {
+    let tile = station.reference_tile();
+    let mut military_base = IndustryBuildingInfo::new(
+        player_id,
+        IndustryBuildingId::random(),
+        TileCoordsXZ::new(tile.x + 10, tile.z),
+        IndustryType::MilitaryBase,
+    );
+    military_base.add_cargo(cargo);
+    let military_base_id = military_base.id();
+    game_state
+        .building_state_mut()
+        .append_industry_building(military_base);
+    military_base_id
+}
+
+fn advance_seconds(game_state: &mut GameState, seconds: u8) -> Vec<GameResponse> {
+    let mut responses = vec![];
+    for _ in 0 .. u16::from(seconds) * 2 {
+        responses.extend(
+            game_state.advance_time_diff(GameTimeDiff::from_seconds(0.5), &NoopMetrics::default()),
+        );
+    }
+    responses
+}
+
+#[test]
+fn test_military_units_deploy_and_need_food_and_fuel_from_a_military_base() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let artillery = deployed_artillery(player_id, &station, None);
+    let transport_id = artillery.transport_id();
+    game_state.upsert_transport(artillery);
+
+    advance_seconds(&mut game_state, 2);
+    let military_unit = game_state
+        .get_transport_info(transport_id)
+        .unwrap()
+        .military_unit()
+        .unwrap();
+    assert!(military_unit.is_deployed());
+    assert!(!military_unit.is_supplied());
+
+    let supplies = CargoMap::from([(ResourceType::Food, 10.0), (ResourceType::Fuel, 10.0)]);
+    let military_base_id = military_base_near(&mut game_state, player_id, &station, &supplies);
+    advance_seconds(&mut game_state, 2);
+    let military_unit = game_state
+        .get_transport_info(transport_id)
+        .unwrap()
+        .military_unit()
+        .unwrap();
+    assert!(military_unit.is_deployed());
+    assert!(military_unit.is_supplied());
+    let military_base = game_state
+        .building_state()
+        .find_industry_building(military_base_id)
+        .unwrap();
+    assert!(military_base.cargo().get(ResourceType::Food) < CargoAmount::new(10.0));
+    assert!(military_base.cargo().get(ResourceType::Fuel) < CargoAmount::new(10.0));
+}
+
+#[test]
+fn test_deployed_artillery_fires_at_the_target_of_its_orders() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let tile = station.reference_tile();
+    let target = TileCoordsXZ::new(tile.x + 20, tile.z + 20);
+    let artillery = deployed_artillery(player_id, &station, Some(target));
+    let transport_id = artillery.transport_id();
+    game_state.upsert_transport(artillery);
+    let supplies = CargoMap::from([
+        (ResourceType::Food, 10.0),
+        (ResourceType::Fuel, 10.0),
+        (ResourceType::Ammunition, 10.0),
+        (ResourceType::ArtilleryWeapons, 10.0),
+    ]);
+    let military_base_id = military_base_near(&mut game_state, player_id, &station, &supplies);
+
+    // The railway gun fires straight away, and again once it has reloaded
+    let responses = advance_seconds(&mut game_state, 16);
+    let projectiles = responses
+        .into_iter()
+        .flat_map(|response| {
+            match response {
+                GameResponse::ProjectilesAdded(projectiles) => projectiles,
+                _ => vec![],
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(projectiles.len(), 2, "{projectiles:?}");
+    for projectile in &projectiles {
+        assert_eq!(projectile.landing_on(), target);
+        assert_eq!(
+            projectile.fired_from(),
+            ProjectileSource::MilitaryUnit(transport_id)
+        );
+    }
+
+    let military_base = game_state
+        .building_state()
+        .find_industry_building(military_base_id)
+        .unwrap();
+    assert!(military_base.cargo().get(ResourceType::Ammunition) < CargoAmount::new(10.0));
+}
+
+#[test]
+fn test_artillery_waiting_for_ammunition_fires_in_the_order_it_started_waiting() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let tile = station.reference_tile();
+    let target = TileCoordsXZ::new(tile.x + 20, tile.z + 20);
+    let upkeep = CargoMap::from([(ResourceType::Food, 10.0), (ResourceType::Fuel, 10.0)]);
+    let military_base_id = military_base_near(&mut game_state, player_id, &station, &upkeep);
+    let pending_shots = |game_state: &GameState| {
+        game_state
+            .building_state()
+            .pending_shots(military_base_id)
+            .collect::<Vec<_>>()
+    };
+
+    let mut sources = vec![];
+    for _ in 0 .. 2 {
+        let artillery = deployed_artillery(player_id, &station, Some(target));
+        sources.push(ProjectileSource::MilitaryUnit(artillery.transport_id()));
+        game_state.upsert_transport(artillery);
+        advance_seconds(&mut game_state, 1);
+    }
+    assert_eq!(pending_shots(&game_state), sources);
+
+    // Restocked with a single shot, which goes to the railway gun that has waited the longest
+    let shell_type = TransportType::railway_artillery().shell_type().unwrap();
+    let mut cargo = upkeep;
+    cargo += &shell_type.cost_per_shot();
+    let treasuries = game_state.players().treasuries().clone();
+    game_state.update_dynamic_infos(
+        game_state.time(),
+        &HashMap::from([(military_base_id, BuildingDynamicInfo::new(cargo))]),
+        &HashMap::new(),
+        &HashMap::new(),
+        &HashMap::new(),
+        &HashMap::new(),
+        &HashMap::new(),
+        &treasuries,
+    );
+    let projectiles = advance_seconds(&mut game_state, 1)
+        .into_iter()
+        .flat_map(|response| {
+            match response {
+                GameResponse::ProjectilesAdded(projectiles) => projectiles,
+                _ => vec![],
+            }
+        })
+        .map(|projectile| projectile.fired_from())
+        .collect::<Vec<_>>();
+    assert_eq!(projectiles, sources[.. 1]);
+    assert_eq!(pending_shots(&game_state), sources[1 ..]);
+    assert_eq!(
+        game_state.next_pending_shot(military_base_id),
+        Some(sources[1])
+    );
+}
+
+#[test]
+fn test_selling_a_transport_refunds_half_of_its_cost_to_the_train_factory() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let location = station
+        .station_exit_tile_tracks()
+        .into_iter()
+        .find_map(|exit| station.transport_location_at_station(exit.tile, exit.pointing_in))
+        .unwrap();
+    let train = transport_at(
+        player_id,
+        TransportType::cargo_train(ResourceType::Coal),
+        location,
+        station.id(),
+    );
+    game_state
+        .purchase_transport(player_id, station.id(), &train)
+        .expect("Failed to purchase the train");
+    let rolling_stock = |game_state: &GameState| {
+        train_factory(game_state, player_id)
+            .cargo()
+            .get(ResourceType::RollingStock)
+    };
+    let before = rolling_stock(&game_state);
+
+    assert_eq!(
+        game_state.attempt_to_remove_transport(player_id, train.transport_id()),
+        Ok(())
+    );
+    assert!(
+        game_state
+            .get_transport_info(train.transport_id())
+            .is_none()
+    );
+    assert_eq!(rolling_stock(&game_state), before + CargoAmount::new(0.5));
+}
+
+#[test]
+fn test_selling_a_transport_fails_without_a_linked_train_factory() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let location = station
+        .station_exit_tile_tracks()
+        .into_iter()
+        .find_map(|exit| station.transport_location_at_station(exit.tile, exit.pointing_in))
+        .unwrap();
+    let train = transport_at(
+        player_id,
+        TransportType::cargo_train(ResourceType::Coal),
+        location,
+        station.id(),
+    );
+    game_state
+        .purchase_transport(player_id, station.id(), &train)
+        .expect("Failed to purchase the train");
+
+    let factory_id = train_factory(&game_state, player_id).id();
+    game_state
+        .remove_industry_building(player_id, factory_id)
+        .expect("Failed to remove the train factory");
+
+    assert_eq!(
+        game_state.attempt_to_remove_transport(player_id, train.transport_id()),
+        Err(())
+    );
+    assert!(
+        game_state
+            .get_transport_info(train.transport_id())
+            .is_some()
+    );
+}
+
+#[test]
+fn test_removing_a_station_cancels_its_trade_offers() {
+    let (mut game_state, player_id, station) = game_state_with_station();
+    let other_player_id = game_state.players().ids()[1];
+
+    let trade_offer = TradeOffer::new(
+        TradeOfferId::random(),
+        player_id,
+        station.id(),
+        CargoMap::new(),
+        other_player_id,
+        CargoMap::single(ResourceType::Coal, 1.0),
+    );
+    game_state
+        .offer_trade(player_id, &trade_offer)
+        .expect("Failed to offer trade");
+    assert_eq!(
+        game_state.trade_offers_from_station(station.id()),
+        vec![trade_offer.id()]
+    );
+
+    game_state
+        .remove_station(player_id, station.id())
+        .expect("Failed to remove the station");
+    assert!(
+        game_state
+            .players()
+            .find_trade_offer(trade_offer.id())
+            .is_none()
+    );
+    assert!(
+        game_state
+            .trade_offers_from_station(station.id())
+            .is_empty()
+    );
+}
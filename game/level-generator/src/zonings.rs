@@ -1,6 +1,8 @@
 use itertools::Itertools;
 use shared_domain::building::WithRelativeTileCoverage;
 use shared_domain::building::industry_type::IndustryType;
+use shared_domain::cargo_amount::CargoAmount;
+use shared_domain::map_level::deposit::Deposit;
 use shared_domain::map_level::map_level::MapLevel;
 use shared_domain::map_level::zoning::{ZoningInfo, ZoningType};
 use shared_domain::resource_type::ResourceType;
@@ -38,8 +40,27 @@ fn options(map_level: &MapLevel) -> Vec<TileCoordsXZ> {
     result
 }
 
+const MIN_DEPOSIT_SIZE: f32 = 2_000.0;
+const MAX_DEPOSIT_SIZE: f32 = 6_000.0;
+fn random_deposit(zoning_type: ZoningType) -> Option<Deposit> {
+    match zoning_type {
+        // Farms and forests regrow, so only the mined resources run out
+        ZoningType::Industrial
+        | ZoningType::Source(ResourceType::FarmProducts | ResourceType::Wood) => None,
+        ZoningType::Source(_) => {
+            let size = MIN_DEPOSIT_SIZE + fastrand::f32() * (MAX_DEPOSIT_SIZE - MIN_DEPOSIT_SIZE);
+            Some(Deposit::new(CargoAmount::new(size)))
+        },
+    }
+}
+
 fn add_zoning(map_level: &mut MapLevel, zoning_type: ZoningType, tile: TileCoordsXZ) {
-    let zoning = ZoningInfo::new(ZoningId::random(), zoning_type, tile);
+    let zoning = ZoningInfo::new(
+        ZoningId::random(),
+        zoning_type,
+        tile,
+        random_deposit(zoning_type),
+    );
 
     map_level.zoning_mut().add_zoning(zoning);
 }
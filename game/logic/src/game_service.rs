@@ -1,22 +1,32 @@
 #![allow(clippy::unnecessary_wraps, clippy::missing_errors_doc)]
 
 use bimap::BiMap;
+use shared_domain::building::BuildError;
+use shared_domain::building::bridge_info::BridgeInfo;
 use shared_domain::building::building_info::{WithBuildingDynamicInfo, WithOwner};
 use shared_domain::building::industry_building_info::IndustryBuildingInfo;
 use shared_domain::building::military_building_info::MilitaryBuildingInfo;
+use shared_domain::building::power_line_info::PowerLineInfo;
 use shared_domain::building::station_info::StationInfo;
 use shared_domain::building::track_info::TrackInfo;
+use shared_domain::building::waypoint_info::WaypointInfo;
 use shared_domain::client_command::{DemolishSelector, GameCommand};
 use shared_domain::game_state::GameState;
 use shared_domain::game_time::GameTimeDiff;
 use shared_domain::metrics::Metrics;
+use shared_domain::players::trade_offer::TradeOffer;
 use shared_domain::scenario::Scenario;
 use shared_domain::server_response::{
     AddressEnvelope, GameError, GameInfo, GameResponse, UserInfo,
 };
 use shared_domain::transport::movement_orders::MovementOrders;
+use shared_domain::transport::track_grade::TrackGrade;
+use shared_domain::transport::transport_group::TransportGroupInfo;
 use shared_domain::transport::transport_info::TransportInfo;
-use shared_domain::{GameId, PlayerId, StationId, TransportId, UserId};
+use shared_domain::{
+    GameId, IndustryBuildingId, PlayerId, StationId, TrackId, TradeOfferId, TransportGroupId,
+    TransportId, UserId,
+};
 
 // Public only for tests
 #[derive(Clone, Debug)]
@@ -87,15 +97,36 @@ impl GameService {
             GameCommand::BuildIndustryBuilding(industry_building) => {
                 self.process_build_industry_building(requesting_player_id, industry_building)
             },
+            GameCommand::UpgradeIndustryBuilding(industry_building_id) => {
+                self.process_upgrade_industry_building(requesting_player_id, *industry_building_id)
+            },
+            GameCommand::SetPreferredSupplier(industry_building_id) => {
+                self.process_set_preferred_supplier(requesting_player_id, *industry_building_id)
+            },
             GameCommand::BuildStation(station) => {
                 self.process_build_station(requesting_player_id, station)
             },
+            GameCommand::ExpandStation(station) => {
+                self.process_expand_station(requesting_player_id, station)
+            },
             GameCommand::BuildMilitaryBuilding(military_building) => {
                 self.process_build_military_building(requesting_player_id, military_building)
             },
+            GameCommand::BuildWaypoint(waypoint) => {
+                self.process_build_waypoint(requesting_player_id, waypoint)
+            },
+            GameCommand::BuildBridge(bridge) => {
+                self.process_build_bridge(requesting_player_id, bridge)
+            },
+            GameCommand::BuildPowerLine(power_line) => {
+                self.process_build_power_line(requesting_player_id, power_line)
+            },
             GameCommand::BuildTracks(track_infos) => {
                 self.process_build_tracks(requesting_player_id, track_infos)
             },
+            GameCommand::UpgradeTracks(track_ids, grade) => {
+                self.process_upgrade_tracks(requesting_player_id, track_ids, *grade)
+            },
             GameCommand::UpdateTransportMovementOrders(transport_id, movement_orders) => {
                 self.process_update_transport_movement_orders(
                     requesting_player_id,
@@ -103,6 +134,34 @@ impl GameService {
                     movement_orders,
                 )
             },
+            GameCommand::CreateTransportGroup(group) => {
+                self.process_create_transport_group(requesting_player_id, group)
+            },
+            GameCommand::AssignTransportToGroup(transport_id, group_id) => {
+                self.state
+                    .assign_transport_to_group(requesting_player_id, *transport_id, *group_id)
+                    .map(|()| vec![self.create_dynamic_info_sync(false)])
+                    .map_err(|()| GameError::UnspecifiedError)
+            },
+            GameCommand::UpdateTransportGroupMovementOrders(group_id, movement_orders) => {
+                self.state
+                    .update_transport_group_movement_orders(
+                        requesting_player_id,
+                        *group_id,
+                        movement_orders,
+                    )
+                    .map_err(|()| GameError::UnspecifiedError)?;
+                Ok(vec![
+                    self.transport_group_updated(*group_id)?,
+                    self.create_dynamic_info_sync(false),
+                ])
+            },
+            GameCommand::SetTransportGroupForceStop(group_id, force_stop) => {
+                self.state
+                    .set_transport_group_force_stop(requesting_player_id, *group_id, *force_stop)
+                    .map(|()| vec![self.create_dynamic_info_sync(false)])
+                    .map_err(|()| GameError::UnspecifiedError)
+            },
             GameCommand::Demolish(demolish_selector) => {
                 self.process_demolish(requesting_player_id, demolish_selector)
             },
@@ -113,6 +172,16 @@ impl GameService {
                 self.state.set_time_factor(*time_factor);
                 Ok(vec![self.create_dynamic_info_sync(true)])
             },
+            GameCommand::RequestStatistics => Ok(self.request_statistics(requesting_player_id)),
+            GameCommand::OfferTrade(trade_offer) => {
+                self.process_offer_trade(requesting_player_id, trade_offer)
+            },
+            GameCommand::AcceptTradeOffer(trade_offer_id, station_id) => {
+                self.process_accept_trade_offer(requesting_player_id, *trade_offer_id, *station_id)
+            },
+            GameCommand::CancelTradeOffer(trade_offer_id) => {
+                self.process_cancel_trade_offer(requesting_player_id, *trade_offer_id)
+            },
         }
     }
 
@@ -126,6 +195,66 @@ impl GameService {
         )])
     }
 
+    fn request_statistics(&self, requesting_player_id: PlayerId) -> Vec<GameResponseWithAddress> {
+        vec![GameResponseWithAddress::new(
+            AddressEnvelope::ToPlayer(self.game_id(), requesting_player_id),
+            GameResponse::Statistics(self.state.statistics().clone()),
+        )]
+    }
+
+    fn process_offer_trade(
+        &mut self,
+        requesting_player_id: PlayerId,
+        trade_offer: &TradeOffer,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .offer_trade(requesting_player_id, trade_offer)
+            .map(|()| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::TradeOffered(trade_offer.clone()),
+                )]
+            })
+            .map_err(|error| GameError::CannotOfferTrade(trade_offer.id(), error))
+    }
+
+    // The stations' cargo is then sent in the sync, as it is for the trains
+    fn process_accept_trade_offer(
+        &mut self,
+        requesting_player_id: PlayerId,
+        trade_offer_id: TradeOfferId,
+        station_id: StationId,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .accept_trade(requesting_player_id, trade_offer_id, station_id)
+            .map(|_| {
+                vec![
+                    GameResponseWithAddress::new(
+                        AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                        GameResponse::TradeOfferAccepted(trade_offer_id),
+                    ),
+                    self.create_dynamic_info_sync(false),
+                ]
+            })
+            .map_err(|error| GameError::CannotAcceptTradeOffer(trade_offer_id, error))
+    }
+
+    fn process_cancel_trade_offer(
+        &mut self,
+        requesting_player_id: PlayerId,
+        trade_offer_id: TradeOfferId,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .cancel_trade_offer(requesting_player_id, trade_offer_id)
+            .map(|()| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::TradeOfferCancelled(trade_offer_id),
+                )]
+            })
+            .map_err(|error| GameError::CannotCancelTradeOffer(trade_offer_id, error))
+    }
+
     fn process_build_industry_building(
         &mut self,
         requesting_player_id: PlayerId,
@@ -133,15 +262,48 @@ impl GameService {
     ) -> Result<Vec<GameResponseWithAddress>, GameError> {
         self.state
             .build_industry_building(requesting_player_id, industry_building)
-            .map(|()| {
+            .map(|built| {
                 vec![GameResponseWithAddress::new(
                     AddressEnvelope::ToAllPlayersInGame(self.game_id()),
-                    GameResponse::IndustryBuildingAdded(industry_building.clone()),
+                    GameResponse::IndustryBuildingAdded(built),
                 )]
             })
             .map_err(|error| GameError::CannotBuildIndustryBuilding(industry_building.id(), error))
     }
 
+    fn process_upgrade_industry_building(
+        &mut self,
+        requesting_player_id: PlayerId,
+        industry_building_id: IndustryBuildingId,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .upgrade_industry_building(requesting_player_id, industry_building_id)
+            .map(|upgraded| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::IndustryBuildingUpgraded(upgraded),
+                )]
+            })
+            .map_err(|error| GameError::CannotUpgradeIndustryBuilding(industry_building_id, error))
+    }
+
+    // Only the owner cares, but the other players' game states should not diverge either
+    fn process_set_preferred_supplier(
+        &mut self,
+        requesting_player_id: PlayerId,
+        industry_building_id: IndustryBuildingId,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .set_preferred_supplier(requesting_player_id, industry_building_id)
+            .map(|()| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::PreferredSupplierSet(industry_building_id),
+                )]
+            })
+            .map_err(|error| GameError::CannotSetPreferredSupplier(industry_building_id, error))
+    }
+
     fn process_build_station(
         &mut self,
         requesting_player_id: PlayerId,
@@ -158,6 +320,28 @@ impl GameService {
             .map_err(|error| GameError::CannotBuildStation(station.id(), error))
     }
 
+    fn process_expand_station(
+        &mut self,
+        requesting_player_id: PlayerId,
+        station: &StationInfo,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .expand_station(requesting_player_id, station)
+            .and_then(|()| {
+                self.state
+                    .building_state()
+                    .find_station(station.id())
+                    .ok_or(BuildError::UnknownError)
+            })
+            .map(|expanded| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::StationExpanded(expanded.clone()),
+                )]
+            })
+            .map_err(|error| GameError::CannotBuildStation(station.id(), error))
+    }
+
     fn process_build_military_building(
         &mut self,
         requesting_player_id: PlayerId,
@@ -174,6 +358,54 @@ impl GameService {
             .map_err(|error| GameError::CannotBuildMilitaryBuilding(military_building.id(), error))
     }
 
+    fn process_build_waypoint(
+        &mut self,
+        requesting_player_id: PlayerId,
+        waypoint: &WaypointInfo,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .build_waypoint(requesting_player_id, waypoint)
+            .map(|()| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::WaypointAdded(waypoint.clone()),
+                )]
+            })
+            .map_err(|error| GameError::CannotBuildWaypoint(waypoint.id(), error))
+    }
+
+    fn process_build_bridge(
+        &mut self,
+        requesting_player_id: PlayerId,
+        bridge: &BridgeInfo,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .build_bridge(requesting_player_id, bridge)
+            .map(|()| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::BridgeAdded(bridge.clone()),
+                )]
+            })
+            .map_err(|error| GameError::CannotBuildBridge(bridge.id(), error))
+    }
+
+    fn process_build_power_line(
+        &mut self,
+        requesting_player_id: PlayerId,
+        power_line: &PowerLineInfo,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .build_power_line(requesting_player_id, power_line)
+            .map(|()| {
+                vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::PowerLineAdded(power_line.clone()),
+                )]
+            })
+            .map_err(|error| GameError::CannotBuildPowerLine(power_line.id(), error))
+    }
+
     fn process_build_tracks(
         &mut self,
         requesting_player_id: PlayerId,
@@ -195,6 +427,26 @@ impl GameService {
         }
     }
 
+    fn process_upgrade_tracks(
+        &mut self,
+        requesting_player_id: PlayerId,
+        track_ids: &[TrackId],
+        grade: TrackGrade,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        match self
+            .state
+            .upgrade_tracks(requesting_player_id, track_ids, grade)
+        {
+            Ok(upgraded) => {
+                Ok(vec![GameResponseWithAddress::new(
+                    AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                    GameResponse::TracksUpgraded(upgraded),
+                )])
+            },
+            Err(error) => Err(GameError::CannotUpgradeTracks(track_ids.to_vec(), error)),
+        }
+    }
+
     fn process_purchase_transport(
         &mut self,
         requesting_player_id: PlayerId,
@@ -225,6 +477,14 @@ impl GameService {
         requesting_player_id: PlayerId,
         demolish_selector: &DemolishSelector,
     ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        let cannot_demolish = |()| GameError::CannotDemolish(demolish_selector.clone());
+        // Removing a station also cancels the trade offers of its cargo
+        let cancelled_trade_offers = match demolish_selector {
+            DemolishSelector::Station(station_id) => {
+                self.state.trade_offers_from_station(*station_id)
+            },
+            _ => vec![],
+        };
         match demolish_selector {
             DemolishSelector::Tracks(track_ids) => {
                 self.state
@@ -235,25 +495,86 @@ impl GameService {
                 self.state
                     .remove_industry_building(requesting_player_id, *industry_building_id)
                     .map(|()| GameResponse::IndustryBuildingRemoved(*industry_building_id))
+                    .map_err(cannot_demolish)
             },
             DemolishSelector::Station(station_id) => {
                 self.state
                     .remove_station(requesting_player_id, *station_id)
                     .map(|()| GameResponse::StationRemoved(*station_id))
+                    .map_err(cannot_demolish)
             },
             DemolishSelector::MilitaryBuilding(military_building_id) => {
                 self.state
                     .remove_military_building(requesting_player_id, *military_building_id)
                     .map(|()| GameResponse::MilitaryBuildingRemoved(*military_building_id))
+                    .map_err(cannot_demolish)
+            },
+            DemolishSelector::Waypoint(waypoint_id) => {
+                self.state
+                    .remove_waypoint(requesting_player_id, *waypoint_id)
+                    .map(|()| GameResponse::WaypointRemoved(*waypoint_id))
+                    .map_err(cannot_demolish)
+            },
+            DemolishSelector::Bridge(bridge_id) => {
+                self.state
+                    .remove_bridge(requesting_player_id, *bridge_id)
+                    .map(|()| GameResponse::BridgeRemoved(*bridge_id))
+                    .map_err(cannot_demolish)
+            },
+            DemolishSelector::PowerLine(power_line_id) => {
+                self.state
+                    .remove_power_line(requesting_player_id, *power_line_id)
+                    .map(|()| GameResponse::PowerLineRemoved(*power_line_id))
+                    .map_err(cannot_demolish)
+            },
+            DemolishSelector::Transport(transport_id) => {
+                self.state
+                    .attempt_to_remove_transport(requesting_player_id, *transport_id)
+                    .map(|()| GameResponse::TransportsRemoved(vec![*transport_id]))
+                    .map_err(cannot_demolish)
             },
         }
         .map(|success| {
-            vec![GameResponseWithAddress::new(
-                AddressEnvelope::ToAllPlayersInGame(self.game_id()),
-                success,
-            )]
+            std::iter::once(success)
+                .chain(
+                    cancelled_trade_offers
+                        .into_iter()
+                        .map(GameResponse::TradeOfferCancelled),
+                )
+                .map(|response| {
+                    GameResponseWithAddress::new(
+                        AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+                        response,
+                    )
+                })
+                .collect()
         })
-        .map_err(|()| GameError::CannotDemolish(demolish_selector.clone()))
+    }
+
+    fn process_create_transport_group(
+        &mut self,
+        requesting_player_id: PlayerId,
+        group: &TransportGroupInfo,
+    ) -> Result<Vec<GameResponseWithAddress>, GameError> {
+        self.state
+            .create_transport_group(requesting_player_id, group)
+            .map_err(|()| GameError::UnspecifiedError)?;
+        Ok(vec![self.transport_group_updated(group.group_id())?])
+    }
+
+    fn transport_group_updated(
+        &self,
+        group_id: TransportGroupId,
+    ) -> Result<GameResponseWithAddress, GameError> {
+        let group = self
+            .state
+            .transport_state()
+            .group_by_id(group_id)
+            .ok_or(GameError::UnspecifiedError)?;
+        Ok(GameResponseWithAddress::new(
+            AddressEnvelope::ToAllPlayersInGame(self.game_id()),
+            GameResponse::TransportGroupsUpdated(vec![group.clone()]),
+        ))
     }
 
     fn process_update_transport_movement_orders(
@@ -267,13 +588,21 @@ impl GameService {
             .transport_state()
             .info_by_id(transport_id)
             .ok_or(GameError::UnspecifiedError)?;
+        let group_id = transport.group_id();
         if transport.owner_id() == requesting_player_id {
             match self
                 .state
                 .update_transport_movement_orders(transport_id, movement_orders)
             {
                 Err(()) => Err(GameError::UnspecifiedError),
-                Ok(()) => Ok(vec![self.create_dynamic_info_sync(false)]),
+                Ok(()) => {
+                    let mut results = vec![self.create_dynamic_info_sync(false)];
+                    // Changing the orders of a grouped transport changes the orders of the group
+                    if let Some(group_id) = group_id {
+                        results.push(self.transport_group_updated(group_id)?);
+                    }
+                    Ok(results)
+                },
             }
         } else {
             Err(GameError::UnspecifiedError)
@@ -410,6 +739,7 @@ impl GameService {
                     .into_iter()
                     .map(|building| (building.id(), building.dynamic_info().clone()))
                     .collect(),
+                self.state.building_state().construction_sites().clone(),
                 self.state
                     .transport_infos()
                     .iter()
@@ -425,6 +755,7 @@ impl GameService {
                         )
                     })
                     .collect(),
+                self.state.players().treasuries().clone(),
             ),
         )
     }
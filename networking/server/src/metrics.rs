@@ -1,5 +1,5 @@
 use bevy::prelude::Resource;
-use metrics::{Histogram, Key, Level, Recorder, metadata_var};
+use metrics::{Counter, Histogram, Key, Level, Recorder, metadata_var};
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle, PrometheusRecorder};
 use shared_domain::metrics::Metrics;
 use shared_domain::transport::track_length::TrackLength;
@@ -12,6 +12,8 @@ pub struct PrometheusMetrics {
     prometheus_handle:           PrometheusHandle,
     track_pathfinding_histogram: Histogram,
     track_planning_histogram:    Histogram,
+    route_cache_hit_counter:     Counter,
+    route_cache_miss_counter:    Counter,
 }
 
 impl Metrics for PrometheusMetrics {
@@ -26,6 +28,14 @@ impl Metrics for PrometheusMetrics {
     fn track_planning_duration(&self, duration: Duration, _result: Option<(usize, TrackLength)>) {
         self.track_planning_histogram.record(duration);
     }
+
+    fn route_cache_hit(&self) {
+        self.route_cache_hit_counter.increment(1);
+    }
+
+    fn route_cache_miss(&self) {
+        self.route_cache_miss_counter.increment(1);
+    }
 }
 
 impl PrometheusMetrics {
@@ -35,6 +45,12 @@ impl PrometheusMetrics {
         recorder.register_histogram(&key, metadata)
     }
 
+    fn create_counter(recorder: &PrometheusRecorder, name: &'static str) -> Counter {
+        let key = Key::from_static_name(name);
+        let metadata = metadata_var!(module_path!(), Level::INFO);
+        recorder.register_counter(&key, metadata)
+    }
+
     #[must_use]
     pub fn new() -> Self {
         let recorder = PrometheusBuilder::new().build_recorder();
@@ -42,11 +58,15 @@ impl PrometheusMetrics {
         let track_pathfinding_histogram =
             Self::create_histogram(&recorder, "track_pathfinding_duration");
         let track_planning_histogram = Self::create_histogram(&recorder, "track_planning_duration");
+        let route_cache_hit_counter = Self::create_counter(&recorder, "route_cache_hits");
+        let route_cache_miss_counter = Self::create_counter(&recorder, "route_cache_misses");
 
         Self {
             prometheus_handle,
             track_pathfinding_histogram,
             track_planning_histogram,
+            route_cache_hit_counter,
+            route_cache_miss_counter,
         }
     }
 
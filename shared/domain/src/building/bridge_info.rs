@@ -0,0 +1,198 @@
+use std::fmt::{Debug, Formatter};
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+use shared_util::direction_xz::DirectionXZ;
+
+use crate::building::building_info::{WithCostToBuild, WithOwner, WithTileCoverage};
+use crate::building::industry_type::IndustryType;
+use crate::cargo_map::CargoMap;
+use crate::resource_type::ResourceType;
+use crate::tile_coords_xz::{TileCoordsXZ, TileDistance};
+use crate::tile_coverage::TileCoverage;
+use crate::transport::track_length::TrackLength;
+use crate::transport::track_type::TrackType;
+use crate::{BridgeId, PlayerId};
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BridgeType {
+    Bridge,
+    Tunnel,
+}
+
+impl BridgeType {
+    #[must_use]
+    pub const fn all() -> [Self; 2] {
+        [Self::Bridge, Self::Tunnel]
+    }
+
+    #[must_use]
+    pub const fn length_in_tiles_range(self) -> RangeInclusive<usize> {
+        match self {
+            BridgeType::Bridge => 2 ..= 8,
+            BridgeType::Tunnel => 2 ..= 12,
+        }
+    }
+
+    // How much more expensive this is than building regular tracks, used when planning
+    #[must_use]
+    pub const fn planning_cost_coef(self) -> f32 {
+        match self {
+            BridgeType::Bridge => 3.0,
+            BridgeType::Tunnel => 4.0,
+        }
+    }
+
+    fn cost_per_tile(self) -> CargoMap {
+        match self {
+            BridgeType::Bridge => {
+                CargoMap::from([(ResourceType::Steel, 0.04), (ResourceType::Timber, 0.01)])
+            },
+            BridgeType::Tunnel => {
+                CargoMap::from([(ResourceType::Steel, 0.01), (ResourceType::Timber, 0.04)])
+            },
+        }
+    }
+}
+
+// Bridges and tunnels are straight spans of track covering multiple tiles, which trains traverse
+// without stopping, so they can cross water or steep terrain that regular tracks cannot.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct BridgeInfo {
+    id:              BridgeId,
+    owner_id:        PlayerId,
+    bridge_type:     BridgeType,
+    // The first tile covered when travelling in `direction`
+    start:           TileCoordsXZ,
+    direction:       DirectionXZ,
+    length_in_tiles: usize,
+}
+
+impl Debug for BridgeInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {:?} {:?} {:?} x{}",
+            self.id, self.bridge_type, self.start, self.direction, self.length_in_tiles
+        )
+    }
+}
+
+impl BridgeInfo {
+    #[must_use]
+    pub fn new(
+        id: BridgeId,
+        owner_id: PlayerId,
+        bridge_type: BridgeType,
+        start: TileCoordsXZ,
+        direction: DirectionXZ,
+        length_in_tiles: usize,
+    ) -> Self {
+        Self {
+            id,
+            owner_id,
+            bridge_type,
+            start,
+            direction,
+            length_in_tiles,
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> BridgeId {
+        self.id
+    }
+
+    #[must_use]
+    pub fn bridge_type(&self) -> BridgeType {
+        self.bridge_type
+    }
+
+    #[must_use]
+    pub fn direction(&self) -> DirectionXZ {
+        self.direction
+    }
+
+    #[must_use]
+    pub fn length_in_tiles(&self) -> usize {
+        self.length_in_tiles
+    }
+
+    #[must_use]
+    pub fn start_tile(&self) -> TileCoordsXZ {
+        self.start
+    }
+
+    #[must_use]
+    pub fn end_tile(&self) -> TileCoordsXZ {
+        self.tile_at(self.length_in_tiles.saturating_sub(1))
+    }
+
+    #[must_use]
+    #[expect(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn tile_at(&self, index: usize) -> TileCoordsXZ {
+        let step = TileCoordsXZ::ZERO + self.direction;
+        let index = index as TileDistance;
+        TileCoordsXZ::new(self.start.x + step.x * index, self.start.z + step.z * index)
+    }
+
+    #[must_use]
+    pub fn tiles(&self) -> Vec<TileCoordsXZ> {
+        (0 .. self.length_in_tiles)
+            .map(|index| self.tile_at(index))
+            .collect()
+    }
+
+    // The other end of the bridge, if `tile` is one of its ends and `direction` leads onto it
+    #[must_use]
+    pub fn far_end(&self, tile: TileCoordsXZ, direction: DirectionXZ) -> Option<TileCoordsXZ> {
+        if tile == self.start_tile() && direction == self.direction {
+            Some(self.end_tile())
+        } else if tile == self.end_tile() && direction == self.direction.reverse() {
+            Some(self.start_tile())
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn track_length(&self) -> TrackLength {
+        self.track_type().length() * self.length_in_tiles as f32
+    }
+
+    #[must_use]
+    pub fn track_type(&self) -> TrackType {
+        match self.direction {
+            DirectionXZ::North | DirectionXZ::South => TrackType::NorthSouth,
+            DirectionXZ::East | DirectionXZ::West => TrackType::WestEast,
+        }
+    }
+}
+
+impl WithOwner for BridgeInfo {
+    fn owner_id(&self) -> PlayerId {
+        self.owner_id
+    }
+}
+
+impl WithTileCoverage for BridgeInfo {
+    fn covers_tiles(&self) -> TileCoverage {
+        let start = self.start;
+        let end = self.end_tile();
+        TileCoverage::Rectangular {
+            north_west_inclusive: TileCoordsXZ::new(start.x.min(end.x), start.z.min(end.z)),
+            south_east_inclusive: TileCoordsXZ::new(start.x.max(end.x), start.z.max(end.z)),
+        }
+    }
+}
+
+impl WithCostToBuild for BridgeInfo {
+    #[expect(clippy::cast_precision_loss)]
+    fn cost_to_build(&self) -> (IndustryType, CargoMap) {
+        (
+            IndustryType::ConstructionYard,
+            self.bridge_type.cost_per_tile() * self.length_in_tiles as f32,
+        )
+    }
+}
@@ -3,8 +3,12 @@ use std::fmt::{Debug, Formatter};
 use serde::{Deserialize, Serialize};
 
 use crate::PlayerId;
+use crate::building::construction_site::ConstructionSite;
 use crate::building::industry_type::IndustryType;
+use crate::cargo_age::CargoAge;
 use crate::cargo_map::{CargoMap, WithCargo, WithCargoMut};
+use crate::game_time::GameTimeDiff;
+use crate::map_level::deposit::Deposit;
 use crate::tile_coords_xz::TileCoordsXZ;
 use crate::tile_coverage::TileCoverage;
 
@@ -49,13 +53,80 @@ impl BuildingStaticInfo {
 
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct BuildingDynamicInfo {
-    cargo: CargoMap,
+    cargo:        CargoMap,
+    // Only kept for the perishable resources in the stockpile
+    cargo_age:    CargoAge,
+    // For industries, the share of the full production rate achieved in the last tick. For
+    // stations, how full the fullest stockpile is. Low (or high) values show the bottlenecks.
+    utilisation:  f32,
+    // For mines, what is left of the deposit being extracted
+    deposit:      Option<Deposit>,
+    // For buildings that are not finished yet, how far along the construction is
+    construction: Option<ConstructionSite>,
 }
 
 impl BuildingDynamicInfo {
     #[must_use]
     pub fn new(cargo: CargoMap) -> Self {
-        Self { cargo }
+        Self {
+            cargo,
+            cargo_age: CargoAge::fresh(),
+            utilisation: 0.0,
+            deposit: None,
+            construction: None,
+        }
+    }
+
+    #[must_use]
+    pub fn construction(&self) -> Option<ConstructionSite> {
+        self.construction
+    }
+
+    pub(crate) fn set_construction(&mut self, construction: Option<ConstructionSite>) {
+        self.construction = construction;
+    }
+
+    pub(crate) fn construction_mut(&mut self) -> Option<&mut ConstructionSite> {
+        self.construction.as_mut()
+    }
+
+    #[must_use]
+    pub fn deposit(&self) -> Option<Deposit> {
+        self.deposit
+    }
+
+    pub(crate) fn set_deposit(&mut self, deposit: Option<Deposit>) {
+        self.deposit = deposit;
+    }
+
+    pub(crate) fn deposit_mut(&mut self) -> Option<&mut Deposit> {
+        self.deposit.as_mut()
+    }
+
+    #[must_use]
+    pub fn cargo_age(&self) -> &CargoAge {
+        &self.cargo_age
+    }
+
+    pub(crate) fn add_aged_cargo(&mut self, cargo: &CargoMap, age: &CargoAge) {
+        self.cargo_age.mix(&self.cargo, cargo, age);
+        self.cargo += cargo;
+    }
+
+    // Ages the stockpile, with part of the perishable cargo spoiling
+    pub(crate) fn age_cargo(&mut self, diff: GameTimeDiff) {
+        let spoiled = CargoAge::spoiled(&self.cargo, diff);
+        self.cargo -= &spoiled;
+        self.cargo_age.advance(&self.cargo, diff);
+    }
+
+    #[must_use]
+    pub fn utilisation(&self) -> f32 {
+        self.utilisation
+    }
+
+    pub(crate) fn set_utilisation(&mut self, utilisation: f32) {
+        self.utilisation = utilisation;
     }
 }
 
@@ -73,7 +144,14 @@ impl WithCargoMut for &mut BuildingDynamicInfo {
 
 impl Debug for BuildingDynamicInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.cargo)
+        write!(f, "{:?} {:.0}%", self.cargo, self.utilisation * 100.0)?;
+        if let Some(deposit) = self.deposit {
+            write!(f, " {deposit:?}")?;
+        }
+        if let Some(construction) = self.construction {
+            write!(f, " {construction:?}")?;
+        }
+        Ok(())
     }
 }
 
@@ -1,40 +1,59 @@
 #![allow(clippy::missing_errors_doc, clippy::result_unit_err)]
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Formatter};
 
-use log::{trace, warn};
+use log::{info, trace, warn};
 use serde::{Deserialize, Serialize};
 use shared_util::bool_ops::BoolResultOps;
 use shared_util::direction_xz::DirectionXZ;
 use shared_util::grid_xz::GridXZ;
 
+use crate::building::bridge_info::BridgeInfo;
 use crate::building::building_info::{
-    BuildingDynamicInfo, BuildingInfo, WithBuildingDynamicInfoMut, WithCostToBuild, WithOwner,
-    WithTileCoverage,
+    BuildingDynamicInfo, BuildingInfo, WithBuildingDynamicInfo, WithBuildingDynamicInfoMut,
+    WithCostToBuild, WithOwner, WithTileCoverage,
 };
+use crate::building::construction_site::{ConstructionSite, ConstructionTarget};
 use crate::building::industry_building_info::IndustryBuildingInfo;
 use crate::building::industry_type::IndustryType;
 use crate::building::military_building_info::{MilitaryBuildingDynamicInfo, MilitaryBuildingInfo};
 use crate::building::military_building_type::MilitaryBuildingType;
+use crate::building::power_grid::PowerGrids;
+use crate::building::power_line_info::PowerLineInfo;
 use crate::building::station_info::StationInfo;
 use crate::building::station_type::StationType;
 use crate::building::track_info::TrackInfo;
 use crate::building::track_state::{MaybeTracksOnTile, TrackState};
+use crate::building::waypoint_info::WaypointInfo;
 use crate::building::{BuildCosts, BuildError};
 use crate::cargo_map::{CargoMap, CargoOps, WithCargo, WithCargoMut};
 use crate::client_command::InternalGameCommand;
 use crate::game_state::GameState;
 use crate::game_time::{GameTime, GameTimeDiff};
+use crate::metrics::Metrics;
+use crate::military::{AmmunitionStatus, ProjectileSource, ShellType};
+use crate::money::Money;
+use crate::players::trade_offer::{TradeError, TradeOffer};
 use crate::resource_type::ResourceType;
+use crate::statistics::StatisticsState;
 use crate::supply_chain::SupplyChain;
+use crate::tile_coords_xz::TileDistance;
 use crate::tile_coverage::TileCoverage;
+use crate::transport::route_cache::RouteCache;
+use crate::transport::tile_track::TileTrack;
+use crate::transport::track_grade::TrackGrade;
+use crate::transport::track_length::TrackLength;
 use crate::transport::track_type_set::TrackTypeSet;
 use crate::transport::transport_type::TransportType;
 use crate::{
-    IndustryBuildingId, MilitaryBuildingId, PlayerId, StationId, TileCoordsXZ, TrackId, TrackType,
+    BridgeId, IndustryBuildingId, MilitaryBuildingId, PlayerId, PowerLineId, StationId,
+    TileCoordsXZ, TrackId, TrackType, WaypointId,
 };
 
+// Building also costs money, proportional to the value of the resources used
+const MONEY_PER_RESOURCE_VALUE: f64 = 100.0;
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum CanBuildResponse {
     Ok,
@@ -48,20 +67,39 @@ enum TileBuildingStatus {
     IndustryBuilding(IndustryBuildingId),
     MilitaryBuilding(MilitaryBuildingId),
     Station(StationId, TrackTypeSet),
+    Bridge(BridgeId),
 }
 
 // Later: There is a dual nature here to both be the "validator" (check if something can be built) and the "state" (store what has been built).
 // Later: Refactor to store also as a `FieldXZ` so that lookup by tile is efficient
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct BuildingState {
-    tracks:               TrackState,
-    industry_buildings:   HashMap<IndustryBuildingId, IndustryBuildingInfo>,
-    tile_buildings:       GridXZ<TileCoordsXZ, TileBuildingStatus>,
-    military_buildings:   HashMap<MilitaryBuildingId, MilitaryBuildingInfo>,
-    stations:             HashMap<StationId, StationInfo>,
-    // Link from each industry building to the closest station
-    // Later: Should these be 1:1, N:1 or N:M correspondence between industry & station? Is it a problem if a station can accept & provide the same good and thus does not need trains?
-    closest_station_link: HashMap<IndustryBuildingId, StationId>,
+    tracks:              TrackState,
+    industry_buildings:  HashMap<IndustryBuildingId, IndustryBuildingInfo>,
+    tile_buildings:      GridXZ<TileCoordsXZ, TileBuildingStatus>,
+    military_buildings:  HashMap<MilitaryBuildingId, MilitaryBuildingInfo>,
+    stations:            HashMap<StationId, StationInfo>,
+    // Waypoints do not occupy `tile_buildings` as they are placed on top of tracks
+    waypoints:           HashMap<WaypointId, WaypointInfo>,
+    bridges:             HashMap<BridgeId, BridgeInfo>,
+    // Power lines do not occupy `tile_buildings` either, as they can run above tracks, so they have
+    // their own index by tile
+    power_lines:         HashMap<PowerLineId, PowerLineInfo>,
+    power_line_tiles:    GridXZ<TileCoordsXZ, Option<PowerLineId>>,
+    // Recalculated whenever the power lines or the industries change, with the loads updated every tick
+    power_grids:         PowerGrids,
+    // Links from each industry building to the stations whose catchment area it is in, the closest first
+    // Later: Is it a problem if a station can accept & provide the same good and thus does not need trains?
+    station_links:       HashMap<IndustryBuildingId, Vec<StationId>>,
+    // The supply buildings the players chose to pay from, when several in range could pay
+    preferred_suppliers: HashSet<IndustryBuildingId>,
+    // The artillery waiting for a military base that ran out of ammunition, in the order it started
+    // waiting, so that the base supplies the shots first come, first served once it is restocked
+    pending_shots:       HashMap<IndustryBuildingId, VecDeque<ProjectileSource>>,
+    // Everything other than the industries that is paid for, but cannot be used until it is built
+    construction_sites:  HashMap<ConstructionTarget, ConstructionSite>,
+    #[serde(skip)]
+    route_cache:         RouteCache,
 }
 
 impl Debug for BuildingState {
@@ -79,16 +117,25 @@ impl BuildingState {
     #[must_use]
     pub fn new(size_x: usize, size_z: usize) -> Self {
         Self {
-            tracks:               TrackState::new(size_x, size_z),
-            industry_buildings:   HashMap::new(),
-            tile_buildings:       GridXZ::filled_with(size_x, size_z, TileBuildingStatus::Empty),
-            military_buildings:   HashMap::new(),
-            stations:             HashMap::new(),
-            closest_station_link: HashMap::new(),
+            tracks:              TrackState::new(size_x, size_z),
+            industry_buildings:  HashMap::new(),
+            tile_buildings:      GridXZ::filled_with(size_x, size_z, TileBuildingStatus::Empty),
+            military_buildings:  HashMap::new(),
+            stations:            HashMap::new(),
+            waypoints:           HashMap::new(),
+            bridges:             HashMap::new(),
+            power_lines:         HashMap::new(),
+            power_line_tiles:    GridXZ::filled_with(size_x, size_z, None),
+            power_grids:         PowerGrids::default(),
+            station_links:       HashMap::new(),
+            preferred_suppliers: HashSet::new(),
+            pending_shots:       HashMap::new(),
+            construction_sites:  HashMap::new(),
+            route_cache:         RouteCache::default(),
         }
     }
 
-    #[expect(clippy::unwrap_used, clippy::missing_panics_doc, clippy::similar_names)]
+    #[expect(clippy::unwrap_used, clippy::missing_panics_doc)]
     pub fn gift_initial_construction_yard(
         &mut self,
         player_id: PlayerId,
@@ -126,11 +173,26 @@ impl BuildingState {
             let multiplied = cost * 400.0;
             *cargo += &multiplied;
         }
+    }
+
+    #[expect(clippy::unwrap_used, clippy::missing_panics_doc)]
+    pub fn gift_initial_train_factory(&mut self, player_id: PlayerId, tile: TileCoordsXZ) {
+        let train_factory_id = IndustryBuildingId::random();
+        let train_factory = IndustryBuildingInfo::new(
+            player_id,
+            train_factory_id,
+            tile,
+            IndustryType::TrainFactory,
+        );
+        let () = self
+            .build_industry_building(&train_factory, &BuildCosts::none())
+            .unwrap();
+        let train_factory = self.find_industry_building_mut(train_factory_id).unwrap();
 
         let (_, train_cost) = TransportType::cargo_train(ResourceType::Steel).cost_to_build();
-        // TODO: 5.0 is rather arbitrary, we really need the trains for supply chains that produce more trains
-        let trains_cost = train_cost * 5.0;
-        *cargo += &trains_cost;
+        // TODO: 25.0 is rather arbitrary, we really need the trains for supply chains that produce more trains
+        let trains_cost = train_cost * 25.0;
+        *train_factory.cargo_mut() += &trains_cost;
     }
 
     // TODO: Optimize this as it is called often
@@ -140,7 +202,7 @@ impl BuildingState {
         tile: TileCoordsXZ,
         connection: DirectionXZ,
     ) -> impl IntoIterator<Item = TrackType> {
-        let track_types = self.track_types_at(tile);
+        let track_types = self.usable_track_types_at(tile);
         TrackType::matching_direction(connection)
             .into_iter()
             .filter(move |track_type| track_types.contains(*track_type))
@@ -150,17 +212,52 @@ impl BuildingState {
     pub fn track_types_at(&self, tile: TileCoordsXZ) -> TrackTypeSet {
         let from_track = self.tracks.track_types_at(tile);
         if from_track.is_empty() {
-            if let Some(TileBuildingStatus::Station(_, track_types)) = self.tile_buildings.get(tile)
-            {
-                *track_types
-            } else {
-                TrackTypeSet::empty()
+            match self.tile_buildings.get(tile) {
+                Some(TileBuildingStatus::Station(_, track_types)) => *track_types,
+                // Trains move across bridges tile by tile, but the pathfinding treats each bridge as a
+                // single edge, see `track_pathfinding::bridge_crossing`
+                Some(TileBuildingStatus::Bridge(bridge_id)) => {
+                    self.bridges
+                        .get(bridge_id)
+                        .map_or_else(TrackTypeSet::empty, |bridge| {
+                            TrackTypeSet::single(bridge.track_type())
+                        })
+                },
+                _ => TrackTypeSet::empty(),
             }
         } else {
             from_track
         }
     }
 
+    // Trains cannot run on the tracks, stations and bridges that are still under construction
+    #[must_use]
+    pub fn usable_track_types_at(&self, tile: TileCoordsXZ) -> TrackTypeSet {
+        let mut track_types = self.track_types_at(tile);
+        if self.construction_sites.is_empty() {
+            return track_types;
+        }
+        let building_under_construction = match self.tile_buildings.get(tile) {
+            Some(TileBuildingStatus::Station(station_id, _)) => {
+                self.is_under_construction(ConstructionTarget::Station(*station_id))
+            },
+            Some(TileBuildingStatus::Bridge(bridge_id)) => {
+                self.is_under_construction(ConstructionTarget::Bridge(*bridge_id))
+            },
+            _ => false,
+        };
+        if building_under_construction {
+            return TrackTypeSet::empty();
+        }
+        for track_type in track_types {
+            let track_id = TrackId::new(tile, track_type);
+            if self.is_under_construction(ConstructionTarget::Track(track_id)) {
+                track_types.remove(track_type);
+            }
+        }
+        track_types
+    }
+
     #[must_use]
     pub fn building_at(&self, tile: TileCoordsXZ) -> Option<&dyn BuildingInfo> {
         let station = self.station_at(tile);
@@ -186,6 +283,24 @@ impl BuildingState {
         self.tracks.tracks_at(tile)
     }
 
+    // Station and bridge tracks are always of the default grade
+    #[must_use]
+    pub fn track_grade_at(&self, tile: TileCoordsXZ, track_type: TrackType) -> TrackGrade {
+        self.tracks
+            .tracks_at(tile)
+            .grade(track_type)
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub(crate) fn track_running_costs_per_second(&self) -> HashMap<PlayerId, Money> {
+        self.tracks.running_costs_per_second()
+    }
+
+    pub fn set_track_grades(&mut self, tracks: &[TrackInfo]) {
+        self.tracks.set_grades(tracks);
+    }
+
     #[must_use]
     pub fn station_at(&self, tile: TileCoordsXZ) -> Option<&StationInfo> {
         match self.tile_buildings.get(tile) {
@@ -221,13 +336,9 @@ impl BuildingState {
     ) -> HashSet<ResourceType> {
         // Note - we are not checking that the building actually is a station here
         let mut results = HashSet::new();
-        for (industry_building_id, linked_station_id) in self.closest_station_link.clone() {
-            if station_id == linked_station_id {
-                if let Some(building) = self.find_industry_building(industry_building_id) {
-                    for resource_type in building.industry_type().input_resource_types() {
-                        results.insert(resource_type);
-                    }
-                }
+        for building in self.find_linked_industry_buildings(station_id) {
+            for resource_type in building.industry_transform_inputs() {
+                results.insert(resource_type);
             }
         }
         results
@@ -278,6 +389,128 @@ impl BuildingState {
         self.military_buildings.values()
     }
 
+    #[must_use]
+    pub fn all_waypoints(&self) -> impl IntoIterator<Item = &WaypointInfo> {
+        self.waypoints.values()
+    }
+
+    #[must_use]
+    pub fn find_waypoint(&self, waypoint_id: WaypointId) -> Option<&WaypointInfo> {
+        self.waypoints.get(&waypoint_id)
+    }
+
+    #[must_use]
+    pub fn waypoint_at(&self, tile: TileCoordsXZ) -> Option<&WaypointInfo> {
+        self.waypoints
+            .values()
+            .find(|waypoint| waypoint.tile() == tile)
+    }
+
+    #[must_use]
+    pub fn all_bridges(&self) -> impl IntoIterator<Item = &BridgeInfo> {
+        self.bridges.values()
+    }
+
+    #[must_use]
+    pub fn find_bridge(&self, bridge_id: BridgeId) -> Option<&BridgeInfo> {
+        self.bridges.get(&bridge_id)
+    }
+
+    #[must_use]
+    pub fn bridge_at(&self, tile: TileCoordsXZ) -> Option<&BridgeInfo> {
+        match self.tile_buildings.get(tile) {
+            Some(TileBuildingStatus::Bridge(bridge_id)) => self.bridges.get(bridge_id),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn all_power_lines(&self) -> impl IntoIterator<Item = &PowerLineInfo> {
+        self.power_lines.values()
+    }
+
+    #[must_use]
+    pub fn find_power_line(&self, power_line_id: PowerLineId) -> Option<&PowerLineInfo> {
+        self.power_lines.get(&power_line_id)
+    }
+
+    #[must_use]
+    pub fn power_line_at(&self, tile: TileCoordsXZ) -> Option<&PowerLineInfo> {
+        let power_line_id = self.power_line_tiles.get(tile)?.as_ref()?;
+        self.power_lines.get(power_line_id)
+    }
+
+    #[must_use]
+    pub fn power_grids(&self) -> &PowerGrids {
+        &self.power_grids
+    }
+
+    #[must_use]
+    pub fn construction_sites(&self) -> &HashMap<ConstructionTarget, ConstructionSite> {
+        &self.construction_sites
+    }
+
+    #[must_use]
+    pub fn construction_site(&self, target: ConstructionTarget) -> Option<ConstructionSite> {
+        self.construction_sites.get(&target).copied()
+    }
+
+    #[must_use]
+    pub fn is_under_construction(&self, target: ConstructionTarget) -> bool {
+        self.construction_sites.contains_key(&target)
+    }
+
+    // The tiles whose tracks can be used once the construction is finished
+    fn construction_track_tiles(&self, target: ConstructionTarget) -> Vec<TileCoordsXZ> {
+        match target {
+            ConstructionTarget::Station(station_id) => {
+                self.find_station(station_id)
+                    .map(|station| station.covers_tiles().into_iter().collect())
+                    .unwrap_or_default()
+            },
+            ConstructionTarget::Track(track_id) => vec![track_id.tile],
+            ConstructionTarget::Bridge(bridge_id) => {
+                self.find_bridge(bridge_id)
+                    .map(BridgeInfo::tiles)
+                    .unwrap_or_default()
+            },
+            ConstructionTarget::MilitaryBuilding(_)
+            | ConstructionTarget::Waypoint(_)
+            | ConstructionTarget::PowerLine(_) => vec![],
+        }
+    }
+
+    // Starting or finishing the construction changes which tracks can be used and which power lines
+    // are part of the grids
+    fn set_construction_site(
+        &mut self,
+        target: ConstructionTarget,
+        construction_site: Option<ConstructionSite>,
+    ) {
+        let tiles = self.construction_track_tiles(target);
+        self.changing_tracks_at(&tiles, |building_state| {
+            match construction_site {
+                Some(construction_site) => {
+                    building_state
+                        .construction_sites
+                        .insert(target, construction_site);
+                },
+                None => {
+                    building_state.construction_sites.remove(&target);
+                },
+            }
+        });
+        if let ConstructionTarget::PowerLine(_) = target {
+            self.recalculate_power_grids();
+        }
+    }
+
+    // Tracks are electrified by a powered grid running above them
+    #[must_use]
+    pub fn is_electrified(&self, tile: TileCoordsXZ) -> bool {
+        self.power_grids.is_electrified(tile)
+    }
+
     #[must_use]
     pub fn all_track_infos(&self) -> Vec<TrackInfo> {
         self.tracks.all_track_infos()
@@ -294,6 +527,7 @@ impl BuildingState {
         self.industry_buildings
             .insert(industry_building.id(), industry_building);
         self.recalculate_cargo_forwarding_links();
+        self.recalculate_power_grids();
     }
 
     pub fn append_military_building(&mut self, military_building: MilitaryBuildingInfo) {
@@ -309,57 +543,207 @@ impl BuildingState {
     }
 
     pub fn append_station(&mut self, station: StationInfo) {
-        for tile in station.covers_tiles() {
-            if self.tile_buildings[tile] != TileBuildingStatus::Empty {
-                warn!("Tried to build station at {tile:?} but it is already occupied",);
+        let tiles = station.covers_tiles().into_iter().collect::<Vec<_>>();
+        self.changing_tracks_at(&tiles, |building_state| {
+            for tile in station.covers_tiles() {
+                if building_state.tile_buildings[tile] != TileBuildingStatus::Empty {
+                    warn!("Tried to build station at {tile:?} but it is already occupied",);
+                }
+                building_state.tile_buildings[tile] =
+                    TileBuildingStatus::Station(station.id(), station.station_track_types_at(tile));
             }
-            self.tile_buildings[tile] =
-                TileBuildingStatus::Station(station.id(), station.station_track_types_at(tile));
-        }
+        });
         self.stations.insert(station.id(), station);
         self.recalculate_cargo_forwarding_links();
     }
 
+    pub fn append_waypoint(&mut self, waypoint: WaypointInfo) {
+        if self.waypoint_at(waypoint.tile()).is_some() {
+            warn!(
+                "Tried to add waypoint at {:?} but there already is one",
+                waypoint.tile()
+            );
+        }
+        self.waypoints.insert(waypoint.id(), waypoint);
+    }
+
+    pub fn append_power_line(&mut self, power_line: PowerLineInfo) {
+        if self.power_line_at(power_line.tile()).is_some() {
+            warn!(
+                "Tried to add power line at {:?} but there already is one",
+                power_line.tile()
+            );
+        }
+        self.power_line_tiles[power_line.tile()] = Some(power_line.id());
+        self.power_lines.insert(power_line.id(), power_line);
+        self.recalculate_power_grids();
+    }
+
+    pub fn append_bridge(&mut self, bridge: BridgeInfo) {
+        let tiles = bridge.tiles();
+        self.changing_tracks_at(&tiles, |building_state| {
+            for tile in &tiles {
+                if building_state.tile_buildings[*tile] != TileBuildingStatus::Empty {
+                    warn!("Tried to build bridge at {tile:?} but it is already occupied");
+                }
+                building_state.tile_buildings[*tile] = TileBuildingStatus::Bridge(bridge.id());
+            }
+            building_state.bridges.insert(bridge.id(), bridge);
+        });
+    }
+
     pub fn append_tracks(&mut self, additional: Vec<TrackInfo>) {
-        self.tracks.append_tracks(additional);
+        let tiles = additional
+            .iter()
+            .map(|track| track.tile)
+            .collect::<Vec<_>>();
+        self.changing_tracks_at(&tiles, |building_state| {
+            building_state.tracks.append_tracks(additional);
+        });
+    }
+
+    // Both the tracks and the stations provide the track types used for routing, so whenever they
+    // change, we compare the tiles before & after and let the route cache drop what is affected
+    fn changing_tracks_at<R>(
+        &mut self,
+        tiles: &[TileCoordsXZ],
+        change: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let before = tiles
+            .iter()
+            .map(|tile| self.usable_track_types_at(*tile))
+            .collect::<Vec<_>>();
+
+        let result = change(self);
+
+        let mut added = vec![];
+        let mut removed = vec![];
+        for (tile, before) in tiles.iter().zip(before) {
+            let after = self.usable_track_types_at(*tile);
+            for track_type in TrackType::all() {
+                let target = match (before.contains(track_type), after.contains(track_type)) {
+                    (false, true) => &mut added,
+                    (true, false) => &mut removed,
+                    _ => continue,
+                };
+                for pointing_in in track_type.connections() {
+                    target.push(TileTrack {
+                        tile: *tile,
+                        track_type,
+                        pointing_in,
+                    });
+                }
+            }
+        }
+
+        self.route_cache.invalidate(&added, &removed, self);
+        self.remove_orphaned_waypoints(tiles);
+        result
+    }
+
+    // Waypoints can only stay where their owner still has tracks
+    fn remove_orphaned_waypoints(&mut self, tiles: &[TileCoordsXZ]) {
+        let orphaned = tiles
+            .iter()
+            .filter_map(|tile| self.waypoint_at(*tile))
+            .filter(|waypoint| {
+                self.tracks_at(waypoint.tile()).owner_id() != Some(waypoint.owner_id())
+            })
+            .map(WaypointInfo::id)
+            .collect::<Vec<_>>();
+        for waypoint_id in orphaned {
+            self.remove_waypoint(waypoint_id);
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn find_route(
+        &self,
+        current_tile_track: TileTrack,
+        targets: &[TileTrack],
+        metrics: &impl Metrics,
+    ) -> Option<(Vec<TileTrack>, TrackLength)> {
+        self.route_cache
+            .find_route(current_tile_track, targets, self, metrics)
     }
 
     fn recalculate_cargo_forwarding_links(&mut self) {
-        self.closest_station_link.clear();
+        self.station_links.clear();
         for building in self.industry_buildings.values() {
-            if let Some((closest_station, distance)) = self.find_closest_station(building) {
-                const CARGO_FORWARDING_DISTANCE_THRESHOLD: i32 = 1;
-                if distance <= CARGO_FORWARDING_DISTANCE_THRESHOLD {
-                    self.closest_station_link
-                        .insert(building.id(), closest_station.id());
-                }
+            let station_ids = self.find_stations_serving(building);
+            if !station_ids.is_empty() {
+                self.station_links.insert(building.id(), station_ids);
             }
         }
     }
 
-    fn find_closest_station(&self, building: &IndustryBuildingInfo) -> Option<(&StationInfo, i32)> {
-        self.find_stations_by_owner(building.owner_id())
+    fn recalculate_power_grids(&mut self) {
+        let finished = self.power_lines.values().filter(|power_line| {
+            !self.is_under_construction(ConstructionTarget::PowerLine(power_line.id()))
+        });
+        self.power_grids = PowerGrids::calculate(finished, &self.industry_buildings);
+    }
+
+    // The stations whose catchment area covers the building, ordered by distance (and then ID, so
+    // that the order is deterministic)
+    fn find_stations_serving(&self, building: &IndustryBuildingInfo) -> Vec<StationId> {
+        let building_coverage = building.covers_tiles();
+        let mut stations = self
+            .find_stations_by_owner(building.owner_id())
             .into_iter()
+            .filter(|station| station.catchment_area().intersects(&building_coverage))
             .map(|station| {
-                (
-                    station,
-                    TileCoverage::manhattan_distance_between_closest_tiles(
-                        &building.covers_tiles(),
-                        &station.covers_tiles(),
-                    ),
-                )
+                let distance = TileCoverage::manhattan_distance_between_closest_tiles(
+                    &building_coverage,
+                    &station.covers_tiles(),
+                );
+                (distance, station.id())
             })
-            .min_by_key(|(_, distance)| *distance)
+            .collect::<Vec<_>>();
+        stations.sort();
+        stations
+            .into_iter()
+            .map(|(_, station_id)| station_id)
+            .collect()
     }
 
+    // The closest of the stations serving this industry building
     #[must_use]
     pub fn find_linked_station(
         &self,
         industry_building_id: IndustryBuildingId,
     ) -> Option<&StationInfo> {
-        self.closest_station_link
+        self.find_linked_stations(industry_building_id)
+            .into_iter()
+            .next()
+    }
+
+    #[must_use]
+    pub fn find_linked_stations(
+        &self,
+        industry_building_id: IndustryBuildingId,
+    ) -> Vec<&StationInfo> {
+        self.station_links
             .get(&industry_building_id)
-            .and_then(|station_id| self.stations.get(station_id))
+            .into_iter()
+            .flatten()
+            .filter_map(|station_id| self.stations.get(station_id))
+            .collect()
+    }
+
+    // The industry buildings within the station's catchment area
+    #[must_use]
+    pub fn find_linked_industry_buildings(
+        &self,
+        station_id: StationId,
+    ) -> Vec<&IndustryBuildingInfo> {
+        self.station_links
+            .iter()
+            .filter(|(_, station_ids)| station_ids.contains(&station_id))
+            .filter_map(|(industry_building_id, _)| {
+                self.industry_buildings.get(industry_building_id)
+            })
+            .collect()
     }
 
     #[must_use]
@@ -370,26 +754,65 @@ impl BuildingState {
         self.all_industry_buildings()
             .into_iter()
             .filter(move |building| {
-                building.owner_id() == player_id
-                    && !self.closest_station_link.contains_key(&building.id())
+                building.owner_id() == player_id && !self.station_links.contains_key(&building.id())
             })
     }
 
     pub fn can_build_station(&self, station: &StationInfo) -> Result<(), BuildError> {
         self.can_build_for_coverage(&station.covers_tiles(), |tile, obtained| {
-            let track_types = station.station_track_types_at(tile);
-            let owner_id = station.owner_id();
-            // If the station is providing exactly the same tracks as already exist there, we should allow building it.
-            // This makes it less likely that we fail to build a station due to tracks crowding the place where we want to build it.
-            obtained == MaybeTracksOnTile::Empty
-                || obtained
-                    == MaybeTracksOnTile::SingleOwner {
-                        owner_id,
-                        track_types,
-                    }
+            Self::valid_station_tracks(station, tile, obtained)
         })
     }
 
+    fn valid_station_tracks(
+        station: &StationInfo,
+        tile: TileCoordsXZ,
+        obtained: MaybeTracksOnTile,
+    ) -> bool {
+        let track_types = station.station_track_types_at(tile);
+        let owner_id = station.owner_id();
+        // If the station is providing exactly the same tracks as already exist there, we should allow building it.
+        // This makes it less likely that we fail to build a station due to tracks crowding the place where we want to build it.
+        obtained == MaybeTracksOnTile::Empty
+            || (obtained.owner_id() == Some(owner_id) && obtained.track_types() == track_types)
+    }
+
+    // The expanded station has to keep covering all the tiles of the existing one, and only the
+    // newly covered tiles are checked
+    pub fn can_expand_station(&self, expanded: &StationInfo) -> Result<(), BuildError> {
+        let existing = self
+            .find_station(expanded.id())
+            .ok_or(BuildError::UnknownError)?;
+        (existing.owner_id() == expanded.owner_id()).then_ok_unit(|| BuildError::InvalidOwner)?;
+
+        let existing_coverage = existing.covers_tiles();
+        let expanded_coverage = expanded.covers_tiles();
+        let valid = existing.station_type().orientation == expanded.station_type().orientation
+            && existing_coverage
+                .into_iter()
+                .all(|tile| expanded_coverage.contains(tile))
+            && expanded_coverage != existing_coverage;
+        valid.then_ok_unit(|| BuildError::InvalidOverlap)?;
+
+        let valid_overlaps = expanded_coverage
+            .into_iter()
+            .filter(|tile| !existing_coverage.contains(*tile))
+            .all(|tile| {
+                self.free_at(tile)
+                    && self.power_line_at(tile).is_none()
+                    && Self::valid_station_tracks(expanded, tile, self.tracks_at(tile))
+            });
+        valid_overlaps.then_ok_unit(|| BuildError::InvalidOverlap)
+    }
+
+    // Finds one of the player's stations that `addition` would expand, and the resulting station
+    #[must_use]
+    pub fn find_station_expansion(&self, addition: &StationInfo) -> Option<StationInfo> {
+        self.find_stations_by_owner(addition.owner_id())
+            .into_iter()
+            .find_map(|station| station.expanded_with(addition))
+    }
+
     pub fn can_build_industry_building(
         &self,
         industry_building_info: &IndustryBuildingInfo,
@@ -408,8 +831,108 @@ impl BuildingState {
         })
     }
 
+    pub fn can_build_waypoint(&self, waypoint: &WaypointInfo) -> Result<(), BuildError> {
+        // Waypoints can only be placed on the owner's own tracks, and only one per tile
+        let tile = waypoint.tile();
+        (self.tracks_at(tile).owner_id() == Some(waypoint.owner_id()))
+            .then_ok_unit(|| BuildError::InvalidOverlap)?;
+        self.waypoint_at(tile)
+            .is_none()
+            .then_ok_unit(|| BuildError::InvalidOverlap)
+    }
+
+    pub(crate) fn build_waypoint(
+        &mut self,
+        waypoint: &WaypointInfo,
+        costs: &BuildCosts,
+    ) -> Result<(), BuildError> {
+        self.can_build_waypoint(waypoint)?;
+        self.pay_costs(costs);
+        self.append_waypoint(waypoint.clone());
+        self.set_construction_site(
+            ConstructionTarget::Waypoint(waypoint.id()),
+            Some(ConstructionSite::new()),
+        );
+        Ok(())
+    }
+
+    pub fn can_build_power_line(&self, power_line: &PowerLineInfo) -> Result<(), BuildError> {
+        // Power lines can run over empty land or the owner's own tracks, and only one per tile
+        let tile = power_line.tile();
+        let tracks_owner = self.tracks_at(tile).owner_id();
+        (self.free_at(tile)
+            && tracks_owner.is_none_or(|owner_id| owner_id == power_line.owner_id()))
+        .then_ok_unit(|| BuildError::InvalidOverlap)?;
+        self.power_line_at(tile)
+            .is_none()
+            .then_ok_unit(|| BuildError::InvalidOverlap)
+    }
+
+    pub(crate) fn build_power_line(
+        &mut self,
+        power_line: &PowerLineInfo,
+        costs: &BuildCosts,
+    ) -> Result<(), BuildError> {
+        self.can_build_power_line(power_line)?;
+        self.pay_costs(costs);
+        self.append_power_line(power_line.clone());
+        self.set_construction_site(
+            ConstructionTarget::PowerLine(power_line.id()),
+            Some(ConstructionSite::new()),
+        );
+        Ok(())
+    }
+
+    pub fn can_build_bridge(&self, bridge: &BridgeInfo) -> Result<(), BuildError> {
+        self.can_build_for_coverage(&bridge.covers_tiles(), |_tile, obtained| {
+            obtained == MaybeTracksOnTile::Empty
+        })
+    }
+
+    pub(crate) fn build_bridge(
+        &mut self,
+        bridge: &BridgeInfo,
+        costs: &BuildCosts,
+    ) -> Result<(), BuildError> {
+        self.can_build_bridge(bridge)?;
+        self.pay_costs(costs);
+        self.append_bridge(bridge.clone());
+        self.set_construction_site(
+            ConstructionTarget::Bridge(bridge.id()),
+            Some(ConstructionSite::new()),
+        );
+        Ok(())
+    }
+
     pub(crate) fn build_tracks(&mut self, tracks: Vec<TrackInfo>, costs: &BuildCosts) {
+        let track_ids = tracks.iter().map(TrackInfo::id).collect::<Vec<_>>();
         self.append_tracks(tracks);
+        for track_id in track_ids {
+            self.set_construction_site(
+                ConstructionTarget::Track(track_id),
+                Some(ConstructionSite::new()),
+            );
+        }
+        self.pay_costs(costs);
+    }
+
+    pub(crate) fn upgrade_industry_building(
+        &mut self,
+        industry_building_id: IndustryBuildingId,
+        costs: &BuildCosts,
+    ) -> Result<IndustryBuildingInfo, BuildError> {
+        let building = self
+            .industry_buildings
+            .get_mut(&industry_building_id)
+            .ok_or(BuildError::UnknownError)?;
+        building.upgrade();
+        let upgraded = building.clone();
+        self.pay_costs(costs);
+        Ok(upgraded)
+    }
+
+    pub(crate) fn upgrade_tracks(&mut self, tracks: &[TrackInfo], costs: &BuildCosts) {
+        self.set_track_grades(tracks);
         self.pay_costs(costs);
     }
 
@@ -427,10 +950,24 @@ impl BuildingState {
             false
         };
 
+        // Tracks are never built on bridges, but the bridge's own track type counts as existing
+        let overlapping_bridge = self.bridge_at(tile);
+        let has_same_track_from_bridge = overlapping_bridge.is_some_and(|bridge| {
+            bridge.owner_id() == owner_id && bridge.track_type() == track_type
+        });
+
         let overlapping_industry = self.industry_building_at(tile);
         let invalid_station_overlap = !has_same_track_from_station && overlapping_station.is_some();
+        let invalid_bridge_overlap = !has_same_track_from_bridge && overlapping_bridge.is_some();
         let invalid_industry_overlap = overlapping_industry.is_some();
-        let invalid_overlaps = invalid_industry_overlap || invalid_station_overlap;
+        // Power lines only run above their owner's tracks
+        let invalid_power_line_overlap = self
+            .power_line_at(tile)
+            .is_some_and(|power_line| power_line.owner_id() != owner_id);
+        let invalid_overlaps = invalid_industry_overlap
+            || invalid_station_overlap
+            || invalid_bridge_overlap
+            || invalid_power_line_overlap;
 
         let overlapping_tracks = self.tracks_at(tile);
 
@@ -442,7 +979,7 @@ impl BuildingState {
         let has_same_track = {
             let has_same_track_from_tracks = overlapping_tracks.track_types().contains(track_type);
 
-            has_same_track_from_tracks || has_same_track_from_station
+            has_same_track_from_tracks || has_same_track_from_station || has_same_track_from_bridge
         };
 
         if overlapping_other_players_tracks || invalid_overlaps {
@@ -483,53 +1020,260 @@ impl BuildingState {
         providing_industry_type: IndustryType,
         cost: CargoMap,
     ) -> Result<BuildCosts, BuildError> {
+        let mut candidates = vec![];
+        for (distance, building) in
+            self.suppliers_in_range(player_id, something, providing_industry_type)?
+        {
+            trace!(
+                "Supply building at distance {distance} has cargo {:?} and we need cost {cost:?}",
+                building.cargo()
+            );
+            if building.cargo().is_superset_of(&cost) {
+                candidates.push((distance, building.id()));
+            }
+        }
+
+        // The player's preferred supplier pays if it can, otherwise the closest one does
+        let paying = candidates
+            .iter()
+            .find(|(_, industry_building_id)| {
+                self.preferred_suppliers.contains(industry_building_id)
+            })
+            .or_else(|| candidates.iter().min())
+            .map(|(_, industry_building_id)| *industry_building_id)
+            .ok_or(BuildError::NotEnoughResources)?;
+        let money = cost.value() * MONEY_PER_RESOURCE_VALUE;
+        Ok(BuildCosts::single(paying, cost).with_money(player_id, money))
+    }
+
+    // Transports are paid for by an industry of the player linked to the station they are purchased
+    // at, which is unlike the construction costs not limited by a supply range
+    pub(crate) fn can_pay_from_linked_industry(
+        &self,
+        player_id: PlayerId,
+        station_id: StationId,
+        providing_industry_type: IndustryType,
+        cost: CargoMap,
+    ) -> Result<BuildCosts, BuildError> {
+        let paying = self
+            .find_linked_industry_buildings(station_id)
+            .into_iter()
+            .filter(|building| {
+                building.owner_id() == player_id
+                    && building.industry_type() == providing_industry_type
+                    && !building.is_under_construction()
+                    && building.cargo().is_superset_of(&cost)
+            })
+            .map(IndustryBuildingInfo::id)
+            .min()
+            .ok_or(BuildError::NotEnoughResources)?;
+        let money = cost.value() * MONEY_PER_RESOURCE_VALUE;
+        Ok(BuildCosts::single(paying, cost).with_money(player_id, money))
+    }
+
+    // The player's completed buildings of this type that have `something` in their supply range,
+    // with their distance to it
+    fn suppliers_in_range<T: WithTileCoverage>(
+        &self,
+        player_id: PlayerId,
+        something: &T,
+        providing_industry_type: IndustryType,
+    ) -> Result<Vec<(TileDistance, &IndustryBuildingInfo)>, BuildError> {
         let coverage = something.covers_tiles();
         let supply_range = providing_industry_type
             .supply_range_in_tiles()
             .ok_or(BuildError::UnknownError)?;
 
+        let mut results = vec![];
         for building in
             self.find_industry_buildings_by_owner_and_type(player_id, providing_industry_type)
         {
+            if building.is_under_construction() {
+                continue;
+            }
             let distance = TileCoverage::manhattan_distance_between_closest_tiles(
                 &coverage,
                 &building.covers_tiles(),
             );
             if distance <= supply_range {
-                trace!(
-                    "Supply building at distance {distance} with supply range {supply_range} has cargo {:?} and we need cost {cost:?}",
-                    building.cargo()
-                );
-                if building.cargo().is_superset_of(&cost) {
-                    // TODO: We currently return the first one that satisfies the conditions - we could instead return the closest one, or the one with most resources.
-                    return Ok(BuildCosts::single(building.id(), cost));
-                }
+                results.push((distance, building));
             }
         }
+        Ok(results)
+    }
 
-        Err(BuildError::NotEnoughResources)
+    // Unlike construction, artillery is always supplied by the same military base - the preferred
+    // one if it is in range, otherwise the closest one - even while it is out of ammunition
+    #[must_use]
+    pub fn ammunition_supplier<T: WithTileCoverage>(
+        &self,
+        player_id: PlayerId,
+        something: &T,
+    ) -> Option<IndustryBuildingId> {
+        let candidates = self
+            .suppliers_in_range(player_id, something, IndustryType::MilitaryBase)
+            .ok()?
+            .into_iter()
+            .map(|(distance, building)| (distance, building.id()))
+            .collect::<Vec<_>>();
+        candidates
+            .iter()
+            .find(|(_, industry_building_id)| {
+                self.preferred_suppliers.contains(industry_building_id)
+            })
+            .or_else(|| candidates.iter().min())
+            .map(|(_, industry_building_id)| *industry_building_id)
     }
 
-    pub(crate) fn can_pay_cost<T: WithCostToBuild + WithTileCoverage>(
+    #[must_use]
+    pub fn ammunition_status<T: WithTileCoverage>(
         &self,
         player_id: PlayerId,
         something: &T,
-    ) -> Result<BuildCosts, BuildError> {
-        let (providing_industry_type, cost) = something.cost_to_build();
+        shell_type: ShellType,
+    ) -> AmmunitionStatus {
+        match self.ammunition_supplier(player_id, something) {
+            None => AmmunitionStatus::NoSupplier,
+            Some(industry_building_id) => {
+                let has_ammunition = self
+                    .find_industry_building(industry_building_id)
+                    .is_some_and(|building| {
+                        building.cargo().is_superset_of(&shell_type.cost_per_shot())
+                    });
+                if has_ammunition {
+                    AmmunitionStatus::Supplied(industry_building_id)
+                } else {
+                    AmmunitionStatus::OutOfAmmunition(industry_building_id)
+                }
+            },
+        }
+    }
 
-        self.can_pay_known_cost(player_id, something, providing_industry_type, cost)
+    pub(crate) fn can_pay_for_shot<T: WithTileCoverage>(
+        &self,
+        player_id: PlayerId,
+        something: &T,
+        shell_type: ShellType,
+    ) -> Result<BuildCosts, BuildError> {
+        match self.ammunition_status(player_id, something, shell_type) {
+            AmmunitionStatus::Supplied(industry_building_id) => {
+                let cost = shell_type.cost_per_shot();
+                let money = cost.value() * MONEY_PER_RESOURCE_VALUE;
+                Ok(BuildCosts::single(industry_building_id, cost).with_money(player_id, money))
+            },
+            AmmunitionStatus::NoSupplier | AmmunitionStatus::OutOfAmmunition(_) => {
+                Err(BuildError::NotEnoughResources)
+            },
+        }
     }
 
-    fn can_build_for_coverage<F>(
+    pub fn pending_shots(
         &self,
-        tile_coverage: &TileCoverage,
+        industry_building_id: IndustryBuildingId,
+    ) -> impl Iterator<Item = ProjectileSource> + '_ {
+        self.pending_shots
+            .get(&industry_building_id)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    // The artillery waits at one base at a time, so it moves to the back of the new queue if its
+    // supplier changed
+    pub(crate) fn queue_shot(
+        &mut self,
+        source: ProjectileSource,
+        industry_building_id: IndustryBuildingId,
+    ) {
+        if !self
+            .pending_shots(industry_building_id)
+            .any(|queued| queued == source)
+        {
+            self.dequeue_shot(source);
+            self.pending_shots
+                .entry(industry_building_id)
+                .or_default()
+                .push_back(source);
+        }
+    }
+
+    pub(crate) fn dequeue_shot(&mut self, source: ProjectileSource) {
+        for queue in self.pending_shots.values_mut() {
+            queue.retain(|queued| *queued != source);
+        }
+        self.pending_shots.retain(|_, queue| !queue.is_empty());
+    }
+
+    #[must_use]
+    pub fn is_preferred_supplier(&self, industry_building_id: IndustryBuildingId) -> bool {
+        self.preferred_suppliers.contains(&industry_building_id)
+    }
+
+    // Replaces the previous preferred supplier of the same owner and type. Also used by the client
+    // to apply the choice made on the server.
+    pub fn set_preferred_supplier(
+        &mut self,
+        industry_building_id: IndustryBuildingId,
+    ) -> Result<(), BuildError> {
+        let building = self
+            .find_industry_building(industry_building_id)
+            .ok_or(BuildError::UnknownError)?;
+        building
+            .industry_type()
+            .supply_range_in_tiles()
+            .ok_or(BuildError::UnknownError)?;
+        let replaced = self
+            .find_industry_buildings_by_owner_and_type(
+                building.owner_id(),
+                building.industry_type(),
+            )
+            .into_iter()
+            .map(IndustryBuildingInfo::id)
+            .collect::<Vec<_>>();
+        for id in replaced {
+            self.preferred_suppliers.remove(&id);
+        }
+        self.preferred_suppliers.insert(industry_building_id);
+        Ok(())
+    }
+
+    // The outlines of the areas that the player's buildings of this type supply
+    #[must_use]
+    pub fn supply_range_outlines(
+        &self,
+        player_id: PlayerId,
+        providing_industry_type: IndustryType,
+    ) -> Vec<TileCoordsXZ> {
+        let Some(supply_range) = providing_industry_type.supply_range_in_tiles() else {
+            return vec![];
+        };
+        self.find_industry_buildings_by_owner_and_type(player_id, providing_industry_type)
+            .into_iter()
+            .flat_map(|building| building.covers_tiles().manhattan_ring(supply_range))
+            .collect()
+    }
+
+    pub(crate) fn can_pay_cost<T: WithCostToBuild + WithTileCoverage>(
+        &self,
+        player_id: PlayerId,
+        something: &T,
+    ) -> Result<BuildCosts, BuildError> {
+        let (providing_industry_type, cost) = something.cost_to_build();
+
+        self.can_pay_known_cost(player_id, something, providing_industry_type, cost)
+    }
+
+    fn can_build_for_coverage<F>(
+        &self,
+        tile_coverage: &TileCoverage,
         track_validator: F,
     ) -> Result<(), BuildError>
     where
         F: Fn(TileCoordsXZ, MaybeTracksOnTile) -> bool,
     {
         let valid_overlaps = tile_coverage.into_iter().all(|tile| {
-            self.tile_buildings[tile] == TileBuildingStatus::Empty
+            self.free_at(tile)
+                && self.power_line_at(tile).is_none()
                 && track_validator(tile, self.tracks_at(tile))
         });
 
@@ -547,6 +1291,79 @@ impl BuildingState {
         Ok(())
     }
 
+    // Projectiles set back the construction of the enemy buildings they land on
+    pub(crate) fn hit_construction_site_at(&mut self, tile: TileCoordsXZ, attacker_id: PlayerId) {
+        let industry_building_id = self
+            .industry_building_at(tile)
+            .filter(|building| building.owner_id() != attacker_id)
+            .map(IndustryBuildingInfo::id);
+        if let Some(industry_building_id) = industry_building_id {
+            if let Some(building) = self.find_industry_building_mut(industry_building_id) {
+                if building.hit_construction() {
+                    info!("Construction site {industry_building_id:?} hit at {tile:?}");
+                }
+            }
+        }
+
+        for target in self.construction_targets_at(tile, attacker_id) {
+            if let Some(construction_site) = self.construction_sites.get_mut(&target) {
+                construction_site.hit();
+                info!("Construction site {target:?} hit at {tile:?}");
+            }
+        }
+    }
+
+    // Everything other than industries on the tile that is not owned by `attacker_id`
+    fn construction_targets_at(
+        &self,
+        tile: TileCoordsXZ,
+        attacker_id: PlayerId,
+    ) -> Vec<ConstructionTarget> {
+        let mut results = vec![];
+        let mut add = |owner_id: PlayerId, target: ConstructionTarget| {
+            if owner_id != attacker_id {
+                results.push(target);
+            }
+        };
+        if let Some(station) = self.station_at(tile) {
+            add(
+                station.owner_id(),
+                ConstructionTarget::Station(station.id()),
+            );
+        }
+        if let Some(military_building) = self.military_building_at(tile) {
+            add(
+                military_building.owner_id(),
+                ConstructionTarget::MilitaryBuilding(military_building.id()),
+            );
+        }
+        if let Some(bridge) = self.bridge_at(tile) {
+            add(bridge.owner_id(), ConstructionTarget::Bridge(bridge.id()));
+        }
+        if let Some(waypoint) = self.waypoint_at(tile) {
+            add(
+                waypoint.owner_id(),
+                ConstructionTarget::Waypoint(waypoint.id()),
+            );
+        }
+        if let Some(power_line) = self.power_line_at(tile) {
+            add(
+                power_line.owner_id(),
+                ConstructionTarget::PowerLine(power_line.id()),
+            );
+        }
+        let tracks = self.tracks_at(tile);
+        if let Some(owner_id) = tracks.owner_id() {
+            for track_type in tracks.track_types() {
+                add(
+                    owner_id,
+                    ConstructionTarget::Track(TrackId::new(tile, track_type)),
+                );
+            }
+        }
+        results
+    }
+
     pub(crate) fn build_military_building(
         &mut self,
         military_building_info: &MilitaryBuildingInfo,
@@ -555,6 +1372,98 @@ impl BuildingState {
         self.can_build_military_building(military_building_info)?;
         self.pay_costs(costs);
         self.append_military_building(military_building_info.clone());
+        self.set_construction_site(
+            ConstructionTarget::MilitaryBuilding(military_building_info.id()),
+            Some(ConstructionSite::new()),
+        );
+        Ok(())
+    }
+
+    // Gives the cargo to the first industry building of this type linked to the station, failing if
+    // there is no such building or the cargo does not fit into its storage
+    pub(crate) fn refund_to_linked_industry(
+        &mut self,
+        station_id: StationId,
+        industry_type: IndustryType,
+        cargo: &CargoMap,
+    ) -> Result<(), ()> {
+        let industry_building_id = self
+            .find_linked_industry_buildings(station_id)
+            .into_iter()
+            .find(|building| building.industry_type() == industry_type)
+            .map(IndustryBuildingInfo::id)
+            .ok_or(())?;
+        let industry_building = self
+            .industry_buildings
+            .get_mut(&industry_building_id)
+            .ok_or(())?;
+        industry_building
+            .remaining_storage_capacity()
+            .is_superset_of(cargo)
+            .then_ok_unit(|| ())?;
+        industry_building.add_cargo(cargo);
+        Ok(())
+    }
+
+    // Swaps the offered cargo from the offering station with the requested cargo from
+    // `to_station_id`, if both stations still have the cargo and the room for what they receive
+    pub(crate) fn exchange_traded_cargo(
+        &mut self,
+        trade_offer: &TradeOffer,
+        to_station_id: StationId,
+    ) -> Result<(), TradeError> {
+        let from_station = self
+            .find_station(trade_offer.from_station_id())
+            .ok_or(TradeError::UnknownStation)?;
+        let to_station = self
+            .find_station(to_station_id)
+            .ok_or(TradeError::UnknownStation)?;
+        if from_station.owner_id() != trade_offer.from_player_id()
+            || to_station.owner_id() != trade_offer.to_player_id()
+        {
+            return Err(TradeError::InvalidOwner);
+        }
+        if !from_station.cargo().is_superset_of(trade_offer.offered())
+            || !to_station.cargo().is_superset_of(trade_offer.requested())
+        {
+            return Err(TradeError::NotEnoughCargo);
+        }
+
+        // What leaves the station frees up room for what arrives
+        let mut from_room = from_station.remaining_storage_capacity();
+        from_room += trade_offer.offered();
+        let mut to_room = to_station.remaining_storage_capacity();
+        to_room += trade_offer.requested();
+        if !from_room.is_superset_of(trade_offer.requested())
+            || !to_room.is_superset_of(trade_offer.offered())
+        {
+            return Err(TradeError::NotEnoughStorage);
+        }
+
+        let from_age = from_station.dynamic_info().cargo_age().clone();
+        let to_age = to_station.dynamic_info().cargo_age().clone();
+        for (station_id, outgoing, incoming, incoming_age) in [
+            (
+                trade_offer.from_station_id(),
+                trade_offer.offered(),
+                trade_offer.requested(),
+                &to_age,
+            ),
+            (
+                to_station_id,
+                trade_offer.requested(),
+                trade_offer.offered(),
+                &from_age,
+            ),
+        ] {
+            if let Some(station) = self.find_station_mut(station_id) {
+                station.remove_cargo(outgoing);
+                station
+                    .dynamic_info_mut()
+                    .add_aged_cargo(incoming, incoming_age);
+                station.update_utilisation();
+            }
+        }
         Ok(())
     }
 
@@ -576,6 +1485,39 @@ impl BuildingState {
         self.can_build_station(station_info)?;
         self.pay_costs(costs);
         self.append_station(station_info.clone());
+        self.set_construction_site(
+            ConstructionTarget::Station(station_info.id()),
+            Some(ConstructionSite::new()),
+        );
+        Ok(())
+    }
+
+    pub(crate) fn expand_station(
+        &mut self,
+        expanded: &StationInfo,
+        costs: &BuildCosts,
+    ) -> Result<(), BuildError> {
+        self.can_expand_station(expanded)?;
+        self.pay_costs(costs);
+
+        let existing = self
+            .find_station(expanded.id())
+            .ok_or(BuildError::UnknownError)?;
+        // The cargo stays with the station, whatever the requester thought it was
+        let mut station = expanded.clone();
+        station.update_dynamic_info(existing.dynamic_info());
+
+        let tiles = station.covers_tiles().into_iter().collect::<Vec<_>>();
+        self.changing_tracks_at(&tiles, |building_state| {
+            for tile in &tiles {
+                building_state.tile_buildings[*tile] = TileBuildingStatus::Station(
+                    station.id(),
+                    station.station_track_types_at(*tile),
+                );
+            }
+        });
+        self.stations.insert(station.id(), station);
+        self.recalculate_cargo_forwarding_links();
         Ok(())
     }
 
@@ -621,6 +1563,14 @@ impl BuildingState {
             })
     }
 
+    #[must_use]
+    pub fn find_military_building(
+        &self,
+        military_building_id: MilitaryBuildingId,
+    ) -> Option<&MilitaryBuildingInfo> {
+        self.military_buildings.get(&military_building_id)
+    }
+
     #[must_use]
     pub(crate) fn find_military_building_mut(
         &mut self,
@@ -656,6 +1606,11 @@ impl BuildingState {
     ) -> Vec<InternalGameCommand> {
         let mut results = vec![];
         for military_building in self.military_buildings.values() {
+            if self
+                .is_under_construction(ConstructionTarget::MilitaryBuilding(military_building.id()))
+            {
+                continue;
+            }
             let commands = military_building.generate_commands(
                 previous_game_time,
                 diff,
@@ -672,40 +1627,96 @@ impl BuildingState {
         previous_game_time: GameTime,
         diff: GameTimeDiff,
         new_game_time: GameTime,
+        statistics: &mut StatisticsState,
     ) {
+        self.advance_construction_sites(diff);
+        self.power_grids.update_loads(&self.industry_buildings);
         for industry_building in &mut self.industry_buildings.values_mut() {
-            industry_building.advance_industry_building(diff);
+            let power_coef = self.power_grids.production_coef(industry_building.id());
+            let transformed = industry_building.advance_industry_building(diff, power_coef);
+            statistics.record_transformed(industry_building.owner_id(), &transformed);
+        }
+        for (industry_building_id, station_ids) in self.station_links.clone() {
+            // Stations under construction do not exchange cargo with the industries yet
+            let station_ids = station_ids
+                .into_iter()
+                .filter(|station_id| {
+                    !self.is_under_construction(ConstructionTarget::Station(*station_id))
+                })
+                .collect::<Vec<_>>();
+            // The output is split evenly between the stations, each taking its share of what the
+            // previous ones left
+            let stations = station_ids.len();
+            for (index, station_id) in station_ids.into_iter().enumerate() {
+                #[expect(clippy::cast_precision_loss)]
+                let output_share = 1.0 / (stations - index) as f32;
+                self.exchange_cargo(industry_building_id, station_id, output_share);
+            }
         }
-        for (industry_building_id, station_id) in self.closest_station_link.clone() {
-            self.exchange_cargo(industry_building_id, station_id);
+        for station in self.stations.values_mut() {
+            station.dynamic_info_mut().age_cargo(diff);
+            station.update_utilisation();
         }
         for military_building in &mut self.military_buildings.values_mut() {
             military_building.advance_time_diff(previous_game_time, diff, new_game_time);
         }
     }
 
+    // Only industries have materials delivered to their sites, so everything else is built at the
+    // base speed
+    fn advance_construction_sites(&mut self, diff: GameTimeDiff) {
+        let seconds = diff.to_seconds();
+        let mut finished = vec![];
+        for (target, construction_site) in &mut self.construction_sites {
+            let _ = construction_site.advance(seconds, &CargoMap::new(), &CargoMap::new());
+            if construction_site.is_finished() {
+                finished.push(*target);
+            }
+        }
+        for target in finished {
+            self.set_construction_site(target, None);
+        }
+    }
+
     #[expect(clippy::unwrap_used)]
-    fn exchange_cargo(&mut self, industry_building_id: IndustryBuildingId, station_id: StationId) {
+    fn exchange_cargo(
+        &mut self,
+        industry_building_id: IndustryBuildingId,
+        station_id: StationId,
+        output_share: f32,
+    ) {
         let industry_building = self.find_industry_building(industry_building_id).unwrap();
         let industry_building_inputs = industry_building.industry_transform_inputs();
-        let cargo_from_building_to_station = industry_building.industry_building_shippable_cargo();
+        let industry_building_space = industry_building.remaining_storage_capacity();
 
         let station = self.find_station(station_id).unwrap();
-        let cargo_from_station_to_building =
-            station
-                .station_shippable_cargo()
-                .filter(|(resource_type, _cargo_amount)| {
-                    industry_building_inputs.contains(&resource_type)
-                });
+        // Whatever does not fit stays where it is, so full stations make the industries throttle
+        let cargo_from_building_to_station =
+            (industry_building.industry_building_shippable_cargo() * output_share)
+                .cap_at(&station.remaining_storage_capacity());
+        let cargo_from_station_to_building = station
+            .station_shippable_cargo()
+            .filter(|(resource_type, _cargo_amount)| {
+                industry_building_inputs.contains(&resource_type)
+            })
+            .cap_at(&industry_building_space);
+
+        // The cargo keeps its age, so perishable cargo goes on spoiling wherever it is
+        let building_cargo_age = industry_building.dynamic_info().cargo_age().clone();
+        let station_cargo_age = station.dynamic_info().cargo_age().clone();
 
         let building_mut = self
             .find_industry_building_mut(industry_building_id)
             .unwrap();
         building_mut.remove_cargo(&cargo_from_building_to_station);
-        building_mut.add_cargo(&cargo_from_station_to_building);
+        building_mut
+            .dynamic_info_mut()
+            .add_aged_cargo(&cargo_from_station_to_building, &station_cargo_age);
 
         let station_mut = self.find_station_mut(station_id).unwrap();
-        station_mut.add_cargo(&cargo_from_building_to_station);
+        station_mut
+            .dynamic_info_mut()
+            .add_aged_cargo(&cargo_from_building_to_station, &building_cargo_age);
         station_mut.remove_cargo(&cargo_from_station_to_building);
     }
 
@@ -714,7 +1725,23 @@ impl BuildingState {
         industry_building_dynamic_infos: &HashMap<IndustryBuildingId, BuildingDynamicInfo>,
         station_dynamic_infos: &HashMap<StationId, BuildingDynamicInfo>,
         military_building_dynamic_infos: &HashMap<MilitaryBuildingId, MilitaryBuildingDynamicInfo>,
+        construction_sites: &HashMap<ConstructionTarget, ConstructionSite>,
     ) {
+        let started_or_finished = self
+            .construction_sites
+            .keys()
+            .chain(construction_sites.keys())
+            .filter(|target| {
+                self.construction_sites.contains_key(target)
+                    != construction_sites.contains_key(target)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        for target in started_or_finished {
+            self.set_construction_site(target, construction_sites.get(&target).copied());
+        }
+        self.construction_sites.clone_from(construction_sites);
+
         for (industry_building_id, building_dynamic_info) in industry_building_dynamic_infos {
             self.update_industry_building_dynamic_info(
                 *industry_building_id,
@@ -749,6 +1776,19 @@ impl BuildingState {
         }
     }
 
+    // Used by the client to apply upgrades done on the server
+    pub fn replace_industry_building(&mut self, industry_building: IndustryBuildingInfo) {
+        if let Some(building) = self.find_industry_building_mut(industry_building.id()) {
+            *building = industry_building;
+            self.recalculate_power_grids();
+        } else {
+            warn!(
+                "Could not find industry building with id {:?}",
+                industry_building.id()
+            );
+        }
+    }
+
     fn update_industry_building_dynamic_info(
         &mut self,
         industry_building_id: IndustryBuildingId,
@@ -777,6 +1817,8 @@ impl BuildingState {
     }
 
     pub fn remove_industry_building(&mut self, industry_building_id: IndustryBuildingId) {
+        self.preferred_suppliers.remove(&industry_building_id);
+        self.pending_shots.remove(&industry_building_id);
         if let Some(removed) = self.industry_buildings.remove(&industry_building_id) {
             for tile in removed.covers_tiles() {
                 match &self.tile_buildings[tile] {
@@ -798,6 +1840,7 @@ impl BuildingState {
             );
         }
         self.recalculate_cargo_forwarding_links();
+        self.recalculate_power_grids();
     }
 
     pub fn remove_military_building(&mut self, military_building_id: MilitaryBuildingId) {
@@ -821,27 +1864,34 @@ impl BuildingState {
                 "Tried to remove military building {military_building_id:?} but it was not found",
             );
         }
+        self.construction_sites
+            .remove(&ConstructionTarget::MilitaryBuilding(military_building_id));
     }
 
     pub fn remove_station(&mut self, station_id: StationId) {
         if let Some(removed) = self.stations.remove(&station_id) {
-            for tile in removed.covers_tiles() {
-                match &self.tile_buildings[tile] {
-                    TileBuildingStatus::Station(found_id, _) if *found_id == station_id => {
-                        self.tile_buildings[tile] = TileBuildingStatus::Empty;
-                    },
-                    found => {
-                        warn!(
-                            "Tried to remove station {station_id:?} at {tile:?} but found {found:?}",
-                        );
-                    },
+            let tiles = removed.covers_tiles().into_iter().collect::<Vec<_>>();
+            self.changing_tracks_at(&tiles, |building_state| {
+                for tile in removed.covers_tiles() {
+                    match &building_state.tile_buildings[tile] {
+                        TileBuildingStatus::Station(found_id, _) if *found_id == station_id => {
+                            building_state.tile_buildings[tile] = TileBuildingStatus::Empty;
+                        },
+                        found => {
+                            warn!(
+                                "Tried to remove station {station_id:?} at {tile:?} but found {found:?}",
+                            );
+                        },
+                    }
                 }
-            }
+            });
 
             self.recalculate_cargo_forwarding_links();
         } else {
             warn!("Tried to remove station {station_id:?} but it was not found",);
         }
+        self.construction_sites
+            .remove(&ConstructionTarget::Station(station_id));
     }
 
     pub fn attempt_to_remove_tracks(
@@ -849,8 +1899,107 @@ impl BuildingState {
         requesting_player_id: PlayerId,
         track_ids: &[TrackId],
     ) -> Result<(), ()> {
-        self.tracks
-            .attempt_to_remove_tracks(requesting_player_id, track_ids)
+        let tiles = track_ids
+            .iter()
+            .map(|track_id| track_id.tile)
+            .collect::<Vec<_>>();
+        self.changing_tracks_at(&tiles, |building_state| {
+            building_state
+                .tracks
+                .attempt_to_remove_tracks(requesting_player_id, track_ids)?;
+            for track_id in track_ids {
+                building_state
+                    .construction_sites
+                    .remove(&ConstructionTarget::Track(*track_id));
+            }
+            Ok(())
+        })
+    }
+
+    pub fn remove_bridge(&mut self, bridge_id: BridgeId) {
+        if let Some(removed) = self.bridges.get(&bridge_id).cloned() {
+            let tiles = removed.tiles();
+            self.changing_tracks_at(&tiles, |building_state| {
+                for tile in &tiles {
+                    match &building_state.tile_buildings[*tile] {
+                        TileBuildingStatus::Bridge(found_id) if *found_id == bridge_id => {
+                            building_state.tile_buildings[*tile] = TileBuildingStatus::Empty;
+                        },
+                        found => {
+                            warn!(
+                                "Tried to remove bridge {bridge_id:?} at {tile:?} but found {found:?}",
+                            );
+                        },
+                    }
+                }
+                building_state.bridges.remove(&bridge_id);
+                building_state
+                    .construction_sites
+                    .remove(&ConstructionTarget::Bridge(bridge_id));
+            });
+        } else {
+            warn!("Tried to remove bridge {bridge_id:?} but it was not found");
+        }
+    }
+
+    pub fn attempt_to_remove_bridge(
+        &mut self,
+        requesting_player_id: PlayerId,
+        bridge_id: BridgeId,
+    ) -> Result<(), ()> {
+        let bridge = self.find_bridge(bridge_id).ok_or(())?;
+        if bridge.owner_id() == requesting_player_id {
+            self.remove_bridge(bridge_id);
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    pub fn remove_waypoint(&mut self, waypoint_id: WaypointId) {
+        if self.waypoints.remove(&waypoint_id).is_none() {
+            warn!("Tried to remove waypoint {waypoint_id:?} but it was not found");
+        }
+        self.construction_sites
+            .remove(&ConstructionTarget::Waypoint(waypoint_id));
+    }
+
+    pub fn attempt_to_remove_waypoint(
+        &mut self,
+        requesting_player_id: PlayerId,
+        waypoint_id: WaypointId,
+    ) -> Result<(), ()> {
+        let waypoint = self.find_waypoint(waypoint_id).ok_or(())?;
+        if waypoint.owner_id() == requesting_player_id {
+            self.remove_waypoint(waypoint_id);
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    pub fn remove_power_line(&mut self, power_line_id: PowerLineId) {
+        match self.power_lines.remove(&power_line_id) {
+            Some(power_line) => self.power_line_tiles[power_line.tile()] = None,
+            None => warn!("Tried to remove power line {power_line_id:?} but it was not found"),
+        }
+        self.construction_sites
+            .remove(&ConstructionTarget::PowerLine(power_line_id));
+        self.recalculate_power_grids();
+    }
+
+    pub fn attempt_to_remove_power_line(
+        &mut self,
+        requesting_player_id: PlayerId,
+        power_line_id: PowerLineId,
+    ) -> Result<(), ()> {
+        let power_line = self.find_power_line(power_line_id).ok_or(())?;
+        if power_line.owner_id() == requesting_player_id {
+            self.remove_power_line(power_line_id);
+            Ok(())
+        } else {
+            Err(())
+        }
     }
 
     pub fn attempt_to_remove_industry_building(
@@ -905,14 +2054,58 @@ impl BuildingState {
     }
 
     pub fn remove_track(&mut self, track_id: TrackId) {
-        self.tracks.remove_track(track_id);
+        self.changing_tracks_at(&[track_id.tile], |building_state| {
+            building_state.tracks.remove_track(track_id);
+            building_state
+                .construction_sites
+                .remove(&ConstructionTarget::Track(track_id));
+        });
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TradeOfferId;
     use crate::building::station_type::StationType;
+    use crate::cargo_amount::CargoAmount;
+
+    #[test]
+    fn test_waypoints_are_placed_on_own_tracks_and_removed_with_them() {
+        let mut building_state = BuildingState::new(4, 1);
+        let owner_id = PlayerId::random();
+        let tile = TileCoordsXZ::new(1, 0);
+        let waypoint = WaypointInfo::new(WaypointId::random(), owner_id, tile);
+        assert_eq!(
+            building_state.can_build_waypoint(&waypoint),
+            Err(BuildError::InvalidOverlap)
+        );
+
+        let track_info = TrackInfo::new(owner_id, tile, TrackType::WestEast);
+        building_state.append_tracks(vec![track_info.clone()]);
+        let other_player = WaypointInfo::new(WaypointId::random(), PlayerId::random(), tile);
+        assert_eq!(
+            building_state.can_build_waypoint(&other_player),
+            Err(BuildError::InvalidOverlap)
+        );
+        building_state
+            .build_waypoint(&waypoint, &BuildCosts::none())
+            .unwrap();
+        assert_eq!(building_state.waypoint_at(tile), Some(&waypoint));
+        assert_eq!(
+            building_state.can_build_waypoint(&WaypointInfo::new(
+                WaypointId::random(),
+                owner_id,
+                tile
+            )),
+            Err(BuildError::InvalidOverlap)
+        );
+
+        building_state
+            .attempt_to_remove_tracks(owner_id, &[track_info.id()])
+            .unwrap();
+        assert_eq!(building_state.find_waypoint(waypoint.id()), None);
+    }
 
     #[test]
     fn test_disallow_build_station_over_tracks() {
@@ -960,4 +2153,544 @@ mod tests {
 
         assert_eq!(result, Ok(()));
     }
+
+    #[test]
+    fn test_expand_station_with_extra_platform() {
+        let mut building_state = BuildingState::new(4, 2);
+        let owner_id = PlayerId::random();
+        let station_id = StationId::random();
+        building_state.append_station(StationInfo::new(
+            owner_id,
+            station_id,
+            TileCoordsXZ::new(0, 0),
+            StationType::WE_1_4,
+        ));
+
+        let addition = StationInfo::new(
+            owner_id,
+            StationId::random(),
+            TileCoordsXZ::new(0, 1),
+            StationType::WE_1_4,
+        );
+        let expanded = building_state.find_station_expansion(&addition).unwrap();
+        assert_eq!(expanded.id(), station_id);
+        assert_eq!(expanded.reference_tile(), TileCoordsXZ::new(0, 0));
+        assert_eq!(expanded.station_type().platforms, 2);
+        assert_eq!(expanded.station_type().length_in_tiles, 4);
+
+        building_state
+            .expand_station(&expanded, &BuildCosts::none())
+            .unwrap();
+        assert_eq!(
+            building_state
+                .station_at(TileCoordsXZ::new(3, 1))
+                .map(StationInfo::id),
+            Some(station_id)
+        );
+
+        // With the first platform taken, arriving trains are sent to the second one
+        let station = building_state.find_station(station_id).unwrap();
+        let free = station.free_platform_exit_tile_tracks(|tile| tile.z == 0);
+        assert_eq!(free.len(), 2);
+        assert!(free.iter().all(|tile_track| tile_track.tile.z == 1));
+    }
+
+    #[test]
+    fn test_artillery_draws_ammunition_from_its_supplying_base() {
+        let mut building_state = BuildingState::new(64, 8);
+        let owner_id = PlayerId::random();
+        let artillery = MilitaryBuildingInfo::new(
+            MilitaryBuildingId::random(),
+            owner_id,
+            MilitaryBuildingType::FixedArtillery,
+            TileCoordsXZ::new(2, 4),
+        );
+        let shell_type = artillery.military_building_type().shell_type();
+        assert_eq!(
+            artillery.ammunition_status(&building_state),
+            AmmunitionStatus::NoSupplier
+        );
+
+        let mut bases = vec![];
+        for x in [10, 20] {
+            let base = IndustryBuildingInfo::new(
+                owner_id,
+                IndustryBuildingId::random(),
+                TileCoordsXZ::new(x, 4),
+                IndustryType::MilitaryBase,
+            );
+            bases.push(base.id());
+            building_state.append_industry_building(base);
+        }
+        let [closest, further] = bases[..] else {
+            panic!("Expected two bases");
+        };
+
+        // The closest base supplies the artillery, and the shot is queued while it is empty, even
+        // if the other base has the ammunition
+        building_state
+            .find_industry_building_mut(further)
+            .unwrap()
+            .add_cargo(&shell_type.cost_per_shot());
+        assert_eq!(
+            artillery.ammunition_status(&building_state),
+            AmmunitionStatus::OutOfAmmunition(closest)
+        );
+        assert_eq!(
+            building_state.can_pay_for_shot(owner_id, &artillery, shell_type),
+            Err(BuildError::NotEnoughResources)
+        );
+
+        // Unless the player prefers the other base
+        building_state.set_preferred_supplier(further).unwrap();
+        assert_eq!(
+            artillery.ammunition_status(&building_state),
+            AmmunitionStatus::Supplied(further)
+        );
+        let costs = building_state
+            .can_pay_for_shot(owner_id, &artillery, shell_type)
+            .unwrap();
+        assert_eq!(costs.costs.keys().collect::<Vec<_>>(), vec![&further]);
+    }
+
+    #[test]
+    fn test_industry_is_served_by_all_stations_within_catchment() {
+        let mut building_state = BuildingState::new(12, 12);
+        let owner_id = PlayerId::random();
+        let industry_building = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            TileCoordsXZ::new(5, 5),
+            IndustryType::CoalMine,
+        );
+        building_state.append_industry_building(industry_building.clone());
+
+        let south = StationId::random();
+        let east = StationId::random();
+        let far = StationId::random();
+        for (station_id, tile, station_type) in [
+            (south, TileCoordsXZ::new(1, 7), StationType::WE_1_4),
+            (east, TileCoordsXZ::new(7, 2), StationType::NS_1_4),
+            (far, TileCoordsXZ::new(0, 10), StationType::WE_1_4),
+        ] {
+            building_state.append_station(StationInfo::new(
+                owner_id,
+                station_id,
+                tile,
+                station_type,
+            ));
+        }
+
+        let mut linked = building_state
+            .find_linked_stations(industry_building.id())
+            .into_iter()
+            .map(StationInfo::id)
+            .collect::<Vec<_>>();
+        linked.sort();
+        let mut expected = vec![south, east];
+        expected.sort();
+        assert_eq!(linked, expected);
+
+        assert_eq!(building_state.find_linked_industry_buildings(far).len(), 0);
+    }
+
+    #[test]
+    fn test_refund_goes_to_the_linked_industry_if_it_fits() {
+        let mut building_state = BuildingState::new(16, 12);
+        let owner_id = PlayerId::random();
+        let train_factory = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            TileCoordsXZ::new(5, 5),
+            IndustryType::TrainFactory,
+        );
+        building_state.append_industry_building(train_factory.clone());
+        let linked = StationId::random();
+        let far = StationId::random();
+        for (station_id, tile) in [
+            (linked, TileCoordsXZ::new(1, 7)),
+            (far, TileCoordsXZ::new(0, 10)),
+        ] {
+            building_state.append_station(StationInfo::new(
+                owner_id,
+                station_id,
+                tile,
+                StationType::WE_1_4,
+            ));
+        }
+
+        let refund = CargoMap::single(ResourceType::RollingStock, 0.5);
+        assert_eq!(
+            building_state.refund_to_linked_industry(far, IndustryType::TrainFactory, &refund),
+            Err(())
+        );
+        assert_eq!(
+            building_state.refund_to_linked_industry(linked, IndustryType::TrainFactory, &refund),
+            Ok(())
+        );
+
+        let too_much = CargoMap::single(
+            ResourceType::RollingStock,
+            train_factory.storage_capacity().as_f32(),
+        );
+        assert_eq!(
+            building_state.refund_to_linked_industry(linked, IndustryType::TrainFactory, &too_much),
+            Err(())
+        );
+        let cargo = building_state
+            .find_industry_building(train_factory.id())
+            .unwrap()
+            .cargo()
+            .clone();
+        assert_eq!(cargo, refund);
+    }
+
+    #[test]
+    fn test_power_grid_boosts_connected_industries_and_electrifies_tracks() {
+        let mut building_state = BuildingState::new(16, 5);
+        let owner_id = PlayerId::random();
+        let power_plant = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            TileCoordsXZ::new(2, 2),
+            IndustryType::PowerPlant,
+        );
+        let coal_mine = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            TileCoordsXZ::new(12, 2),
+            IndustryType::CoalMine,
+        );
+        building_state.append_industry_building(power_plant.clone());
+        building_state.append_industry_building(coal_mine.clone());
+        building_state
+            .find_industry_building_mut(power_plant.id())
+            .unwrap()
+            .add_cargo(&CargoMap::single(ResourceType::Coal, 50.0));
+
+        let power_lines = (4 ..= 10)
+            .map(|x| PowerLineInfo::new(PowerLineId::random(), owner_id, TileCoordsXZ::new(x, 2)))
+            .collect::<Vec<_>>();
+        for power_line in &power_lines {
+            building_state.append_power_line(power_line.clone());
+        }
+
+        let diff = GameTimeDiff::from_seconds(1.0);
+        for _ in 0 .. 2 {
+            building_state.advance_time_diff(
+                GameTime::new(),
+                diff,
+                GameTime::new(),
+                &mut StatisticsState::default(),
+            );
+        }
+        let grids = building_state.power_grids();
+        assert!((grids.production_coef(coal_mine.id()) - 1.5).abs() < f32::EPSILON);
+        assert!((grids.production_coef(power_plant.id()) - 1.0).abs() < f32::EPSILON);
+        assert!(building_state.is_electrified(TileCoordsXZ::new(7, 2)));
+
+        building_state.remove_power_line(power_lines[3].id());
+        building_state.advance_time_diff(
+            GameTime::new(),
+            diff,
+            GameTime::new(),
+            &mut StatisticsState::default(),
+        );
+        let grids = building_state.power_grids();
+        assert!((grids.production_coef(coal_mine.id()) - 1.0).abs() < f32::EPSILON);
+        assert!(!building_state.is_electrified(TileCoordsXZ::new(9, 2)));
+    }
+
+    #[test]
+    fn test_tracks_under_construction_cannot_be_used_and_are_set_back_by_hits() {
+        let mut building_state = BuildingState::new(4, 1);
+        let owner_id = PlayerId::random();
+        let tile = TileCoordsXZ::new(1, 0);
+        let track_info = TrackInfo::new(owner_id, tile, TrackType::WestEast);
+        let target = ConstructionTarget::Track(track_info.id());
+        building_state.build_tracks(vec![track_info], &BuildCosts::none());
+        assert!(building_state.is_under_construction(target));
+        assert!(building_state.usable_track_types_at(tile).is_empty());
+
+        let advance = |building_state: &mut BuildingState, seconds: f32| {
+            building_state.advance_time_diff(
+                GameTime::new(),
+                GameTimeDiff::from_seconds(seconds),
+                GameTime::new(),
+                &mut StatisticsState::default(),
+            );
+        };
+        advance(&mut building_state, 5.0);
+        let progress = |building_state: &BuildingState| {
+            building_state
+                .construction_site(target)
+                .map(|construction_site| construction_site.progress())
+        };
+        assert!(progress(&building_state).is_some_and(|progress| (progress - 0.5).abs() < 0.001));
+
+        // Only the projectiles of other players set the construction back
+        building_state.hit_construction_site_at(tile, owner_id);
+        assert!(progress(&building_state).is_some_and(|progress| (progress - 0.5).abs() < 0.001));
+        building_state.hit_construction_site_at(tile, PlayerId::random());
+        assert!(progress(&building_state).is_some_and(|progress| (progress - 0.25).abs() < 0.001));
+
+        advance(&mut building_state, 10.0);
+        assert!(!building_state.is_under_construction(target));
+        assert_eq!(
+            building_state.usable_track_types_at(tile),
+            TrackTypeSet::single(TrackType::WestEast)
+        );
+    }
+
+    #[test]
+    fn test_power_lines_under_construction_do_not_power_industries() {
+        let mut building_state = BuildingState::new(16, 5);
+        let owner_id = PlayerId::random();
+        let power_plant = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            TileCoordsXZ::new(2, 2),
+            IndustryType::PowerPlant,
+        );
+        let coal_mine = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            TileCoordsXZ::new(8, 2),
+            IndustryType::CoalMine,
+        );
+        building_state.append_industry_building(power_plant.clone());
+        building_state.append_industry_building(coal_mine.clone());
+        building_state
+            .find_industry_building_mut(power_plant.id())
+            .unwrap()
+            .add_cargo(&CargoMap::single(ResourceType::Coal, 50.0));
+        for x in 4 ..= 6 {
+            let power_line =
+                PowerLineInfo::new(PowerLineId::random(), owner_id, TileCoordsXZ::new(x, 2));
+            building_state
+                .build_power_line(&power_line, &BuildCosts::none())
+                .unwrap();
+        }
+
+        let advance = |building_state: &mut BuildingState| {
+            building_state.advance_time_diff(
+                GameTime::new(),
+                GameTimeDiff::from_seconds(1.0),
+                GameTime::new(),
+                &mut StatisticsState::default(),
+            );
+        };
+        advance(&mut building_state);
+        let grids = building_state.power_grids();
+        assert!((grids.production_coef(coal_mine.id()) - 1.0).abs() < f32::EPSILON);
+
+        for _ in 0 .. 11 {
+            advance(&mut building_state);
+        }
+        let grids = building_state.power_grids();
+        assert!((grids.production_coef(coal_mine.id()) - 1.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_power_lines_do_not_overlap_other_buildings() {
+        let mut building_state = BuildingState::new(16, 16);
+        let owner_id = PlayerId::random();
+        let other_player_id = PlayerId::random();
+
+        // Nothing can be built under an existing power line, except for the owner's tracks
+        let power_line_tile = TileCoordsXZ::new(3, 3);
+        let power_line = PowerLineInfo::new(PowerLineId::random(), owner_id, power_line_tile);
+        building_state.append_power_line(power_line.clone());
+        assert_eq!(
+            building_state.power_line_at(power_line_tile),
+            Some(&power_line)
+        );
+        let industry_building = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            power_line_tile,
+            IndustryType::CoalMine,
+        );
+        assert_eq!(
+            building_state.can_build_industry_building(&industry_building),
+            Err(BuildError::InvalidOverlap)
+        );
+        let station = StationInfo::new(
+            owner_id,
+            StationId::random(),
+            power_line_tile,
+            StationType::all()[0],
+        );
+        assert_eq!(
+            building_state.can_build_station(&station),
+            Err(BuildError::InvalidOverlap)
+        );
+        assert_eq!(
+            building_state.can_build_track(other_player_id, power_line_tile, TrackType::WestEast),
+            CanBuildResponse::Invalid(BuildError::InvalidOverlap)
+        );
+        assert_eq!(
+            building_state.can_build_track(owner_id, power_line_tile, TrackType::WestEast),
+            CanBuildResponse::Ok
+        );
+
+        // Once the power line is removed, the tile is free again
+        building_state.remove_power_line(power_line.id());
+        assert_eq!(building_state.power_line_at(power_line_tile), None);
+        assert_eq!(
+            building_state.can_build_industry_building(&industry_building),
+            Ok(())
+        );
+
+        // And power lines cannot be built over buildings or other players' tracks
+        building_state.append_industry_building(industry_building);
+        let over_industry = PowerLineInfo::new(PowerLineId::random(), owner_id, power_line_tile);
+        assert_eq!(
+            building_state.can_build_power_line(&over_industry),
+            Err(BuildError::InvalidOverlap)
+        );
+        let track_tile = TileCoordsXZ::new(12, 12);
+        building_state.append_tracks(vec![TrackInfo::new(
+            other_player_id,
+            track_tile,
+            TrackType::WestEast,
+        )]);
+        let over_tracks = PowerLineInfo::new(PowerLineId::random(), owner_id, track_tile);
+        assert_eq!(
+            building_state.can_build_power_line(&over_tracks),
+            Err(BuildError::InvalidOverlap)
+        );
+    }
+
+    #[test]
+    fn test_exchange_traded_cargo_between_stations() {
+        let mut building_state = BuildingState::new(4, 2);
+        let seller_id = PlayerId::random();
+        let buyer_id = PlayerId::random();
+        let seller_station = StationInfo::new(
+            seller_id,
+            StationId::random(),
+            TileCoordsXZ::new(0, 0),
+            StationType::WE_1_4,
+        );
+        let buyer_station = StationInfo::new(
+            buyer_id,
+            StationId::random(),
+            TileCoordsXZ::new(0, 1),
+            StationType::WE_1_4,
+        );
+        building_state.append_station(seller_station.clone());
+        building_state.append_station(buyer_station.clone());
+
+        let trade_offer = TradeOffer::new(
+            TradeOfferId::random(),
+            seller_id,
+            seller_station.id(),
+            CargoMap::single(ResourceType::Coal, 10.0),
+            buyer_id,
+            CargoMap::single(ResourceType::Steel, 2.0),
+        );
+        assert_eq!(
+            building_state.exchange_traded_cargo(&trade_offer, buyer_station.id()),
+            Err(TradeError::NotEnoughCargo)
+        );
+
+        for (station_id, cargo) in [
+            (
+                seller_station.id(),
+                CargoMap::single(ResourceType::Coal, 15.0),
+            ),
+            (
+                buyer_station.id(),
+                CargoMap::single(ResourceType::Steel, 2.0),
+            ),
+        ] {
+            building_state
+                .find_station_mut(station_id)
+                .unwrap()
+                .add_cargo(&cargo);
+        }
+        // The buyer cannot pay with the seller's own station
+        assert_eq!(
+            building_state.exchange_traded_cargo(&trade_offer, seller_station.id()),
+            Err(TradeError::InvalidOwner)
+        );
+        assert_eq!(
+            building_state.exchange_traded_cargo(&trade_offer, buyer_station.id()),
+            Ok(())
+        );
+
+        let seller_cargo = building_state
+            .find_station(seller_station.id())
+            .unwrap()
+            .cargo();
+        assert_eq!(seller_cargo.get(ResourceType::Coal), CargoAmount::new(5.0));
+        assert_eq!(seller_cargo.get(ResourceType::Steel), CargoAmount::new(2.0));
+        let buyer_cargo = building_state
+            .find_station(buyer_station.id())
+            .unwrap()
+            .cargo();
+        assert_eq!(buyer_cargo.get(ResourceType::Coal), CargoAmount::new(10.0));
+        assert_eq!(buyer_cargo.get(ResourceType::Steel), CargoAmount::ZERO);
+    }
+
+    #[test]
+    fn test_perishable_cargo_spoils_at_industries_and_keeps_its_age_when_shipped() {
+        let mut building_state = BuildingState::new(12, 12);
+        let owner_id = PlayerId::random();
+        let farm = IndustryBuildingInfo::new(
+            owner_id,
+            IndustryBuildingId::random(),
+            TileCoordsXZ::new(5, 5),
+            IndustryType::Farm,
+        );
+        building_state.append_industry_building(farm.clone());
+
+        let diff = GameTimeDiff::from_seconds(1.0);
+        let ticks = 60u8;
+        for _ in 0 .. ticks {
+            building_state.advance_time_diff(
+                GameTime::new(),
+                diff,
+                GameTime::new(),
+                &mut StatisticsState::default(),
+            );
+        }
+        let produced = IndustryType::Farm
+            .transform_per_second()
+            .scaled(f32::from(ticks))
+            .outputs[0]
+            .amount;
+        let stockpile = building_state
+            .find_industry_building(farm.id())
+            .unwrap()
+            .dynamic_info();
+        let farm_products = stockpile.cargo().get(ResourceType::FarmProducts);
+        let farm_age = stockpile.cargo_age().get(ResourceType::FarmProducts);
+        assert!(farm_products > CargoAmount::ZERO);
+        assert!(farm_products < produced);
+        assert!(farm_age > GameTimeDiff::ZERO);
+
+        // The farm products shipped to the station are as old as they were at the farm
+        let station = StationInfo::new(
+            owner_id,
+            StationId::random(),
+            TileCoordsXZ::new(1, 7),
+            StationType::WE_1_4,
+        );
+        building_state.append_station(station.clone());
+        building_state.advance_time_diff(
+            GameTime::new(),
+            diff,
+            GameTime::new(),
+            &mut StatisticsState::default(),
+        );
+        let station_age = building_state
+            .find_station(station.id())
+            .unwrap()
+            .dynamic_info()
+            .cargo_age()
+            .get(ResourceType::FarmProducts);
+        assert!(station_age > farm_age);
+    }
 }
@@ -0,0 +1,109 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cargo_amount::CargoAmount;
+use crate::cargo_map::CargoMap;
+use crate::{BridgeId, MilitaryBuildingId, PowerLineId, StationId, TrackId, WaypointId};
+
+// How long it takes to finish a building without any materials delivered to the site
+const CONSTRUCTION_SECONDS: f32 = 10.0;
+// How much faster the construction goes while the site has materials to use up
+const MATERIALS_SPEEDUP: f32 = 3.0;
+// How much of the progress is lost when an enemy projectile lands on the site
+const PROGRESS_LOST_PER_HIT: f32 = 0.25;
+
+// Everything other than industry buildings that can be under construction. Industry buildings keep
+// their construction site in their dynamic info, as they also collect the materials for it.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum ConstructionTarget {
+    Station(StationId),
+    MilitaryBuilding(MilitaryBuildingId),
+    Track(TrackId),
+    Bridge(BridgeId),
+    Waypoint(WaypointId),
+    PowerLine(PowerLineId),
+}
+
+// A building that has already been paid for, but is still being built. Delivering the building's
+// cost in materials once more to the site speeds up the whole construction `MATERIALS_SPEEDUP`
+// times.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+pub struct ConstructionSite {
+    // From 0.0 (just started) to 1.0 (finished)
+    progress: f32,
+}
+
+impl Debug for ConstructionSite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "🏗 {:.0}%", self.progress * 100.0)
+    }
+}
+
+impl ConstructionSite {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.progress >= 1.0
+    }
+
+    pub(crate) fn hit(&mut self) {
+        self.progress = (self.progress - PROGRESS_LOST_PER_HIT).max(0.0);
+    }
+
+    // Returns the materials used up from `available`
+    pub(crate) fn advance(
+        &mut self,
+        seconds: f32,
+        available: &CargoMap,
+        materials: &CargoMap,
+    ) -> CargoMap {
+        let base_progress = seconds / CONSTRUCTION_SECONDS;
+        let sped_up_progress = base_progress * MATERIALS_SPEEDUP;
+        let needed = materials.clone() * sped_up_progress;
+        let (progress, used) =
+            if materials.total_amount() > CargoAmount::ZERO && available.is_superset_of(&needed) {
+                (sped_up_progress, needed)
+            } else {
+                (base_progress, CargoMap::new())
+            };
+        self.progress = (self.progress + progress).min(1.0);
+        used
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource_type::ResourceType;
+
+    #[test]
+    fn test_materials_speed_up_construction() {
+        let materials = CargoMap::single(ResourceType::Steel, 10.0);
+
+        let mut without = ConstructionSite::new();
+        let used = without.advance(1.0, &CargoMap::new(), &materials);
+        assert_eq!(used.total_amount(), CargoAmount::ZERO);
+        assert!((without.progress() - 0.1).abs() < 0.001);
+
+        let mut with = ConstructionSite::new();
+        let used = with.advance(1.0, &materials, &materials);
+        assert!((used.get(ResourceType::Steel).as_f32() - 3.0).abs() < 0.001);
+        assert!((with.progress() - 0.3).abs() < 0.001);
+
+        with.hit();
+        assert!((with.progress() - 0.05).abs() < 0.001);
+        with.hit();
+        assert!(with.progress().abs() < 0.001);
+        assert!(!with.is_finished());
+    }
+}
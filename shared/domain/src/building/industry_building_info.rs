@@ -8,9 +8,14 @@ use crate::building::building_info::{
     BuildingDynamicInfo, BuildingInfo, BuildingStaticInfo, WithBuildingDynamicInfo,
     WithBuildingDynamicInfoMut, WithCostToBuild, WithOwner, WithTileCoverage,
 };
+use crate::building::construction_site::ConstructionSite;
 use crate::building::industry_type::IndustryType;
-use crate::cargo_map::{CargoMap, WithCargo, WithCargoMut};
+use crate::building::resource_transform::ResourceTransform;
+use crate::cargo_age::CargoAge;
+use crate::cargo_amount::CargoAmount;
+use crate::cargo_map::{CargoMap, CargoOps, WithCargo, WithCargoMut};
 use crate::game_time::GameTimeDiff;
+use crate::map_level::deposit::Deposit;
 use crate::map_level::zoning::ZoningType;
 use crate::resource_type::ResourceType;
 use crate::tile_coords_xz::TileCoordsXZ;
@@ -21,6 +26,8 @@ use crate::{IndustryBuildingId, PlayerId};
 pub struct IndustryBuildingInfo {
     id:            IndustryBuildingId,
     industry_type: IndustryType,
+    // Each level multiplies the throughput and storage of the building
+    level:         u8,
     static_info:   BuildingStaticInfo,
     dynamic_info:  BuildingDynamicInfo,
 }
@@ -29,16 +36,19 @@ impl Debug for IndustryBuildingInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:?} {:?} {:?} {:?}",
+            "{:?} {:?} {:?} L{} {:?}",
             self.id(),
             self.static_info.reference_tile(),
             self.industry_type,
+            self.level,
             self.dynamic_info
         )
     }
 }
 
 impl IndustryBuildingInfo {
+    pub const MAX_LEVEL: u8 = 3;
+
     #[must_use]
     pub fn new(
         owner_id: PlayerId,
@@ -49,6 +59,7 @@ impl IndustryBuildingInfo {
         Self {
             id,
             industry_type,
+            level: 1,
             static_info: BuildingStaticInfo::new(owner_id, reference_tile),
             dynamic_info: BuildingDynamicInfo::new(CargoMap::new()),
         }
@@ -73,9 +84,91 @@ impl IndustryBuildingInfo {
         self.static_info.reference_tile()
     }
 
-    pub fn advance_industry_building(&mut self, diff: GameTimeDiff) {
+    #[must_use]
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    #[must_use]
+    pub fn can_be_upgraded(&self) -> bool {
+        self.level < Self::MAX_LEVEL
+    }
+
+    pub(crate) fn upgrade(&mut self) {
+        self.level = (self.level + 1).min(Self::MAX_LEVEL);
+    }
+
+    /// Upgrading costs as much as building the industry again for every level it already has.
+    #[must_use]
+    pub fn upgrade_cost(&self) -> (IndustryType, CargoMap) {
+        let (providing_industry_type, cost) = self.cost_to_build();
+        (providing_industry_type, cost * f32::from(self.level))
+    }
+
+    #[must_use]
+    pub fn transform_per_second(&self) -> ResourceTransform {
+        self.industry_type
+            .transform_per_second()
+            .scaled(f32::from(self.level))
+    }
+
+    #[must_use]
+    pub fn storage_capacity(&self) -> CargoAmount {
+        self.industry_type.storage_capacity() * f32::from(self.level)
+    }
+
+    // The `power_coef` speeds up industries connected to a powered grid. Returns the resources
+    // that were actually transformed.
+    pub fn advance_industry_building(
+        &mut self,
+        diff: GameTimeDiff,
+        power_coef: f32,
+    ) -> ResourceTransform {
         let seconds = diff.to_seconds();
-        self.advance_industry(seconds, self.industry_type());
+        let transformed = if self.is_under_construction() {
+            self.advance_construction(seconds);
+            ResourceTransform::new(vec![], vec![])
+        } else {
+            self.advance_industry(seconds, power_coef)
+        };
+        // Perishable inputs and outputs spoil while they wait in the stockpile
+        self.dynamic_info_mut().age_cargo(diff);
+        transformed
+    }
+
+    #[must_use]
+    pub fn construction(&self) -> Option<ConstructionSite> {
+        self.dynamic_info.construction()
+    }
+
+    #[must_use]
+    pub fn is_under_construction(&self) -> bool {
+        self.construction().is_some()
+    }
+
+    pub(crate) fn start_construction(&mut self) {
+        self.dynamic_info
+            .set_construction(Some(ConstructionSite::new()));
+    }
+
+    // Returns if there was anything unfinished to damage
+    pub(crate) fn hit_construction(&mut self) -> bool {
+        match self.dynamic_info.construction_mut() {
+            Some(construction) => {
+                construction.hit();
+                true
+            },
+            None => false,
+        }
+    }
+
+    #[must_use]
+    pub fn deposit(&self) -> Option<Deposit> {
+        self.dynamic_info.deposit()
+    }
+
+    pub(crate) fn set_deposit(&mut self, deposit: Option<Deposit>) {
+        self.dynamic_info.set_deposit(deposit);
     }
 
     #[must_use]
@@ -83,21 +176,38 @@ impl IndustryBuildingInfo {
         self.industry_type.required_zoning()
     }
 
+    // Construction sites accept the materials the building is built from instead
     #[must_use]
     pub fn industry_transform_inputs(&self) -> HashSet<ResourceType> {
+        if self.is_under_construction() {
+            let (_, materials) = self.cost_to_build();
+            return materials.resource_types_present();
+        }
         let mut result = HashSet::new();
-        for input in self.industry_type.transform_per_second().inputs {
+        for input in self.transform_per_second().inputs {
             result.insert(input.resource);
         }
         result
     }
 
+    #[must_use]
+    pub fn remaining_storage_capacity(&self) -> CargoMap {
+        self.cargo().remaining_capacity(self.storage_capacity())
+    }
+
     #[must_use]
     pub fn industry_building_shippable_cargo(&self) -> CargoMap {
-        let transform = self.industry_type.transform_per_second();
         let mut result = CargoMap::new();
+        if self.is_under_construction() {
+            // Whatever is on the site is there to be built into it
+            return result;
+        }
+        let transform = self.transform_per_second();
         for output in transform.outputs {
             let resource = output.resource;
+            if !resource.is_transportable() {
+                continue;
+            }
             // Later: This is now insta-shipping of everything... consider doing this more gradually?
             let amount = self.dynamic_info().cargo().get(resource);
             result.add(resource, amount);
@@ -105,22 +215,48 @@ impl IndustryBuildingInfo {
         result
     }
 
-    fn advance_industry(&mut self, seconds: f32, industry_type: IndustryType) {
-        let transform = industry_type.transform_per_second();
-        let utilisation =
-            transform.calculate_utilisation_percentage(self.dynamic_info().cargo(), seconds);
+    fn advance_construction(&mut self, seconds: f32) {
+        let (_, materials) = self.cost_to_build();
+        let available = self.cargo().clone();
+        let Some(construction) = self.dynamic_info.construction_mut() else {
+            return;
+        };
+        let used = construction.advance(seconds, &available, &materials);
+        let finished = construction.is_finished();
+        self.remove_cargo(&used);
+        if finished {
+            self.dynamic_info.set_construction(None);
+        }
+    }
+
+    fn advance_industry(&mut self, seconds: f32, power_coef: f32) -> ResourceTransform {
+        let transform = self.transform_per_second().scaled(power_coef);
+        let utilisation = transform.calculate_utilisation_percentage(
+            self.dynamic_info().cargo(),
+            seconds,
+            self.storage_capacity(),
+        );
+        // Mines slow down as their deposit runs out, and stop once it is exhausted
+        let utilisation = utilisation * self.deposit().map_or(1.0, |deposit| deposit.output_coef());
         let effective = seconds * utilisation;
+        self.dynamic_info_mut().set_utilisation(utilisation);
 
-        for item in transform.inputs {
+        let transformed = transform.scaled(effective);
+        for item in &transformed.inputs {
             self.dynamic_info_mut()
                 .cargo_mut()
-                .add(item.resource, -item.amount * effective);
+                .add(item.resource, -item.amount);
         }
-        for item in transform.outputs {
-            self.dynamic_info_mut()
-                .cargo_mut()
-                .add(item.resource, item.amount * effective);
+        let mut produced = CargoMap::new();
+        for item in &transformed.outputs {
+            produced.add(item.resource, item.amount);
+            if let Some(deposit) = self.dynamic_info_mut().deposit_mut() {
+                deposit.extract(item.amount);
+            }
         }
+        self.dynamic_info_mut()
+            .add_aged_cargo(&produced, &CargoAge::fresh());
+        transformed
     }
 }
 
@@ -8,6 +8,7 @@ use crate::building::WithRelativeTileCoverage;
 use crate::building::building_info::WithCostToBuild;
 use crate::building::industry_type::IndustryType::*;
 use crate::building::resource_transform::{ResourceTransform, ResourceTransformItem};
+use crate::cargo_amount::CargoAmount;
 use crate::cargo_map::CargoMap;
 use crate::map_level::zoning::ZoningType;
 use crate::map_level::zoning::ZoningType::{Industrial, Source};
@@ -182,6 +183,23 @@ impl IndustryType {
         }
     }
 
+    // How much of each resource the building can store, production stopping once the outputs are full.
+    // The construction yard and the military bases are warehouses that stockpile what they are
+    // supplied, while the factories only keep a small stock of their finished goods.
+    #[must_use]
+    pub fn storage_capacity(self) -> CargoAmount {
+        let capacity = match self {
+            CoalMine | OilWell | IronMine | NitrateMine | SulfurMine | Farm | Forestry
+            | ClayPit | LimestoneMine | SandAndGravelQuarry => 150.0,
+            PowerPlant | CoalToOilPlant | SteelMill | ExplosivesPlant | FoodProcessingPlant
+            | LumberMill | CellulosePlant | CementPlant | OilRefinery | ConcretePlant => 100.0,
+            TrainFactory | WeaponsFactory | AmmunitionFactory => 50.0,
+            MilitaryBase => 250.0,
+            ConstructionYard => 500.0,
+        };
+        CargoAmount::new(capacity)
+    }
+
     #[must_use]
     #[expect(clippy::missing_panics_doc)]
     pub fn transform_per_second(self) -> ResourceTransform {
@@ -191,6 +209,7 @@ impl IndustryType {
                 // Later: In theory, it is 2 iron to 1 coal, but we simplified it for now to avoid so much legwork for the player.
                 ResourceTransform::make(vec![(Iron, X1), (Coal, X1)], vec![(Steel, X1)])
             },
+            // Produces electricity into its `PowerGrid` instead of cargo
             PowerPlant => ResourceTransform::make(vec![(Coal, X1)], vec![]),
             CoalToOilPlant => ResourceTransform::make(vec![(Coal, X1)], vec![(Oil, X1)]),
             ExplosivesPlant => {
@@ -212,7 +231,7 @@ impl IndustryType {
                     Concrete, X1,
                 )])
             },
-            TrainFactory => ResourceTransform::make(vec![(Steel, X1)], vec![]),
+            TrainFactory => ResourceTransform::make(vec![(Steel, X1)], vec![(RollingStock, X1)]),
             WeaponsFactory => {
                 ResourceTransform::make(vec![(Steel, X1)], vec![(ArtilleryWeapons, X1)])
             },
@@ -262,19 +281,30 @@ mod tests {
     use crate::cargo_map::CargoMap;
     use crate::resource_type::ResourceType;
 
+    const CAPACITY: CargoAmount = CargoAmount::new(100.0);
+
     #[test]
     fn test_coal_mine() {
         let transform = IndustryType::CoalMine.transform_per_second();
         let cargo = CargoMap::new();
-        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5);
+        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5, CAPACITY);
         assert_eq!(utilisation, 1f32);
     }
 
+    #[test]
+    fn test_coal_mine_throttles_when_storage_is_full() {
+        let transform = IndustryType::CoalMine.transform_per_second();
+        let mut cargo = CargoMap::new();
+        cargo.add(ResourceType::Coal, CAPACITY);
+        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5, CAPACITY);
+        assert_eq!(utilisation, 0f32);
+    }
+
     #[test]
     fn test_iron_works_empty() {
         let transform = IndustryType::SteelMill.transform_per_second();
         let cargo = CargoMap::new();
-        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5);
+        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5, CAPACITY);
         assert_eq!(utilisation, 0f32);
     }
 
@@ -283,7 +313,7 @@ mod tests {
         let transform = IndustryType::SteelMill.transform_per_second();
         let mut cargo = CargoMap::new();
         cargo.add(ResourceType::Coal, CargoAmount::new(4.0));
-        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5);
+        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5, CAPACITY);
         assert_eq!(utilisation, 0f32);
     }
 
@@ -293,7 +323,7 @@ mod tests {
         let mut cargo = CargoMap::new();
         cargo.add(ResourceType::Coal, CargoAmount::new(4.0));
         cargo.add(ResourceType::Iron, CargoAmount::new(4.0));
-        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5);
+        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5, CAPACITY);
         assert_eq!(utilisation, 1f32);
     }
 
@@ -303,7 +333,7 @@ mod tests {
         let mut cargo = CargoMap::new();
         cargo.add(ResourceType::Coal, CargoAmount::new(0.025));
         cargo.add(ResourceType::Iron, CargoAmount::new(4.0));
-        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5);
+        let utilisation = transform.calculate_utilisation_percentage(&cargo, 0.5, CAPACITY);
         assert_eq!(utilisation, 0.25f32);
     }
 }
@@ -1,20 +1,18 @@
 use std::fmt::{Debug, Formatter};
 
-use bevy_math::Vec3;
-use log::info;
 use serde::{Deserialize, Serialize};
-use shared_physics::projectile::best_effort_start_velocity_vector_given_start_velocity;
 
 use crate::building::WithRelativeTileCoverage;
 use crate::building::building_info::{BuildingInfo, WithCostToBuild, WithOwner, WithTileCoverage};
+use crate::building::building_state::BuildingState;
 use crate::building::industry_type::IndustryType;
 use crate::building::military_building_type::MilitaryBuildingType;
 use crate::cargo_map::CargoMap;
 use crate::client_command::InternalGameCommand;
 use crate::game_state::GameState;
 use crate::game_time::{GameTime, GameTimeDiff};
-use crate::military::ProjectileType;
 use crate::military::projectile_info::ProjectileInfo;
+use crate::military::{AmmunitionStatus, ProjectileSource};
 use crate::tile_coords_xz::TileCoordsXZ;
 use crate::tile_coverage::TileCoverage;
 use crate::{MilitaryBuildingId, PlayerId, ProjectileId};
@@ -100,64 +98,13 @@ impl MilitaryBuildingInfo {
         self.dynamic_info.last_fired_at + self.military_building_type.reload_time()
     }
 
-    fn make_projectile(
-        &self,
-        game_state: &GameState,
-        fired_at: GameTime,
-    ) -> Option<ProjectileInfo> {
-        let artillery_height = Vec3::new(0.0, 0.5, 0.0);
-        let from_position = game_state
-            .map_level()
-            .terrain()
-            .tile_center_coordinate(self.reference_tile())
-            + artillery_height;
-        let location = from_position.into();
-
-        // TODO HIGH: Have a target selection (with the "instructions" selected in dynamic_info - initially just the closest enemy building)
-        let landing_on = TileCoordsXZ::new(56, 207);
-
-        let target_position = game_state
-            .map_level()
-            .terrain()
-            .tile_center_coordinate(landing_on);
-
-        let projectile_type = ProjectileType::Standard;
-
-        let projectile_properties = projectile_type.projectile_properties();
-
-        let velocity_and_time = best_effort_start_velocity_vector_given_start_velocity(
-            from_position,
-            target_position,
-            &projectile_properties,
-        );
-
-        info!("Calculated velocity and time: {velocity_and_time:?}");
-
-        match velocity_and_time {
-            None => {
-                info!(
-                    "Failed to create projectile from {from_position:?} to {target_position:?} - perhaps the target is too far?"
-                );
-                None
-            },
-            Some((velocity, time)) => {
-                let landing_at = fired_at + GameTimeDiff::from_seconds(time);
-
-                let projectile_info = ProjectileInfo::new(
-                    ProjectileId::new(self.id, self.dynamic_info.next_projectile_sequence_number),
-                    self.owner_id,
-                    projectile_type,
-                    self.id,
-                    fired_at,
-                    landing_at,
-                    landing_on,
-                    location,
-                    velocity.into(),
-                );
-                info!("Firing {projectile_info:?}",);
-                Some(projectile_info)
-            },
-        }
+    #[must_use]
+    pub fn ammunition_status(&self, building_state: &BuildingState) -> AmmunitionStatus {
+        building_state.ammunition_status(
+            self.owner_id,
+            self,
+            self.military_building_type.shell_type(),
+        )
     }
 
     #[must_use]
@@ -166,22 +113,26 @@ impl MilitaryBuildingInfo {
         game_state: &GameState,
         fired_at: GameTime,
     ) -> Option<InternalGameCommand> {
-        let costs = game_state.building_state().can_pay_known_cost(
-            self.owner_id,
-            self,
-            IndustryType::MilitaryBase,
-            self.military_building_type
-                .projectile_type()
-                .cost_per_shot(),
-        );
-        match costs {
+        let source = ProjectileSource::MilitaryBuilding(self.id);
+        let shell_type = self.military_building_type.shell_type();
+        match game_state.can_pay_for_shot(self.owner_id, self, shell_type, source) {
             Ok(costs) => {
-                let projectile_info = self.make_projectile(game_state, fired_at);
+                // TODO HIGH: Have a target selection (with the "instructions" selected in dynamic_info - initially just the closest enemy building)
+                let landing_on = TileCoordsXZ::new(56, 207);
+                let projectile_info = ProjectileInfo::fire_from_tile(
+                    game_state,
+                    ProjectileId::new(source, self.dynamic_info.next_projectile_sequence_number),
+                    self.owner_id,
+                    shell_type.projectile_type(),
+                    self.reference_tile,
+                    landing_on,
+                    fired_at,
+                );
                 projectile_info.map(|projectile_info| {
                     InternalGameCommand::SpawnProjectile(projectile_info, costs)
                 })
             },
-            Err(_) => None,
+            Err(_) => game_state.queue_shot_command(self.owner_id, self, source),
         }
     }
 
@@ -195,6 +146,8 @@ impl MilitaryBuildingInfo {
     ) -> Vec<InternalGameCommand> {
         let ready_to_fire = self.ready_to_fire_at();
         if new_game_time >= ready_to_fire {
+            // A shot that could not be paid for is queued at the supplying base, and is fired as
+            // soon as the base has the ammunition and the artillery queued earlier has fired
             let fired_at = ready_to_fire.max(previous_game_time);
             // Note: This can miss firing in cases where the reload rate is faster than our time diff tick, and we should have fired multiple times per this tick...
             self.fire_command(game_state, fired_at)
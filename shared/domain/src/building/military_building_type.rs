@@ -7,7 +7,7 @@ use crate::building::building_info::WithCostToBuild;
 use crate::building::industry_type::IndustryType;
 use crate::cargo_map::CargoMap;
 use crate::game_time::GameTimeDiff;
-use crate::military::ProjectileType;
+use crate::military::ShellType;
 use crate::resource_type::ResourceType;
 use crate::tile_coverage::TileCoverage;
 
@@ -39,9 +39,9 @@ impl MilitaryBuildingType {
     }
 
     #[must_use]
-    pub fn projectile_type(&self) -> ProjectileType {
+    pub fn shell_type(&self) -> ShellType {
         match self {
-            MilitaryBuildingType::FixedArtillery => ProjectileType::Standard,
+            MilitaryBuildingType::FixedArtillery => ShellType::Naval16Inch,
         }
     }
 }
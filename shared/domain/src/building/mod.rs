@@ -5,21 +5,27 @@ use std::ops::AddAssign;
 
 use serde::{Deserialize, Serialize};
 
-use crate::IndustryBuildingId;
 use crate::cargo_map::CargoMap;
+use crate::money::Money;
 use crate::tile_coverage::TileCoverage;
+use crate::{IndustryBuildingId, PlayerId};
 
+pub mod bridge_info;
 pub mod building_info;
 pub mod building_state;
+pub mod construction_site;
 pub mod industry_building_info;
 pub mod industry_type;
 pub mod military_building_info;
 pub mod military_building_type;
+pub mod power_grid;
+pub mod power_line_info;
 pub mod resource_transform;
 pub mod station_info;
 pub mod station_type;
 pub mod track_info;
 pub mod track_state;
+pub mod waypoint_info;
 
 pub trait WithRelativeTileCoverage {
     fn relative_tiles_used(&self) -> TileCoverage;
@@ -28,6 +34,7 @@ pub trait WithRelativeTileCoverage {
 #[derive(PartialEq, Clone, Debug)]
 pub struct BuildCosts {
     pub costs: HashMap<IndustryBuildingId, CargoMap>,
+    pub money: HashMap<PlayerId, Money>,
 }
 
 impl BuildCosts {
@@ -35,6 +42,7 @@ impl BuildCosts {
     pub fn none() -> Self {
         Self {
             costs: HashMap::new(),
+            money: HashMap::new(),
         }
     }
 
@@ -42,7 +50,16 @@ impl BuildCosts {
     pub fn single(industry_building_id: IndustryBuildingId, cargo_map: CargoMap) -> Self {
         let mut costs = HashMap::new();
         costs.insert(industry_building_id, cargo_map);
-        Self { costs }
+        Self {
+            costs,
+            money: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_money(mut self, player_id: PlayerId, money: Money) -> Self {
+        *self.money.entry(player_id).or_default() += money;
+        self
     }
 }
 
@@ -54,6 +71,9 @@ impl AddAssign for BuildCosts {
                 .and_modify(|existing| *existing += &cargo_map)
                 .or_insert(cargo_map);
         }
+        for (player_id, money) in rhs.money {
+            *self.money.entry(player_id).or_default() += money;
+        }
     }
 }
 
@@ -63,6 +83,12 @@ pub enum BuildError {
     InvalidTerrain,
     InvalidZoning,
     NotEnoughResources,
+    NotEnoughMoney,
     InvalidOwner,
+    TransportNotOnPlatform,
+    TransportTooLongForPlatform,
+    PlatformOccupied,
+    AlreadyAtMaxLevel,
+    UnderConstruction,
     UnknownError,
 }
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+use shared_util::direction_xz::DirectionXZ;
+
+use crate::building::building_info::{WithBuildingDynamicInfo, WithOwner, WithTileCoverage};
+use crate::building::industry_building_info::IndustryBuildingInfo;
+use crate::building::industry_type::IndustryType;
+use crate::building::power_line_info::PowerLineInfo;
+use crate::tile_coords_xz::TileCoordsXZ;
+use crate::{IndustryBuildingId, PlayerId};
+
+// Power produced by a fully utilised power plant, per level
+const POWER_PER_PLANT_LEVEL: f32 = 4.0;
+// Power drawn by a producing industry, per level
+const DEMAND_PER_INDUSTRY_LEVEL: f32 = 1.0;
+// Extra production of an industry on a grid that covers its whole demand
+const FULLY_POWERED_PRODUCTION_BONUS: f32 = 0.5;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct PowerGrid {
+    owner_id:     PlayerId,
+    tiles:        HashSet<TileCoordsXZ>,
+    power_plants: Vec<IndustryBuildingId>,
+    consumers:    Vec<IndustryBuildingId>,
+    supply:       f32,
+    load:         f32,
+}
+
+impl Debug for PowerGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} tiles, {} plants, {} consumers, {:.1} / {:.1}",
+            self.tiles.len(),
+            self.power_plants.len(),
+            self.consumers.len(),
+            self.load,
+            self.supply,
+        )
+    }
+}
+
+impl PowerGrid {
+    #[must_use]
+    pub fn owner_id(&self) -> PlayerId {
+        self.owner_id
+    }
+
+    #[must_use]
+    pub fn supply(&self) -> f32 {
+        self.supply
+    }
+
+    #[must_use]
+    pub fn load(&self) -> f32 {
+        self.load
+    }
+
+    #[must_use]
+    pub fn is_powered(&self) -> bool {
+        self.supply > 0.0
+    }
+
+    /// The share of the load that the power plants on the grid can cover.
+    #[must_use]
+    pub fn satisfaction(&self) -> f32 {
+        if self.load > 0.0 {
+            (self.supply / self.load).min(1.0)
+        } else if self.is_powered() {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn update_load(
+        &mut self,
+        industry_buildings: &HashMap<IndustryBuildingId, IndustryBuildingInfo>,
+    ) {
+        // Plants run on the coal they have, so we use how busy they were recently
+        self.supply = self
+            .power_plants
+            .iter()
+            .filter_map(|id| industry_buildings.get(id))
+            .map(|plant| {
+                POWER_PER_PLANT_LEVEL
+                    * f32::from(plant.level())
+                    * plant.dynamic_info().utilisation()
+            })
+            .sum();
+        self.load = self
+            .consumers
+            .iter()
+            .filter_map(|id| industry_buildings.get(id))
+            .filter(|consumer| !consumer.is_under_construction())
+            .map(|consumer| DEMAND_PER_INDUSTRY_LEVEL * f32::from(consumer.level()))
+            .sum();
+    }
+}
+
+/// The grids formed by the power lines, and the industries connected to each of them.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default, Debug)]
+pub struct PowerGrids {
+    grids:            Vec<PowerGrid>,
+    grid_by_tile:     HashMap<TileCoordsXZ, usize>,
+    grid_by_industry: HashMap<IndustryBuildingId, usize>,
+}
+
+impl PowerGrids {
+    // Power lines connect to the same owner's power lines on the neighbouring tiles, and industries
+    // connect to the same owner's grids that pass right next to (or under) them
+    pub(crate) fn calculate<'a>(
+        power_lines: impl IntoIterator<Item = &'a PowerLineInfo>,
+        industry_buildings: &HashMap<IndustryBuildingId, IndustryBuildingInfo>,
+    ) -> Self {
+        let owners = power_lines
+            .into_iter()
+            .map(|power_line| (power_line.tile(), power_line.owner_id()))
+            .collect::<HashMap<_, _>>();
+        // Sorted, so that the grids are numbered the same way on the server & the client
+        let mut tiles = owners.keys().copied().collect::<Vec<_>>();
+        tiles.sort();
+
+        let mut result = Self::default();
+        for start in tiles {
+            if result.grid_by_tile.contains_key(&start) {
+                continue;
+            }
+            let owner_id = owners[&start];
+            let grid_index = result.grids.len();
+            let mut grid_tiles = HashSet::new();
+            let mut queue = VecDeque::from([start]);
+            while let Some(tile) = queue.pop_front() {
+                if owners.get(&tile) != Some(&owner_id) || !grid_tiles.insert(tile) {
+                    continue;
+                }
+                result.grid_by_tile.insert(tile, grid_index);
+                for direction in DirectionXZ::cardinal() {
+                    queue.push_back(tile + direction);
+                }
+            }
+            result.grids.push(PowerGrid {
+                owner_id,
+                tiles: grid_tiles,
+                power_plants: vec![],
+                consumers: vec![],
+                supply: 0.0,
+                load: 0.0,
+            });
+        }
+
+        let mut industry_ids = industry_buildings.keys().copied().collect::<Vec<_>>();
+        industry_ids.sort();
+        for industry_building_id in industry_ids {
+            let building = &industry_buildings[&industry_building_id];
+            // Later: An industry touching several grids could join them together
+            let grid_index = building
+                .covers_tiles()
+                .extend(1)
+                .into_iter()
+                .filter_map(|tile| result.grid_by_tile.get(&tile).copied())
+                .filter(|grid_index| result.grids[*grid_index].owner_id == building.owner_id())
+                .min();
+            if let Some(grid_index) = grid_index {
+                let grid = &mut result.grids[grid_index];
+                if building.industry_type() == IndustryType::PowerPlant {
+                    grid.power_plants.push(industry_building_id);
+                } else if building.transform_per_second().outputs.is_empty() {
+                    // Warehouses do not produce anything, so they have no use for power
+                    continue;
+                } else {
+                    grid.consumers.push(industry_building_id);
+                }
+                result
+                    .grid_by_industry
+                    .insert(industry_building_id, grid_index);
+            }
+        }
+
+        result.update_loads(industry_buildings);
+        result
+    }
+
+    pub(crate) fn update_loads(
+        &mut self,
+        industry_buildings: &HashMap<IndustryBuildingId, IndustryBuildingInfo>,
+    ) {
+        for grid in &mut self.grids {
+            grid.update_load(industry_buildings);
+        }
+    }
+
+    #[must_use]
+    pub fn grid_at(&self, tile: TileCoordsXZ) -> Option<&PowerGrid> {
+        self.grid_by_tile
+            .get(&tile)
+            .map(|grid_index| &self.grids[*grid_index])
+    }
+
+    #[must_use]
+    pub fn grid_of_industry(&self, industry_building_id: IndustryBuildingId) -> Option<&PowerGrid> {
+        self.grid_by_industry
+            .get(&industry_building_id)
+            .map(|grid_index| &self.grids[*grid_index])
+    }
+
+    #[must_use]
+    pub fn is_electrified(&self, tile: TileCoordsXZ) -> bool {
+        self.grid_at(tile).is_some_and(PowerGrid::is_powered)
+    }
+
+    /// Multiplies the production of the industry, depending on how well its grid is powered.
+    #[must_use]
+    pub fn production_coef(&self, industry_building_id: IndustryBuildingId) -> f32 {
+        match self.grid_of_industry(industry_building_id) {
+            Some(grid) if grid.consumers.contains(&industry_building_id) => {
+                1.0 + FULLY_POWERED_PRODUCTION_BONUS * grid.satisfaction()
+            },
+            _ => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PowerLineId;
+
+    #[test]
+    fn test_only_adjacent_power_lines_of_the_same_owner_are_connected() {
+        let player_a = PlayerId::random();
+        let player_b = PlayerId::random();
+        let power_lines = [
+            (player_a, TileCoordsXZ::new(0, 0)),
+            (player_a, TileCoordsXZ::new(1, 0)),
+            (player_a, TileCoordsXZ::new(1, 1)),
+            (player_a, TileCoordsXZ::new(3, 1)),
+            (player_b, TileCoordsXZ::new(2, 1)),
+        ]
+        .map(|(owner_id, tile)| PowerLineInfo::new(PowerLineId::random(), owner_id, tile));
+
+        let grids = PowerGrids::calculate(&power_lines, &HashMap::new());
+        assert_eq!(grids.grids.len(), 3);
+        assert_eq!(
+            grids.grid_by_tile[&TileCoordsXZ::new(0, 0)],
+            grids.grid_by_tile[&TileCoordsXZ::new(1, 1)]
+        );
+        assert_ne!(
+            grids.grid_by_tile[&TileCoordsXZ::new(1, 1)],
+            grids.grid_by_tile[&TileCoordsXZ::new(3, 1)]
+        );
+        assert!(!grids.is_electrified(TileCoordsXZ::new(0, 0)));
+    }
+}
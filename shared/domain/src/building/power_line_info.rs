@@ -0,0 +1,64 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::building::building_info::{WithCostToBuild, WithOwner, WithTileCoverage};
+use crate::building::industry_type::IndustryType;
+use crate::cargo_map::CargoMap;
+use crate::resource_type::ResourceType;
+use crate::tile_coords_xz::TileCoordsXZ;
+use crate::tile_coverage::TileCoverage;
+use crate::{PlayerId, PowerLineId};
+
+// A single tile of overhead power line. Adjacent power lines of the same owner form a grid, and
+// power lines built over tracks electrify them.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct PowerLineInfo {
+    id:       PowerLineId,
+    owner_id: PlayerId,
+    tile:     TileCoordsXZ,
+}
+
+impl Debug for PowerLineInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {:?}", self.id, self.tile)
+    }
+}
+
+impl PowerLineInfo {
+    #[must_use]
+    pub fn new(id: PowerLineId, owner_id: PlayerId, tile: TileCoordsXZ) -> Self {
+        Self { id, owner_id, tile }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> PowerLineId {
+        self.id
+    }
+
+    #[must_use]
+    pub fn tile(&self) -> TileCoordsXZ {
+        self.tile
+    }
+}
+
+impl WithOwner for PowerLineInfo {
+    fn owner_id(&self) -> PlayerId {
+        self.owner_id
+    }
+}
+
+impl WithTileCoverage for PowerLineInfo {
+    fn covers_tiles(&self) -> TileCoverage {
+        TileCoverage::Single(self.tile)
+    }
+}
+
+impl WithCostToBuild for PowerLineInfo {
+    fn cost_to_build(&self) -> (IndustryType, CargoMap) {
+        (
+            IndustryType::ConstructionYard,
+            CargoMap::single(ResourceType::Steel, 0.02),
+        )
+    }
+}
@@ -52,9 +52,26 @@ impl ResourceTransform {
         ResourceTransform::make(inputs, vec![])
     }
 
-    // Later: How do we handle when the stock is too full, and we should stop producing due to that?
+    /// The same transform running `coef` times as fast.
     #[must_use]
-    pub fn calculate_utilisation_percentage(&self, cargo: &CargoMap, seconds: f32) -> f32 {
+    pub fn scaled(self, coef: f32) -> Self {
+        let scale = |items: Vec<ResourceTransformItem>| {
+            items
+                .into_iter()
+                .map(|item| ResourceTransformItem::new(item.resource, item.amount * coef))
+                .collect()
+        };
+        ResourceTransform::new(scale(self.inputs), scale(self.outputs))
+    }
+
+    // Production is limited both by the inputs available, and by the space left for the outputs
+    #[must_use]
+    pub fn calculate_utilisation_percentage(
+        &self,
+        cargo: &CargoMap,
+        seconds: f32,
+        capacity: CargoAmount,
+    ) -> f32 {
         let mut utilisation = 1f32;
         for item in &self.inputs {
             let available = cargo.get(item.resource);
@@ -62,7 +79,13 @@ impl ResourceTransform {
             let ratio = available / required;
             utilisation = utilisation.min(ratio);
         }
-        utilisation
+        for item in &self.outputs {
+            let space = capacity - cargo.get(item.resource);
+            let produced = item.amount * seconds;
+            let ratio = space / produced;
+            utilisation = utilisation.min(ratio);
+        }
+        utilisation.max(0.0)
     }
 }
 
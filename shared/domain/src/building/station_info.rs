@@ -70,6 +70,66 @@ impl StationInfo {
             .collect()
     }
 
+    /// The exits of the platforms that have no tiles occupied (as decided by `is_occupied`), so
+    /// that arriving trains spread across the platforms. If every platform is occupied, we return
+    /// all the exits, as the train will have to wait for one of them anyway.
+    #[must_use]
+    pub fn free_platform_exit_tile_tracks(
+        &self,
+        is_occupied: impl Fn(TileCoordsXZ) -> bool,
+    ) -> Vec<TileTrack> {
+        let exits = self.station_type.exit_tile_tracks(self.reference_tile());
+        let free = exits
+            .iter()
+            .filter(|(platform_index, _)| {
+                !self
+                    .station_type
+                    .platform_tiles(self.reference_tile(), *platform_index)
+                    .into_iter()
+                    .any(&is_occupied)
+            })
+            .map(|(_, tile_track)| *tile_track)
+            .collect::<Vec<_>>();
+
+        if free.is_empty() {
+            self.station_exit_tile_tracks()
+        } else {
+            free
+        }
+    }
+
+    /// The station that we get when we expand this station with `addition`, keeping this station's
+    /// ID and cargo. The addition has to have the same orientation and together they have to form
+    /// a rectangle that is a valid station.
+    #[must_use]
+    pub fn expanded_with(&self, addition: &StationInfo) -> Option<StationInfo> {
+        let orientation = self.station_type.orientation;
+        if self.owner_id() != addition.owner_id()
+            || orientation != addition.station_type.orientation
+        {
+            return None;
+        }
+
+        let existing = self.covers_tiles();
+        let added = addition.covers_tiles();
+        let combined = existing.bounding_box_with(&added);
+        let is_rectangle = combined
+            .into_iter()
+            .all(|tile| existing.contains(tile) || added.contains(tile));
+        if combined == existing || !is_rectangle {
+            return None;
+        }
+
+        let station_type =
+            StationType::with_size(orientation, combined.size_x(), combined.size_z())?;
+        Some(Self {
+            id: self.id,
+            station_type,
+            static_info: BuildingStaticInfo::new(self.owner_id(), combined.north_west_corner()),
+            dynamic_info: self.dynamic_info.clone(),
+        })
+    }
+
     #[must_use]
     #[expect(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
     pub fn transport_location_at_station(
@@ -136,6 +196,24 @@ impl StationInfo {
             .track_types_at(tile - self.reference_tile())
     }
 
+    // The industries intersecting this area exchange cargo with the station
+    #[must_use]
+    pub fn catchment_area(&self) -> TileCoverage {
+        self.covers_tiles()
+            .extend(self.station_type.catchment_radius())
+    }
+
+    #[must_use]
+    pub fn remaining_storage_capacity(&self) -> CargoMap {
+        self.cargo()
+            .remaining_capacity(self.station_type.storage_capacity())
+    }
+
+    pub(crate) fn update_utilisation(&mut self) {
+        let fullness = self.cargo().fullness(self.station_type.storage_capacity());
+        self.dynamic_info.set_utilisation(fullness);
+    }
+
     #[must_use]
     pub fn station_shippable_cargo(&self) -> CargoMap {
         self.dynamic_info().cargo().clone()
@@ -6,15 +6,16 @@ use shared_util::direction_xz::DirectionXZ;
 use crate::building::WithRelativeTileCoverage;
 use crate::building::building_info::WithCostToBuild;
 use crate::building::industry_type::IndustryType;
+use crate::cargo_amount::CargoAmount;
 use crate::cargo_map::CargoMap;
 use crate::resource_type::ResourceType;
-use crate::tile_coords_xz::TileCoordsXZ;
+use crate::tile_coords_xz::{TileCoordsXZ, TileDistance};
 use crate::tile_coverage::TileCoverage;
 use crate::transport::tile_track::TileTrack;
 use crate::transport::track_type::TrackType;
 use crate::transport::track_type_set::TrackTypeSet;
 
-#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash)]
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash, Debug)]
 pub struct PlatformIndex(usize);
 
 impl PlatformIndex {
@@ -30,6 +31,13 @@ pub enum StationOrientation {
     WestToEast,
 }
 
+impl StationOrientation {
+    #[must_use]
+    pub const fn all() -> [Self; 2] {
+        [Self::NorthToSouth, Self::WestToEast]
+    }
+}
+
 impl Debug for StationOrientation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -57,6 +65,8 @@ impl Debug for StationType {
 }
 
 impl StationType {
+    pub const MAX_LENGTH_IN_TILES: usize = 8;
+    pub const MAX_PLATFORMS: usize = 4;
     pub const NS_1_4: StationType = StationType {
         orientation:     StationOrientation::NorthToSouth,
         platforms:       1,
@@ -68,9 +78,105 @@ impl StationType {
         length_in_tiles: 4,
     };
 
+    /// The station types offered for building, the first one being the smallest.
+    /// Larger stations can still be reached by expanding existing ones.
     #[must_use]
-    pub const fn all() -> [Self; 2] {
-        [Self::NS_1_4, Self::WE_1_4]
+    pub fn all() -> Vec<Self> {
+        let mut results = vec![];
+        for platforms in [1, 2, 3] {
+            for length_in_tiles in [4, 6] {
+                for orientation in StationOrientation::all() {
+                    results.push(Self {
+                        orientation,
+                        platforms,
+                        length_in_tiles,
+                    });
+                }
+            }
+        }
+        results
+    }
+
+    /// Every station type that can exist, including those only obtainable by expansion
+    #[must_use]
+    pub fn all_sizes() -> Vec<Self> {
+        let mut results = vec![];
+        for platforms in 1 ..= Self::MAX_PLATFORMS {
+            for length_in_tiles in 1 ..= Self::MAX_LENGTH_IN_TILES {
+                for orientation in StationOrientation::all() {
+                    results.push(Self {
+                        orientation,
+                        platforms,
+                        length_in_tiles,
+                    });
+                }
+            }
+        }
+        results
+    }
+
+    /// The station type with this orientation that covers `size_x` × `size_z` tiles, if it is
+    /// within the limits
+    #[must_use]
+    pub fn with_size(
+        orientation: StationOrientation,
+        size_x: TileDistance,
+        size_z: TileDistance,
+    ) -> Option<Self> {
+        let size_x = usize::try_from(size_x).ok()?;
+        let size_z = usize::try_from(size_z).ok()?;
+        let (platforms, length_in_tiles) = match orientation {
+            StationOrientation::NorthToSouth => (size_x, size_z),
+            StationOrientation::WestToEast => (size_z, size_x),
+        };
+        let valid = (1 ..= Self::MAX_PLATFORMS).contains(&platforms)
+            && (1 ..= Self::MAX_LENGTH_IN_TILES).contains(&length_in_tiles);
+        valid.then_some(Self {
+            orientation,
+            platforms,
+            length_in_tiles,
+        })
+    }
+
+    /// How far around the station (in tiles) the industries are served by it, larger stations
+    /// reaching further
+    #[must_use]
+    #[expect(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn catchment_radius(self) -> TileDistance {
+        const TILES_PER_EXTRA_RADIUS: usize = 8;
+        const MAX_CATCHMENT_RADIUS: TileDistance = 3;
+        let extra =
+            (self.platforms * self.length_in_tiles / TILES_PER_EXTRA_RADIUS) as TileDistance;
+        (1 + extra).min(MAX_CATCHMENT_RADIUS)
+    }
+
+    // How much of each resource the station can store, larger stations storing more
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn storage_capacity(self) -> CargoAmount {
+        const CAPACITY_PER_TILE: f32 = 10.0;
+        CargoAmount::new(CAPACITY_PER_TILE * (self.platforms * self.length_in_tiles) as f32)
+    }
+
+    #[must_use]
+    pub fn platform_tiles(
+        self,
+        reference_tile: TileCoordsXZ,
+        platform_index: PlatformIndex,
+    ) -> Vec<TileCoordsXZ> {
+        let PlatformIndex(platform) = platform_index;
+        (0 .. self.length_in_tiles)
+            .map(|i| {
+                match self.orientation {
+                    StationOrientation::NorthToSouth => {
+                        reference_tile + TileCoordsXZ::from_usizes(platform, i)
+                    },
+                    StationOrientation::WestToEast => {
+                        reference_tile + TileCoordsXZ::from_usizes(i, platform)
+                    },
+                }
+            })
+            .collect()
     }
 
     /// These are the last `TileTrack`-s in a station, so if a train is parked `about_to_exit` on
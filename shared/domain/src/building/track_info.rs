@@ -7,6 +7,7 @@ use crate::edge_xz::EdgeXZ;
 use crate::tile_coords_xz::TileCoordsXZ;
 use crate::tile_coverage::TileCoverage;
 use crate::transport::tile_track::TileTrack;
+use crate::transport::track_grade::TrackGrade;
 use crate::transport::track_type::TrackType;
 use crate::{PlayerId, TrackId};
 
@@ -18,6 +19,7 @@ pub struct TrackInfo {
     owner_id:       PlayerId,
     pub tile:       TileCoordsXZ,
     pub track_type: TrackType,
+    pub grade:      TrackGrade,
 }
 
 impl TrackInfo {
@@ -28,9 +30,16 @@ impl TrackInfo {
             owner_id,
             tile,
             track_type,
+            grade: TrackGrade::default(),
         }
     }
 
+    #[must_use]
+    pub fn with_grade(mut self, grade: TrackGrade) -> Self {
+        self.grade = grade;
+        self
+    }
+
     #[must_use]
     pub fn from_tile_track(owner_id: PlayerId, tile_track: TileTrack) -> Self {
         Self::new(owner_id, tile_track.tile, tile_track.track_type)
@@ -59,7 +68,8 @@ impl WithOwner for TrackInfo {
 
 impl WithCostToBuild for TrackInfo {
     fn cost_to_build(&self) -> (IndustryType, CargoMap) {
-        self.track_type.cost_to_build()
+        let (industry_type, cost) = self.track_type.cost_to_build();
+        (industry_type, cost * self.grade.cost_coef())
     }
 }
 
@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
 use log::warn;
 use serde::{Deserialize, Serialize};
 use shared_util::grid_xz::GridXZ;
 
 use crate::building::building_info::WithOwner;
 use crate::building::track_info::TrackInfo;
+use crate::money::Money;
 use crate::tile_coords_xz::TileCoordsXZ;
+use crate::transport::track_grade::TrackGrade;
 use crate::transport::track_type::TrackType;
 use crate::transport::track_type_set::TrackTypeSet;
 use crate::{PlayerId, TrackId};
@@ -16,6 +20,8 @@ pub enum MaybeTracksOnTile {
     SingleOwner {
         owner_id:    PlayerId,
         track_types: TrackTypeSet,
+        // Indexed by `TrackType as usize`, only meaningful for the types in `track_types`
+        grades:      [TrackGrade; 6],
     },
 }
 
@@ -36,13 +42,49 @@ impl MaybeTracksOnTile {
         }
     }
 
+    #[must_use]
+    pub fn grade(&self, track_type: TrackType) -> Option<TrackGrade> {
+        match self {
+            Self::Empty => None,
+            Self::SingleOwner {
+                track_types,
+                grades,
+                ..
+            } => {
+                track_types
+                    .contains(track_type)
+                    .then_some(grades[track_type as usize])
+            },
+        }
+    }
+
+    fn set_grade(&mut self, track_type: TrackType, grade: TrackGrade) {
+        match self {
+            Self::SingleOwner {
+                track_types,
+                grades,
+                ..
+            } if track_types.contains(track_type) => {
+                grades[track_type as usize] = grade;
+            },
+            _ => {
+                warn!("Tried to set grade of missing track: {:?}", track_type);
+            },
+        }
+    }
+
     fn remove_track_type(&mut self, track_type: TrackType) {
         match self {
             Self::Empty => {
                 warn!("Tried to remove track from empty tile: {:?}", track_type);
             },
-            Self::SingleOwner { track_types, .. } => {
+            Self::SingleOwner {
+                track_types,
+                grades,
+                ..
+            } => {
                 track_types.remove(track_type);
+                grades[track_type as usize] = TrackGrade::default();
                 if track_types.is_empty() {
                     *self = Self::Empty;
                 }
@@ -53,17 +95,22 @@ impl MaybeTracksOnTile {
     fn append_track(&mut self, track: &TrackInfo) {
         match self {
             Self::Empty => {
+                let mut grades = [TrackGrade::default(); 6];
+                grades[track.track_type as usize] = track.grade;
                 *self = Self::SingleOwner {
-                    owner_id:    track.owner_id(),
+                    owner_id: track.owner_id(),
                     track_types: TrackTypeSet::single(track.track_type),
+                    grades,
                 };
             },
             Self::SingleOwner {
                 owner_id,
                 track_types,
+                grades,
             } => {
                 if *owner_id == track.owner_id() {
                     track_types.insert(track.track_type);
+                    grades[track.track_type as usize] = track.grade;
                 } else {
                     warn!(
                         "Tried to add track to tile with different owner: {:?}",
@@ -131,6 +178,22 @@ impl TrackState {
         }
     }
 
+    pub(crate) fn set_grades(&mut self, tracks: &[TrackInfo]) {
+        for track in tracks {
+            match self.grid.get_mut(track.tile) {
+                None => {
+                    warn!(
+                        "Tried to upgrade track on non-existing tile: {:?}",
+                        track.tile
+                    );
+                },
+                Some(contents) => {
+                    contents.set_grade(track.track_type, track.grade);
+                },
+            }
+        }
+    }
+
     fn append_track(&mut self, track: &TrackInfo) {
         match self.grid.get_mut(track.tile) {
             None => {
@@ -149,11 +212,37 @@ impl TrackState {
             if let MaybeTracksOnTile::SingleOwner {
                 owner_id,
                 track_types,
+                grades,
             } = self.tracks_at(tile)
             {
                 for track_type in TrackType::all() {
                     if track_types.contains(track_type) {
-                        results.push(TrackInfo::new(owner_id, tile, track_type));
+                        results.push(
+                            TrackInfo::new(owner_id, tile, track_type)
+                                .with_grade(grades[track_type as usize]),
+                        );
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    // Iterates the whole grid, so should not be called too often
+    #[must_use]
+    pub(crate) fn running_costs_per_second(&self) -> HashMap<PlayerId, Money> {
+        let mut results = HashMap::new();
+        for tile in self.grid.coords() {
+            if let Some(MaybeTracksOnTile::SingleOwner {
+                owner_id,
+                track_types,
+                grades,
+            }) = self.grid.get(tile)
+            {
+                let result: &mut Money = results.entry(*owner_id).or_default();
+                for track_type in TrackType::all() {
+                    if track_types.contains(track_type) {
+                        *result += grades[track_type as usize].running_cost_per_second();
                     }
                 }
             }
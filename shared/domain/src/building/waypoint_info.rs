@@ -0,0 +1,63 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::building::building_info::{WithCostToBuild, WithOwner, WithTileCoverage};
+use crate::building::industry_type::IndustryType;
+use crate::cargo_map::CargoMap;
+use crate::resource_type::ResourceType;
+use crate::tile_coords_xz::TileCoordsXZ;
+use crate::tile_coverage::TileCoverage;
+use crate::{PlayerId, WaypointId};
+
+// A marker placed on a tile with tracks, which trains can be ordered to pass through without stopping
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct WaypointInfo {
+    id:       WaypointId,
+    owner_id: PlayerId,
+    tile:     TileCoordsXZ,
+}
+
+impl Debug for WaypointInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {:?}", self.id, self.tile)
+    }
+}
+
+impl WaypointInfo {
+    #[must_use]
+    pub fn new(id: WaypointId, owner_id: PlayerId, tile: TileCoordsXZ) -> Self {
+        Self { id, owner_id, tile }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> WaypointId {
+        self.id
+    }
+
+    #[must_use]
+    pub fn tile(&self) -> TileCoordsXZ {
+        self.tile
+    }
+}
+
+impl WithOwner for WaypointInfo {
+    fn owner_id(&self) -> PlayerId {
+        self.owner_id
+    }
+}
+
+impl WithTileCoverage for WaypointInfo {
+    fn covers_tiles(&self) -> TileCoverage {
+        TileCoverage::Single(self.tile)
+    }
+}
+
+impl WithCostToBuild for WaypointInfo {
+    fn cost_to_build(&self) -> (IndustryType, CargoMap) {
+        (
+            IndustryType::ConstructionYard,
+            CargoMap::single(ResourceType::Steel, 0.01),
+        )
+    }
+}
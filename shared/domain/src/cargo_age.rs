@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cargo_map::CargoMap;
+use crate::game_time::GameTimeDiff;
+use crate::money::Money;
+use crate::resource_type::ResourceType;
+
+/// How long the perishable cargo in a stockpile has been on its way. Cargo of different ages gets
+/// mixed, so we only keep the average age of each resource, weighted by the amounts.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CargoAge {
+    seconds: HashMap<ResourceType, f32>,
+}
+
+impl Debug for CargoAge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut results = vec![];
+        for resource in ResourceType::all() {
+            if let Some(seconds) = self.seconds.get(&resource) {
+                results.push(format!("{resource:?} {seconds:.0}s"));
+            }
+        }
+        write!(f, "{}", results.join(", "))
+    }
+}
+
+impl CargoAge {
+    /// Freshly produced cargo.
+    #[must_use]
+    pub fn fresh() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn get(&self, resource: ResourceType) -> GameTimeDiff {
+        GameTimeDiff::from_seconds(self.seconds.get(&resource).copied().unwrap_or_default())
+    }
+
+    /// The share of the value the cargo still has, from 1 for fresh cargo down towards 0.
+    #[must_use]
+    pub fn freshness(&self, resource: ResourceType) -> f32 {
+        match resource.shelf_life() {
+            None => 1.0,
+            Some(shelf_life) => (-self.get(resource).to_seconds() / shelf_life.to_seconds()).exp(),
+        }
+    }
+
+    /// What the cargo is worth when delivered, less for the perishable cargo that arrives late.
+    #[must_use]
+    pub fn value_of(&self, cargo: &CargoMap) -> Money {
+        let mut result = Money::ZERO;
+        for resource in cargo.resource_types_present() {
+            let amount = cargo.get(resource).as_f32() * self.freshness(resource);
+            result += resource.value() * f64::from(amount);
+        }
+        result
+    }
+
+    // To be called before `added` is added to `existing`
+    pub(crate) fn mix(&mut self, existing: &CargoMap, added: &CargoMap, added_age: &CargoAge) {
+        for resource in added.resource_types_present() {
+            if resource.shelf_life().is_some() {
+                let existing_amount = existing.get(resource).as_f32().max(0.0);
+                let added_amount = added.get(resource).as_f32().max(0.0);
+                let total = existing_amount + added_amount;
+                if total > 0.0 {
+                    let mixed = (existing_amount * self.get(resource).to_seconds()
+                        + added_amount * added_age.get(resource).to_seconds())
+                        / total;
+                    self.seconds.insert(resource, mixed);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn advance(&mut self, cargo: &CargoMap, diff: GameTimeDiff) {
+        // Whatever is used up leaves no age behind, so the next cargo starts fresh
+        self.seconds
+            .retain(|resource, _| cargo.contains_resource(*resource));
+        for resource in cargo.resource_types_present() {
+            if resource.shelf_life().is_some() {
+                *self.seconds.entry(resource).or_default() += diff.to_seconds();
+            }
+        }
+    }
+
+    // The part of the stockpile that has gone off during `diff`
+    pub(crate) fn spoiled(cargo: &CargoMap, diff: GameTimeDiff) -> CargoMap {
+        let mut result = CargoMap::new();
+        for resource in cargo.resource_types_present() {
+            if let Some(shelf_life) = resource.shelf_life() {
+                let remaining = (-diff.to_seconds() / shelf_life.to_seconds()).exp();
+                let amount = cargo.get(resource).as_f32();
+                result += &CargoMap::single(resource, amount * (1.0 - remaining));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cargo_amount::CargoAmount;
+
+    #[test]
+    fn test_mixing_averages_the_ages() {
+        let mut age = CargoAge::fresh();
+        let mut cargo = CargoMap::new();
+        let added = CargoMap::single(ResourceType::Food, 10.0);
+        age.mix(&cargo, &added, &CargoAge::fresh());
+        cargo += &added;
+        age.advance(&cargo, GameTimeDiff::from_seconds(30.0));
+
+        let mut added_age = CargoAge::fresh();
+        added_age.advance(&added, GameTimeDiff::from_seconds(90.0));
+        age.mix(&cargo, &added, &added_age);
+        assert!((age.get(ResourceType::Food).to_seconds() - 60.0).abs() < 0.001);
+
+        // Non-perishable cargo does not age
+        let coal = CargoMap::single(ResourceType::Coal, 10.0);
+        age.advance(&coal, GameTimeDiff::from_seconds(30.0));
+        assert_eq!(age.get(ResourceType::Coal), GameTimeDiff::ZERO);
+        assert!((age.freshness(ResourceType::Coal) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_only_perishable_cargo_spoils() {
+        let cargo = CargoMap::from([(ResourceType::Food, 10.0), (ResourceType::Coal, 10.0)]);
+        let spoiled = CargoAge::spoiled(&cargo, GameTimeDiff::from_seconds(60.0));
+        assert!(spoiled.get(ResourceType::Food) > CargoAmount::ZERO);
+        assert!(spoiled.get(ResourceType::Food) < CargoAmount::new(10.0));
+        assert_eq!(spoiled.get(ResourceType::Coal), CargoAmount::ZERO);
+    }
+}
@@ -11,7 +11,7 @@ impl CargoAmount {
     pub const ZERO: Self = Self(0.0);
 
     #[must_use]
-    pub fn new(amount: f32) -> Self {
+    pub const fn new(amount: f32) -> Self {
         Self(amount)
     }
 
@@ -5,6 +5,7 @@ use std::ops::{AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use serde::{Deserialize, Serialize};
 
 use crate::cargo_amount::CargoAmount;
+use crate::money::Money;
 use crate::resource_type::ResourceType;
 
 pub trait WithCargo {
@@ -89,6 +90,15 @@ impl CargoMap {
         result
     }
 
+    #[must_use]
+    pub fn value(&self) -> Money {
+        let mut result = Money::ZERO;
+        for (resource_type, amount) in &self.map {
+            result += resource_type.value() * f64::from(amount.as_f32());
+        }
+        result
+    }
+
     #[must_use]
     pub fn filter<F>(self, f: F) -> Self
     where
@@ -114,6 +124,28 @@ impl CargoMap {
         Self { map }
     }
 
+    // How much more of each resource fits, if every resource can be stored up to `capacity`
+    #[must_use]
+    pub fn remaining_capacity(&self, capacity: CargoAmount) -> Self {
+        let map = ResourceType::all()
+            .into_iter()
+            .map(|resource_type| {
+                let remaining = capacity - self.get(resource_type);
+                (resource_type, remaining.max(CargoAmount::ZERO))
+            })
+            .collect();
+        Self { map }
+    }
+
+    // The share of `capacity` used by the resource that has the most of it
+    #[must_use]
+    pub fn fullness(&self, capacity: CargoAmount) -> f32 {
+        self.map
+            .values()
+            .map(|amount| *amount / capacity)
+            .fold(0.0, f32::max)
+    }
+
     #[must_use]
     pub fn contains_resource(&self, resource: ResourceType) -> bool {
         self.get(resource) != CargoAmount::ZERO
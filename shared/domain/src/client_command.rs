@@ -7,18 +7,26 @@ use uuid::Uuid;
 use web_time::Duration;
 
 use crate::building::BuildCosts;
+use crate::building::bridge_info::BridgeInfo;
 use crate::building::industry_building_info::IndustryBuildingInfo;
 use crate::building::military_building_info::MilitaryBuildingInfo;
+use crate::building::power_line_info::PowerLineInfo;
 use crate::building::station_info::StationInfo;
 use crate::building::track_info::TrackInfo;
+use crate::building::waypoint_info::WaypointInfo;
 use crate::game_state::GameState;
 use crate::game_time::TimeFactor;
+use crate::military::ProjectileSource;
 use crate::military::projectile_info::ProjectileInfo;
+use crate::players::trade_offer::TradeOffer;
 use crate::transport::movement_orders::MovementOrders;
+use crate::transport::track_grade::TrackGrade;
+use crate::transport::transport_group::TransportGroupInfo;
 use crate::transport::transport_info::TransportInfo;
 use crate::{
-    ClientId, GameId, IndustryBuildingId, MilitaryBuildingId, PlayerId, ProjectileId, ScenarioId,
-    StationId, TrackId, TransportId, UserId,
+    BridgeId, ClientId, GameId, IndustryBuildingId, MilitaryBuildingId, PlayerId, PowerLineId,
+    ProjectileId, ScenarioId, StationId, TrackId, TradeOfferId, TransportGroupId, TransportId,
+    UserId, WaypointId,
 };
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -52,6 +60,10 @@ pub enum DemolishSelector {
     Industry(IndustryBuildingId),
     Station(StationId),
     MilitaryBuilding(MilitaryBuildingId),
+    Waypoint(WaypointId),
+    Bridge(BridgeId),
+    PowerLine(PowerLineId),
+    Transport(TransportId),
 }
 
 impl Debug for DemolishSelector {
@@ -63,6 +75,14 @@ impl Debug for DemolishSelector {
             DemolishSelector::MilitaryBuilding(military_building_id) => {
                 write!(f, "MilitaryBuilding({military_building_id:?})")
             },
+            DemolishSelector::Waypoint(waypoint_id) => write!(f, "Waypoint({waypoint_id:?})"),
+            DemolishSelector::Bridge(bridge_id) => write!(f, "Bridge({bridge_id:?})"),
+            DemolishSelector::PowerLine(power_line_id) => {
+                write!(f, "PowerLine({power_line_id:?})")
+            },
+            DemolishSelector::Transport(transport_id) => {
+                write!(f, "Transport({transport_id:?})")
+            },
         }
     }
 }
@@ -70,19 +90,35 @@ impl Debug for DemolishSelector {
 #[derive(PartialEq, Clone, Debug)]
 pub enum InternalGameCommand {
     SpawnProjectile(ProjectileInfo, BuildCosts),
+    QueueShot(ProjectileSource, IndustryBuildingId),
     ProjectileLanded(ProjectileId),
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub enum GameCommand {
     BuildIndustryBuilding(IndustryBuildingInfo),
+    UpgradeIndustryBuilding(IndustryBuildingId),
+    SetPreferredSupplier(IndustryBuildingId),
     BuildStation(StationInfo),
+    ExpandStation(StationInfo),
     BuildTracks(Vec<TrackInfo>),
+    UpgradeTracks(Vec<TrackId>, TrackGrade),
     BuildMilitaryBuilding(MilitaryBuildingInfo),
+    BuildWaypoint(WaypointInfo),
+    BuildBridge(BridgeInfo),
+    BuildPowerLine(PowerLineInfo),
     PurchaseTransport(StationId, TransportInfo),
     UpdateTransportMovementOrders(TransportId, MovementOrders),
+    CreateTransportGroup(TransportGroupInfo),
+    AssignTransportToGroup(TransportId, Option<TransportGroupId>),
+    UpdateTransportGroupMovementOrders(TransportGroupId, MovementOrders),
+    SetTransportGroupForceStop(TransportGroupId, bool),
     Demolish(DemolishSelector),
     SetTimeFactor(TimeFactor),
+    RequestStatistics,
+    OfferTrade(TradeOffer),
+    AcceptTradeOffer(TradeOfferId, StationId),
+    CancelTradeOffer(TradeOfferId),
 
     // Later: This is only used for testing purposes, perhaps we can refactor to avoid this
     RequestGameStateSnapshot,
@@ -100,6 +136,12 @@ impl Debug for GameCommand {
                     building.industry_type()
                 )
             },
+            GameCommand::UpgradeIndustryBuilding(industry_building_id) => {
+                write!(f, "UpgradeIndustryBuilding({industry_building_id:?})")
+            },
+            GameCommand::SetPreferredSupplier(industry_building_id) => {
+                write!(f, "SetPreferredSupplier({industry_building_id:?})")
+            },
             GameCommand::BuildStation(station) => {
                 write!(
                     f,
@@ -109,18 +151,54 @@ impl Debug for GameCommand {
                     station.reference_tile()
                 )
             },
+            GameCommand::ExpandStation(station) => {
+                write!(
+                    f,
+                    "ExpandStation({:?}, {:?}, {:?})",
+                    station.id(),
+                    station.station_type(),
+                    station.reference_tile()
+                )
+            },
             GameCommand::BuildTracks(tracks) => {
                 write!(f, "BuildTracks({} tracks)", tracks.len())
             },
+            GameCommand::UpgradeTracks(track_ids, grade) => {
+                write!(f, "UpgradeTracks({} tracks, {grade:?})", track_ids.len())
+            },
             GameCommand::PurchaseTransport(station_id, transport) => {
                 write!(f, "PurchaseTransport({station_id:?}, {transport:?})")
             },
             GameCommand::BuildMilitaryBuilding(unit) => {
                 write!(f, "BuildMilitaryBuilding({})", unit.id())
             },
+            GameCommand::BuildWaypoint(waypoint) => {
+                write!(f, "BuildWaypoint({waypoint:?})")
+            },
+            GameCommand::BuildBridge(bridge) => {
+                write!(f, "BuildBridge({bridge:?})")
+            },
+            GameCommand::BuildPowerLine(power_line) => {
+                write!(f, "BuildPowerLine({power_line:?})")
+            },
             GameCommand::UpdateTransportMovementOrders(transport_id, _) => {
                 write!(f, "UpdateTransportMovementOrders({transport_id:?})",)
             },
+            GameCommand::CreateTransportGroup(group) => {
+                write!(f, "CreateTransportGroup({group:?})")
+            },
+            GameCommand::AssignTransportToGroup(transport_id, group_id) => {
+                write!(f, "AssignTransportToGroup({transport_id:?}, {group_id:?})")
+            },
+            GameCommand::UpdateTransportGroupMovementOrders(group_id, _) => {
+                write!(f, "UpdateTransportGroupMovementOrders({group_id:?})")
+            },
+            GameCommand::SetTransportGroupForceStop(group_id, force_stop) => {
+                write!(
+                    f,
+                    "SetTransportGroupForceStop({group_id:?}, {force_stop:?})"
+                )
+            },
             GameCommand::Demolish(selector) => {
                 write!(f, "Demolish({selector:?})")
             },
@@ -130,6 +208,18 @@ impl Debug for GameCommand {
             GameCommand::SetTimeFactor(time_factor) => {
                 write!(f, "SetTimeFactor({time_factor:?})")
             },
+            GameCommand::RequestStatistics => {
+                write!(f, "RequestStatistics")
+            },
+            GameCommand::OfferTrade(trade_offer) => {
+                write!(f, "OfferTrade({trade_offer:?})")
+            },
+            GameCommand::AcceptTradeOffer(trade_offer_id, station_id) => {
+                write!(f, "AcceptTradeOffer({trade_offer_id:?}, {station_id:?})")
+            },
+            GameCommand::CancelTradeOffer(trade_offer_id) => {
+                write!(f, "CancelTradeOffer({trade_offer_id:?})")
+            },
         }
     }
 }
@@ -139,6 +229,8 @@ pub enum NetworkCommand {
     Ping { id: Uuid, elapsed: Duration },
 }
 
+// Later: The game commands carrying whole `TransportInfo`s are much larger than the rest
+#[expect(clippy::large_enum_variant)]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub enum ClientCommand {
     Network(NetworkCommand),
@@ -6,34 +6,51 @@ use log::{debug, info, trace, warn};
 use serde::{Deserialize, Serialize, Serializer};
 use shared_util::bool_ops::BoolResultOps;
 
+use crate::building::bridge_info::BridgeInfo;
 use crate::building::building_info::{
     BuildingDynamicInfo, WithCostToBuild, WithOwner, WithTileCoverage,
 };
 use crate::building::building_state::{BuildingState, CanBuildResponse};
+use crate::building::construction_site::{ConstructionSite, ConstructionTarget};
 use crate::building::industry_building_info::IndustryBuildingInfo;
+use crate::building::industry_type::IndustryType;
 use crate::building::military_building_info::{MilitaryBuildingDynamicInfo, MilitaryBuildingInfo};
+use crate::building::power_line_info::PowerLineInfo;
 use crate::building::station_info::StationInfo;
 use crate::building::track_info::TrackInfo;
+use crate::building::waypoint_info::WaypointInfo;
 use crate::building::{BuildCosts, BuildError};
-use crate::client_command::InternalGameCommand;
+use crate::cargo_map::{CargoMap, WithCargo};
+use crate::client_command::{DemolishSelector, InternalGameCommand};
 use crate::game_time::{GameTime, GameTimeDiff, TimeFactor};
 use crate::map_level::map_level::{MapLevel, MapLevelFlattened};
-use crate::map_level::zoning::ZoningInfo;
-use crate::metrics::Metrics;
+use crate::map_level::zoning::{ZoningInfo, ZoningType};
+use crate::metrics::{Metrics, NoopMetrics};
 use crate::military::projectile_info::{ProjectileDynamicInfo, ProjectileInfo};
 use crate::military::projectile_state::ProjectileState;
+use crate::military::{ProjectileSource, ShellType};
+use crate::money::Money;
 use crate::players::player_state::PlayerState;
+use crate::players::trade_offer::{TradeError, TradeOffer};
 use crate::scenario::{PlayerProfile, Scenario};
-use crate::server_response::GameResponse;
+use crate::server_response::{GameError, GameResponse};
+use crate::statistics::StatisticsState;
 use crate::supply_chain::SupplyChain;
 use crate::tile_coords_xz::TileCoordsXZ;
+use crate::tile_coverage::TileCoverage;
 use crate::transport::movement_orders::MovementOrders;
+use crate::transport::progress_within_tile::ProgressWithinTile;
+use crate::transport::tile_occupancy::TileOccupancy;
+use crate::transport::tile_track::TileTrack;
+use crate::transport::track_grade::TrackGrade;
+use crate::transport::track_pathfinding::find_route_to;
 use crate::transport::track_type::TrackType;
+use crate::transport::transport_group::TransportGroupInfo;
 use crate::transport::transport_info::{TransportDynamicInfo, TransportInfo};
 use crate::transport::transport_state::TransportState;
 use crate::{
-    GameId, IndustryBuildingId, MilitaryBuildingId, PlayerId, ProjectileId, ScenarioId, StationId,
-    TrackId, TransportId,
+    BridgeId, GameId, IndustryBuildingId, MilitaryBuildingId, PlayerId, PowerLineId, ProjectileId,
+    ScenarioId, StationId, TrackId, TradeOfferId, TransportGroupId, TransportId, WaypointId,
 };
 
 // Later:   So this is used both on the server (to store authoritative game state), and on the client (to store the game state as known by the client).
@@ -48,6 +65,9 @@ pub struct GameState {
     projectiles: ProjectileState,
     players: PlayerState,
     supply_chain: SupplyChain,
+    // Sent to the players when they ask for it, and kept with the snapshots so that the history
+    // survives reloading the game
+    statistics: StatisticsState,
     time: GameTime,
     time_factor: TimeFactor,
     ignore_requesting_player_id: bool,
@@ -84,6 +104,7 @@ pub struct GameStateFlattened {
     transports:  TransportState,
     projectiles: ProjectileState,
     players:     PlayerState,
+    statistics:  StatisticsState,
     time:        GameTime,
     time_factor: TimeFactor,
 }
@@ -98,6 +119,7 @@ impl From<GameState> for GameStateFlattened {
             transports:  value.transports.clone(),
             projectiles: value.projectiles.clone(),
             players:     value.players.clone(),
+            statistics:  value.statistics.clone(),
             time:        value.time,
             time_factor: value.time_factor,
         }
@@ -115,6 +137,7 @@ impl From<GameStateFlattened> for GameState {
             projectiles: value.projectiles.clone(),
             players: value.players.clone(),
             supply_chain: SupplyChain::new(),
+            statistics: value.statistics,
             time: value.time,
             time_factor: value.time_factor,
             ignore_requesting_player_id: false,
@@ -146,6 +169,7 @@ impl GameState {
             projectiles: ProjectileState::empty(),
             players,
             supply_chain: SupplyChain::new(),
+            statistics: StatisticsState::default(),
             time: GameTime::new(),
             time_factor: TimeFactor::default(),
             ignore_requesting_player_id,
@@ -158,11 +182,30 @@ impl GameState {
                 player.initial_construction_yard,
                 &SupplyChain::new(),
             );
+            if let Some(tile) =
+                result.closest_free_industrial_tile(player.initial_construction_yard)
+            {
+                result
+                    .building_state_mut()
+                    .gift_initial_train_factory(player.player_id, tile);
+            } else {
+                warn!(
+                    "No free industrial zoning for the initial train factory of {:?}",
+                    player.player_id
+                );
+            }
         }
 
         result
     }
 
+    fn closest_free_industrial_tile(&self, tile: TileCoordsXZ) -> Option<TileCoordsXZ> {
+        self.all_free_zonings()
+            .filter(|zoning| zoning.zoning_type() == ZoningType::Industrial)
+            .map(ZoningInfo::reference_tile)
+            .min_by_key(|reference_tile| reference_tile.manhattan_distance(tile))
+    }
+
     pub fn set_time_factor(&mut self, time_factor: TimeFactor) {
         self.time_factor = time_factor;
     }
@@ -195,14 +238,26 @@ impl GameState {
         costs: &BuildCosts,
     ) -> Result<GameResponse, BuildError> {
         self.can_pay_costs(projectile.owner_id(), costs)?;
-        let building = self
-            .buildings
-            .find_military_building_mut(projectile.fired_from())
-            .ok_or(BuildError::UnknownError)?;
-
-        building.update_projectile_fired(projectile);
+        match projectile.fired_from() {
+            ProjectileSource::MilitaryBuilding(military_building_id) => {
+                self.buildings
+                    .find_military_building_mut(military_building_id)
+                    .ok_or(BuildError::UnknownError)?
+                    .update_projectile_fired(projectile);
+            },
+            ProjectileSource::MilitaryUnit(transport_id) => {
+                self.transports
+                    .transport_mut(transport_id)
+                    .and_then(|transport| transport.dynamic_info.military_unit.as_mut())
+                    .ok_or(BuildError::UnknownError)?
+                    .update_projectile_fired(projectile);
+            },
+        }
+        self.buildings.dequeue_shot(projectile.fired_from());
         self.upsert_projectile(projectile.clone());
         self.pay_costs(costs);
+        self.statistics
+            .record_projectile_fired(projectile.owner_id());
         Ok(GameResponse::ProjectilesAdded(vec![projectile.clone()]))
     }
 
@@ -221,9 +276,18 @@ impl GameState {
                     },
                 }
             },
+            InternalGameCommand::QueueShot(source, industry_building_id) => {
+                self.buildings.queue_shot(*source, *industry_building_id);
+                vec![]
+            },
             InternalGameCommand::ProjectileLanded(projectile_id) => {
-                // TODO HIGH: Spawn explosion, do damage, etc.
+                // TODO HIGH: Spawn explosion, do damage to finished buildings, etc.
                 info!("Projectile {projectile_id:?} landed");
+                if let Some(projectile) = self.projectiles.find_projectile(*projectile_id) {
+                    let (owner_id, landing_on) = (projectile.owner_id(), projectile.landing_on());
+                    self.buildings
+                        .hit_construction_site_at(landing_on, owner_id);
+                }
                 self.remove_projectile(*projectile_id);
                 vec![GameResponse::ProjectilesRemoved(vec![*projectile_id])]
             },
@@ -243,7 +307,31 @@ impl GameState {
         let projectile_commands =
             self.projectiles
                 .generate_commands(previous_game_time, diff, new_game_time);
-        [building_commands, projectile_commands].concat()
+        let military_unit_commands =
+            self.transports
+                .generate_commands(previous_game_time, new_game_time, self);
+        [
+            building_commands,
+            military_unit_commands,
+            projectile_commands,
+        ]
+        .concat()
+    }
+
+    // Charged once per whole game second, as going through all the tracks every tick is too slow
+    fn charge_running_costs(&mut self, previous_game_time: GameTime, new_game_time: GameTime) {
+        let seconds = new_game_time.to_seconds().floor() - previous_game_time.to_seconds().floor();
+        if seconds > 0.0 {
+            let mut costs = self.buildings.track_running_costs_per_second();
+            for (player_id, cost) in self.transports.running_costs_per_second() {
+                *costs.entry(player_id).or_default() += cost;
+            }
+            for (player_id, cost) in costs {
+                self.players.spend(player_id, cost * f64::from(seconds));
+            }
+            self.transports
+                .supply_military_units(&mut self.buildings, seconds);
+        }
     }
 
     #[must_use]
@@ -256,11 +344,22 @@ impl GameState {
         if diff > GameTimeDiff::ZERO {
             let previous_game_time = self.time;
             let new_game_time = previous_game_time + diff;
-            self.buildings
-                .advance_time_diff(previous_game_time, diff, new_game_time);
-            self.transports
+            self.buildings.advance_time_diff(
+                previous_game_time,
+                diff,
+                new_game_time,
+                &mut self.statistics,
+            );
+            let deliveries = self
+                .transports
                 .advance_time_diff(diff, &mut self.buildings, metrics);
+            for (player_id, delivery) in deliveries {
+                self.players.earn(player_id, delivery.income);
+                self.statistics.record_delivered(player_id, delivery.cargo);
+            }
             self.projectiles.advance_time_diff(diff);
+            self.charge_running_costs(previous_game_time, new_game_time);
+            self.statistics.advance_time(new_game_time, &self.players);
             self.time = new_game_time;
             self.generate_commands(previous_game_time, diff, new_game_time)
         } else {
@@ -308,6 +407,11 @@ impl GameState {
         &self.players
     }
 
+    #[must_use]
+    pub fn statistics(&self) -> &StatisticsState {
+        &self.statistics
+    }
+
     pub fn upsert_projectile(&mut self, projectile: ProjectileInfo) {
         self.projectiles.upsert(projectile);
     }
@@ -320,6 +424,13 @@ impl GameState {
         self.transports.upsert(transport);
     }
 
+    pub fn remove_transport(&mut self, transport_id: TransportId) -> Result<(), ()> {
+        self.transports.remove(transport_id)?;
+        self.buildings
+            .dequeue_shot(ProjectileSource::MilitaryUnit(transport_id));
+        Ok(())
+    }
+
     pub fn update_transport_movement_orders(
         &mut self,
         transport_id: TransportId,
@@ -336,18 +447,215 @@ impl GameState {
     ) -> Result<Vec<TrackInfo>, BuildError> {
         let (filtered, costs) = self.can_build_tracks(requesting_player_id, tracks)?;
         self.buildings.build_tracks(filtered.clone(), &costs);
+        self.players.pay_costs(&costs);
+        for track in &filtered {
+            self.statistics.record_building_built(track.owner_id());
+        }
         Ok(filtered)
     }
 
+    // Upgrading replaces the existing tracks in place, so only the difference in cost between the
+    // grades is paid. Tracks that are already of this grade or better are skipped.
+    pub fn can_upgrade_tracks(
+        &self,
+        requesting_player_id: PlayerId,
+        track_ids: &[TrackId],
+        grade: TrackGrade,
+    ) -> Result<(Vec<TrackInfo>, BuildCosts), BuildError> {
+        let mut results = vec![];
+        let mut costs = BuildCosts::none();
+        let mut player_ids = HashSet::new();
+        for track_id in track_ids {
+            let tracks = self.buildings.tracks_at(track_id.tile);
+            let owner_id = tracks.owner_id().ok_or(BuildError::UnknownError)?;
+            let existing_grade = tracks
+                .grade(track_id.track_type)
+                .ok_or(BuildError::UnknownError)?;
+            self.valid_owner(requesting_player_id, owner_id)?;
+
+            if existing_grade < grade {
+                let existing = TrackInfo::new(owner_id, track_id.tile, track_id.track_type)
+                    .with_grade(existing_grade);
+                let upgraded = existing.clone().with_grade(grade);
+                let (industry_type, upgraded_cost) = upgraded.cost_to_build();
+                let (_, existing_cost) = existing.cost_to_build();
+                costs += self.can_pay_known_cost(
+                    owner_id,
+                    &upgraded,
+                    industry_type,
+                    upgraded_cost - existing_cost,
+                )?;
+                player_ids.insert(owner_id);
+                results.push(upgraded);
+            }
+        }
+
+        (player_ids.len() <= 1).then_ok_unit(|| BuildError::InvalidOwner)?;
+        if let Some(player_id) = player_ids.iter().next() {
+            self.can_pay_costs(*player_id, &costs)?;
+        }
+        Ok((results, costs))
+    }
+
+    pub fn upgrade_tracks(
+        &mut self,
+        requesting_player_id: PlayerId,
+        track_ids: &[TrackId],
+        grade: TrackGrade,
+    ) -> Result<Vec<TrackInfo>, BuildError> {
+        let (upgraded, costs) = self.can_upgrade_tracks(requesting_player_id, track_ids, grade)?;
+        self.buildings.upgrade_tracks(&upgraded, &costs);
+        self.players.pay_costs(&costs);
+        Ok(upgraded)
+    }
+
+    #[expect(clippy::missing_errors_doc)]
+    pub fn can_upgrade_industry_building(
+        &self,
+        requesting_player_id: PlayerId,
+        industry_building_id: IndustryBuildingId,
+    ) -> Result<BuildCosts, BuildError> {
+        let building = self
+            .buildings
+            .find_industry_building(industry_building_id)
+            .ok_or(BuildError::UnknownError)?;
+        self.valid_owner(requesting_player_id, building.owner_id())?;
+        (!building.is_under_construction()).then_ok_unit(|| BuildError::UnderConstruction)?;
+        building
+            .can_be_upgraded()
+            .then_ok_unit(|| BuildError::AlreadyAtMaxLevel)?;
+
+        let (providing_industry_type, cost) = building.upgrade_cost();
+        self.can_pay_known_cost(building.owner_id(), building, providing_industry_type, cost)
+    }
+
+    pub fn upgrade_industry_building(
+        &mut self,
+        requesting_player_id: PlayerId,
+        industry_building_id: IndustryBuildingId,
+    ) -> Result<IndustryBuildingInfo, BuildError> {
+        let costs =
+            self.can_upgrade_industry_building(requesting_player_id, industry_building_id)?;
+        let upgraded = self
+            .buildings
+            .upgrade_industry_building(industry_building_id, &costs)?;
+        self.players.pay_costs(&costs);
+        Ok(upgraded)
+    }
+
+    /// Makes this supply building pay for the player's construction whenever it is in range.
+    pub fn set_preferred_supplier(
+        &mut self,
+        requesting_player_id: PlayerId,
+        industry_building_id: IndustryBuildingId,
+    ) -> Result<(), BuildError> {
+        let building = self
+            .buildings
+            .find_industry_building(industry_building_id)
+            .ok_or(BuildError::UnknownError)?;
+        self.valid_owner(requesting_player_id, building.owner_id())?;
+        self.buildings.set_preferred_supplier(industry_building_id)
+    }
+
+    pub fn can_offer_trade(
+        &self,
+        requesting_player_id: PlayerId,
+        trade_offer: &TradeOffer,
+    ) -> Result<(), TradeError> {
+        trade_offer.validate()?;
+        self.valid_trader(requesting_player_id, trade_offer.from_player_id())?;
+        if self.players.get(trade_offer.to_player_id()).is_none() {
+            return Err(TradeError::InvalidCounterparty);
+        }
+        if self.players.find_trade_offer(trade_offer.id()).is_some() {
+            return Err(TradeError::DuplicateOffer);
+        }
+        let station = self
+            .buildings
+            .find_station(trade_offer.from_station_id())
+            .ok_or(TradeError::UnknownStation)?;
+        if station.owner_id() != trade_offer.from_player_id() {
+            return Err(TradeError::InvalidOwner);
+        }
+        // Checked again when the offer is accepted, as the cargo may be gone by then
+        if !station.cargo().is_superset_of(trade_offer.offered()) {
+            return Err(TradeError::NotEnoughCargo);
+        }
+        Ok(())
+    }
+
+    pub fn offer_trade(
+        &mut self,
+        requesting_player_id: PlayerId,
+        trade_offer: &TradeOffer,
+    ) -> Result<(), TradeError> {
+        self.can_offer_trade(requesting_player_id, trade_offer)?;
+        self.upsert_trade_offer(trade_offer.clone());
+        Ok(())
+    }
+
+    /// Exchanges the cargo between the offering player's station and `station_id` of the accepting
+    /// player.
+    pub fn accept_trade(
+        &mut self,
+        requesting_player_id: PlayerId,
+        trade_offer_id: TradeOfferId,
+        station_id: StationId,
+    ) -> Result<TradeOffer, TradeError> {
+        let trade_offer = self
+            .players
+            .find_trade_offer(trade_offer_id)
+            .ok_or(TradeError::UnknownOffer)?
+            .clone();
+        self.valid_trader(requesting_player_id, trade_offer.to_player_id())?;
+        self.buildings
+            .exchange_traded_cargo(&trade_offer, station_id)?;
+        self.remove_trade_offer(trade_offer_id);
+        Ok(trade_offer)
+    }
+
+    /// Either side can call off the trade, the offering player withdrawing it, or the other player
+    /// declining it.
+    pub fn cancel_trade_offer(
+        &mut self,
+        requesting_player_id: PlayerId,
+        trade_offer_id: TradeOfferId,
+    ) -> Result<(), TradeError> {
+        let trade_offer = self
+            .players
+            .find_trade_offer(trade_offer_id)
+            .ok_or(TradeError::UnknownOffer)?;
+        if !self.ignore_requesting_player_id && !trade_offer.involves(requesting_player_id) {
+            return Err(TradeError::InvalidOwner);
+        }
+        self.remove_trade_offer(trade_offer_id);
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn trade_offers_from_station(&self, station_id: StationId) -> Vec<TradeOfferId> {
+        self.players
+            .trade_offers()
+            .into_iter()
+            .filter(|trade_offer| trade_offer.from_station_id() == station_id)
+            .map(TradeOffer::id)
+            .collect()
+    }
+
+    pub fn upsert_trade_offer(&mut self, trade_offer: TradeOffer) {
+        self.players.upsert_trade_offer(trade_offer);
+    }
+
+    pub fn remove_trade_offer(&mut self, trade_offer_id: TradeOfferId) {
+        let _ = self.players.remove_trade_offer(trade_offer_id);
+    }
+
     pub fn can_purchase_transport(
         &self,
         requesting_player_id: PlayerId,
         station_id: StationId,
         transport_info: &TransportInfo,
     ) -> Result<BuildCosts, BuildError> {
-        // TODO: Check if the track / road / etc. is free and owned by the purchaser
-        // TODO: Check if the transport is on a station
-
         self.valid_owner(requesting_player_id, transport_info.owner_id())?;
 
         let (source_industry, cargo_map) = transport_info.cost_to_build();
@@ -355,16 +663,57 @@ impl GameState {
             .building_state()
             .find_station(station_id)
             .ok_or(BuildError::UnknownError)?;
-        let costs = self.building_state().can_pay_known_cost(
+        (station.owner_id() == transport_info.owner_id())
+            .then_ok_unit(|| BuildError::InvalidOwner)?;
+        (!self
+            .buildings
+            .is_under_construction(ConstructionTarget::Station(station_id)))
+        .then_ok_unit(|| BuildError::UnderConstruction)?;
+        self.valid_initial_transport_location(station, transport_info)?;
+
+        let costs = self.buildings.can_pay_from_linked_industry(
             transport_info.owner_id(),
-            station,
+            station_id,
             source_industry,
             cargo_map,
         )?;
+        self.can_afford(&costs)?;
 
         Ok(costs)
     }
 
+    // New transports start at the exit of one of the station's platforms, covering the whole
+    // platform, and that platform has to be free of other transports
+    #[expect(clippy::cast_precision_loss)]
+    fn valid_initial_transport_location(
+        &self,
+        station: &StationInfo,
+        transport_info: &TransportInfo,
+    ) -> Result<(), BuildError> {
+        // The transport length is a sum of `f32`-s, so we allow for rounding errors
+        const LENGTH_TOLERANCE: f32 = 0.001;
+
+        let tile_path = &transport_info.location().tile_path;
+        let head = tile_path
+            .first()
+            .ok_or(BuildError::TransportNotOnPlatform)?;
+        let platform_location = station
+            .transport_location_at_station(head.tile, head.pointing_in)
+            .ok_or(BuildError::TransportNotOnPlatform)?;
+        (platform_location.tile_path == *tile_path)
+            .then_ok_unit(|| BuildError::TransportNotOnPlatform)?;
+
+        let platform_length = station.station_type().length_in_tiles as f32;
+        (transport_info.transport_type().length_in_tiles() <= platform_length + LENGTH_TOLERANCE)
+            .then_ok_unit(|| BuildError::TransportTooLongForPlatform)?;
+
+        let occupancy = TileOccupancy::new(self.transports.all_transports());
+        (!tile_path
+            .iter()
+            .any(|tile_track| occupancy.is_occupied(tile_track.tile)))
+        .then_ok_unit(|| BuildError::PlatformOccupied)
+    }
+
     pub fn purchase_transport(
         &mut self,
         requesting_player_id: PlayerId,
@@ -380,8 +729,118 @@ impl GameState {
         Ok(())
     }
 
+    // The station where the transport is parked, having arrived at the exit of one of its platforms
+    #[must_use]
+    pub fn station_where_transport_is_stopped(
+        &self,
+        transport_id: TransportId,
+    ) -> Option<&StationInfo> {
+        let location = self.transports.info_by_id(transport_id)?.location();
+        let current = location.next_tile_in_path();
+        let station = self.buildings.station_at(current.tile)?;
+        let stopped = location.progress_within_tile() == ProgressWithinTile::about_to_exit()
+            && station.station_exit_tile_tracks().contains(&current);
+        stopped.then_some(station)
+    }
+
+    // Transports can only be sold when stopped at a station, and part of their cost is refunded to
+    // the industry linked to the station that provides it, so the sale fails if there is no such
+    // industry or it has no room for the refund
+    pub fn attempt_to_remove_transport(
+        &mut self,
+        requesting_player_id: PlayerId,
+        transport_id: TransportId,
+    ) -> Result<(), ()> {
+        const REFUND_SHARE: f32 = 0.5;
+
+        let transport = self.transports.info_by_id(transport_id).ok_or(())?;
+        if transport.owner_id() != requesting_player_id {
+            return Err(());
+        }
+        let (providing_industry_type, cost) = transport.cost_to_build();
+        let station_id = self
+            .station_where_transport_is_stopped(transport_id)
+            .ok_or(())?
+            .id();
+
+        self.buildings.refund_to_linked_industry(
+            station_id,
+            providing_industry_type,
+            &(cost * REFUND_SHARE),
+        )?;
+        self.transports.remove(transport_id)
+    }
+
+    pub fn upsert_transport_group(&mut self, group: TransportGroupInfo) {
+        self.transports.upsert_group(group);
+    }
+
+    pub fn create_transport_group(
+        &mut self,
+        requesting_player_id: PlayerId,
+        group: &TransportGroupInfo,
+    ) -> Result<(), ()> {
+        self.valid_owner(requesting_player_id, group.owner_id())
+            .map_err(|_| ())?;
+        self.transports
+            .group_by_id(group.group_id())
+            .is_none()
+            .then_ok_unit(|| ())?;
+        self.transports.upsert_group(group.clone());
+        Ok(())
+    }
+
+    fn valid_group_owner(
+        &self,
+        requesting_player_id: PlayerId,
+        group_id: TransportGroupId,
+    ) -> Result<(), ()> {
+        let group = self.transports.group_by_id(group_id).ok_or(())?;
+        self.valid_owner(requesting_player_id, group.owner_id())
+            .map_err(|_| ())
+    }
+
+    // `None` removes the transport from its group, keeping its current movement orders
+    pub fn assign_transport_to_group(
+        &mut self,
+        requesting_player_id: PlayerId,
+        transport_id: TransportId,
+        group_id: Option<TransportGroupId>,
+    ) -> Result<(), ()> {
+        let transport = self.transports.info_by_id(transport_id).ok_or(())?;
+        self.valid_owner(requesting_player_id, transport.owner_id())
+            .map_err(|_| ())?;
+        if let Some(group_id) = group_id {
+            let group = self.transports.group_by_id(group_id).ok_or(())?;
+            (group.owner_id() == transport.owner_id()).then_ok_unit(|| ())?;
+        }
+        self.transports.assign_to_group(transport_id, group_id)
+    }
+
+    pub fn update_transport_group_movement_orders(
+        &mut self,
+        requesting_player_id: PlayerId,
+        group_id: TransportGroupId,
+        movement_orders: &MovementOrders,
+    ) -> Result<(), ()> {
+        self.valid_group_owner(requesting_player_id, group_id)?;
+        self.transports
+            .update_group_movement_orders(group_id, movement_orders)
+    }
+
+    pub fn set_transport_group_force_stop(
+        &mut self,
+        requesting_player_id: PlayerId,
+        group_id: TransportGroupId,
+        force_stop: bool,
+    ) -> Result<(), ()> {
+        self.valid_group_owner(requesting_player_id, group_id)?;
+        self.transports.set_group_force_stop(group_id, force_stop)
+    }
+
     pub fn pay_costs(&mut self, costs: &BuildCosts) {
         self.buildings.pay_costs(costs);
+        self.players.pay_costs(costs);
     }
 
     #[expect(clippy::missing_panics_doc, clippy::unwrap_used)]
@@ -438,6 +897,11 @@ impl GameState {
         tile: TileCoordsXZ,
         track_type: TrackType,
     ) -> CanBuildResponse {
+        // Bridges span terrain where regular tracks could not be built, so we skip those checks
+        if self.buildings.bridge_at(tile).is_some() {
+            return self.buildings.can_build_track(owner_id, tile, track_type);
+        }
+
         match self.map_level.can_build_track(tile, track_type) {
             Ok(()) => self.buildings.can_build_track(owner_id, tile, track_type),
             Err(err) => CanBuildResponse::Invalid(err),
@@ -470,13 +934,25 @@ impl GameState {
         self.can_pay_cost(building.owner_id(), building)
     }
 
+    /// Returns the building as built, with the deposit of its zoning if it is a mine.
     pub fn build_industry_building(
         &mut self,
         requesting_player_id: PlayerId,
         building: &IndustryBuildingInfo,
-    ) -> Result<(), BuildError> {
+    ) -> Result<IndustryBuildingInfo, BuildError> {
         let costs = self.can_build_industry_building(requesting_player_id, building)?;
-        self.buildings.build_industry_building(building, &costs)
+        let mut building = building.clone();
+        building.set_deposit(
+            self.map_level
+                .zoning()
+                .zoning_at_reference_tile(building.reference_tile())
+                .and_then(ZoningInfo::deposit),
+        );
+        building.start_construction();
+        self.buildings.build_industry_building(&building, &costs)?;
+        self.players.pay_costs(&costs);
+        self.statistics.record_building_built(building.owner_id());
+        Ok(building)
     }
 
     pub fn build_military_building(
@@ -485,7 +961,10 @@ impl GameState {
         building: &MilitaryBuildingInfo,
     ) -> Result<(), BuildError> {
         let costs = self.can_build_military_building(requesting_player_id, building)?;
-        self.buildings.build_military_building(building, &costs)
+        self.buildings.build_military_building(building, &costs)?;
+        self.players.pay_costs(&costs);
+        self.statistics.record_building_built(building.owner_id());
+        Ok(())
     }
 
     #[expect(clippy::missing_errors_doc)]
@@ -500,16 +979,230 @@ impl GameState {
         self.can_pay_cost(station.owner_id(), station)
     }
 
+    #[expect(clippy::missing_errors_doc)]
+    pub fn can_expand_station(
+        &self,
+        requesting_player_id: PlayerId,
+        expanded: &StationInfo,
+    ) -> Result<BuildCosts, BuildError> {
+        self.valid_owner(requesting_player_id, expanded.owner_id())?;
+        self.map_level.can_build_station(expanded)?;
+        self.buildings.can_expand_station(expanded)?;
+        let existing = self
+            .buildings
+            .find_station(expanded.id())
+            .ok_or(BuildError::UnknownError)?;
+        // We only pay for the part that is being added
+        let (providing_industry_type, expanded_cost) = expanded.cost_to_build();
+        let (_, existing_cost) = existing.cost_to_build();
+        self.can_pay_known_cost(
+            expanded.owner_id(),
+            expanded,
+            providing_industry_type,
+            expanded_cost - existing_cost,
+        )
+    }
+
+    pub fn expand_station(
+        &mut self,
+        requesting_player_id: PlayerId,
+        expanded: &StationInfo,
+    ) -> Result<(), BuildError> {
+        let costs = self.can_expand_station(requesting_player_id, expanded)?;
+        self.buildings.expand_station(expanded, &costs)?;
+        self.players.pay_costs(&costs);
+        Ok(())
+    }
+
+    #[expect(clippy::missing_errors_doc)]
+    pub fn can_build_waypoint(
+        &self,
+        requesting_player_id: PlayerId,
+        waypoint: &WaypointInfo,
+    ) -> Result<BuildCosts, BuildError> {
+        self.valid_owner(requesting_player_id, waypoint.owner_id())?;
+        self.buildings.can_build_waypoint(waypoint)?;
+        self.can_pay_cost(waypoint.owner_id(), waypoint)
+    }
+
+    pub fn build_waypoint(
+        &mut self,
+        requesting_player_id: PlayerId,
+        waypoint: &WaypointInfo,
+    ) -> Result<(), BuildError> {
+        let costs = self.can_build_waypoint(requesting_player_id, waypoint)?;
+        self.buildings.build_waypoint(waypoint, &costs)?;
+        self.players.pay_costs(&costs);
+        self.statistics.record_building_built(waypoint.owner_id());
+        Ok(())
+    }
+
+    #[expect(clippy::missing_errors_doc)]
+    pub fn can_build_power_line(
+        &self,
+        requesting_player_id: PlayerId,
+        power_line: &PowerLineInfo,
+    ) -> Result<BuildCosts, BuildError> {
+        self.valid_owner(requesting_player_id, power_line.owner_id())?;
+        self.map_level.can_build_power_line(power_line)?;
+        self.buildings.can_build_power_line(power_line)?;
+        self.can_pay_cost(power_line.owner_id(), power_line)
+    }
+
+    pub fn build_power_line(
+        &mut self,
+        requesting_player_id: PlayerId,
+        power_line: &PowerLineInfo,
+    ) -> Result<(), BuildError> {
+        let costs = self.can_build_power_line(requesting_player_id, power_line)?;
+        self.buildings.build_power_line(power_line, &costs)?;
+        self.players.pay_costs(&costs);
+        self.statistics.record_building_built(power_line.owner_id());
+        Ok(())
+    }
+
+    // Used when planning, so we are not checking ownership or whether we can pay for it
+    pub(crate) fn can_build_bridge_internal(&self, bridge: &BridgeInfo) -> Result<(), BuildError> {
+        self.map_level.can_build_bridge(bridge)?;
+        self.buildings.can_build_bridge(bridge)
+    }
+
+    #[expect(clippy::missing_errors_doc)]
+    pub fn can_build_bridge(
+        &self,
+        requesting_player_id: PlayerId,
+        bridge: &BridgeInfo,
+    ) -> Result<BuildCosts, BuildError> {
+        self.valid_owner(requesting_player_id, bridge.owner_id())?;
+        self.can_build_bridge_internal(bridge)?;
+        self.can_pay_cost(bridge.owner_id(), bridge)
+    }
+
+    pub fn build_bridge(
+        &mut self,
+        requesting_player_id: PlayerId,
+        bridge: &BridgeInfo,
+    ) -> Result<(), BuildError> {
+        let costs = self.can_build_bridge(requesting_player_id, bridge)?;
+        self.buildings.build_bridge(bridge, &costs)?;
+        self.players.pay_costs(&costs);
+        self.statistics.record_building_built(bridge.owner_id());
+        Ok(())
+    }
+
     fn can_pay_cost<T: WithCostToBuild + WithTileCoverage>(
         &self,
         player_id: PlayerId,
         something: &T,
     ) -> Result<BuildCosts, BuildError> {
-        self.buildings.can_pay_cost(player_id, something)
+        let costs = self.buildings.can_pay_cost(player_id, something)?;
+        self.can_afford(&costs)?;
+        Ok(costs)
+    }
+
+    pub(crate) fn can_pay_known_cost<T: WithTileCoverage>(
+        &self,
+        player_id: PlayerId,
+        something: &T,
+        providing_industry_type: IndustryType,
+        cost: CargoMap,
+    ) -> Result<BuildCosts, BuildError> {
+        let costs = self.buildings.can_pay_known_cost(
+            player_id,
+            something,
+            providing_industry_type,
+            cost,
+        )?;
+        self.can_afford(&costs)?;
+        Ok(costs)
+    }
+
+    // The artillery that started waiting for the base earlier fires first once it is restocked
+    pub(crate) fn can_pay_for_shot<T: WithTileCoverage>(
+        &self,
+        player_id: PlayerId,
+        something: &T,
+        shell_type: ShellType,
+        source: ProjectileSource,
+    ) -> Result<BuildCosts, BuildError> {
+        let costs = self
+            .buildings
+            .can_pay_for_shot(player_id, something, shell_type)?;
+        let waiting = self
+            .buildings
+            .ammunition_supplier(player_id, something)
+            .and_then(|industry_building_id| self.next_pending_shot(industry_building_id));
+        if waiting.is_some_and(|waiting| waiting != source) {
+            return Err(BuildError::NotEnoughResources);
+        }
+        self.can_afford(&costs)?;
+        Ok(costs)
+    }
+
+    // A shot that could not be paid for waits in the queue of the supplying base
+    #[must_use]
+    pub(crate) fn queue_shot_command<T: WithTileCoverage>(
+        &self,
+        player_id: PlayerId,
+        something: &T,
+        source: ProjectileSource,
+    ) -> Option<InternalGameCommand> {
+        self.buildings
+            .ammunition_supplier(player_id, something)
+            .filter(|industry_building_id| {
+                !self
+                    .buildings
+                    .pending_shots(*industry_building_id)
+                    .any(|queued| queued == source)
+            })
+            .map(|industry_building_id| {
+                InternalGameCommand::QueueShot(source, industry_building_id)
+            })
+    }
+
+    // Skips the artillery that is no longer waiting for this base, e.g. as it has moved elsewhere
+    #[must_use]
+    pub fn next_pending_shot(
+        &self,
+        industry_building_id: IndustryBuildingId,
+    ) -> Option<ProjectileSource> {
+        self.buildings
+            .pending_shots(industry_building_id)
+            .find(|source| self.shot_supplier(*source) == Some(industry_building_id))
+    }
+
+    #[must_use]
+    fn shot_supplier(&self, source: ProjectileSource) -> Option<IndustryBuildingId> {
+        match source {
+            ProjectileSource::MilitaryBuilding(military_building_id) => {
+                let building = self
+                    .buildings
+                    .find_military_building(military_building_id)?;
+                self.buildings
+                    .ammunition_supplier(building.owner_id(), building)
+            },
+            ProjectileSource::MilitaryUnit(transport_id) => {
+                let transport = self.get_transport_info(transport_id)?;
+                let military_unit = transport.dynamic_info.military_unit.as_ref()?;
+                if !military_unit.is_deployed() {
+                    return None;
+                }
+                let tile = transport.location().next_tile_in_path().tile;
+                self.buildings
+                    .ammunition_supplier(transport.owner_id(), &TileCoverage::Single(tile))
+            },
+        }
     }
 
     fn can_pay_costs(&self, player_id: PlayerId, costs: &BuildCosts) -> Result<(), BuildError> {
-        self.buildings.can_pay_costs(player_id, costs)
+        self.buildings.can_pay_costs(player_id, costs)?;
+        self.can_afford(costs)
+    }
+
+    fn can_afford(&self, costs: &BuildCosts) -> Result<(), BuildError> {
+        self.players
+            .can_pay_costs(costs)
+            .then_ok_unit(|| BuildError::NotEnoughMoney)
     }
 
     pub fn build_station(
@@ -518,17 +1211,84 @@ impl GameState {
         station: &StationInfo,
     ) -> Result<(), BuildError> {
         let costs = self.can_build_station(requesting_player_id, station)?;
-        self.buildings.build_station(station, &costs)
+        self.buildings.build_station(station, &costs)?;
+        self.players.pay_costs(&costs);
+        self.statistics.record_building_built(station.owner_id());
+        Ok(())
     }
 
+    // Fails with the transports that are in the way if the tracks are occupied
     pub fn remove_tracks(
         &mut self,
         requesting_player_id: PlayerId,
         track_ids: &[TrackId],
-    ) -> Result<(), ()> {
-        // TODO: Check there are no trains on (or near?) these tracks
+    ) -> Result<(), GameError> {
+        let blocking_transports = self.transports_blocking_track_removal(track_ids);
+        if !blocking_transports.is_empty() {
+            return Err(GameError::CannotDemolishOccupiedTracks(
+                track_ids.to_vec(),
+                blocking_transports,
+            ));
+        }
         self.buildings
             .attempt_to_remove_tracks(requesting_player_id, track_ids)
+            .map_err(|()| GameError::CannotDemolish(DemolishSelector::Tracks(track_ids.to_vec())))
+    }
+
+    // Transports that are on these tracks, or have them anywhere on the route that they are taking,
+    // as removing the tracks would leave them stranded
+    #[must_use]
+    pub fn transports_blocking_track_removal(&self, track_ids: &[TrackId]) -> Vec<TransportId> {
+        let is_removed = |tile_track: &TileTrack| {
+            track_ids.iter().any(|track_id| {
+                track_id.tile == tile_track.tile && track_id.track_type == tile_track.track_type
+            })
+        };
+        let orphaned_waypoints = self.waypoints_orphaned_by_track_removal(track_ids);
+
+        let transports = self.transports.all_transports();
+        let occupancy = TileOccupancy::new(transports);
+        transports
+            .iter()
+            .filter(|transport| {
+                let location = transport.location();
+                let route = find_route_to(
+                    location.next_tile_in_path(),
+                    transport.movement_orders().current_order().go_to,
+                    &self.buildings,
+                    occupancy.occupied_by_others(transport),
+                    &NoopMetrics::default(),
+                )
+                .unwrap_or_default();
+                location
+                    .tile_path
+                    .iter()
+                    .chain(route.iter())
+                    .any(is_removed)
+                    || orphaned_waypoints.iter().any(|waypoint_id| {
+                        transport.movement_orders().contains_waypoint(*waypoint_id)
+                    })
+            })
+            .map(TransportInfo::transport_id)
+            .collect()
+    }
+
+    // Waypoints are removed together with the last of their owner's tracks on their tile
+    fn waypoints_orphaned_by_track_removal(&self, track_ids: &[TrackId]) -> Vec<WaypointId> {
+        track_ids
+            .iter()
+            .filter_map(|track_id| self.buildings.waypoint_at(track_id.tile))
+            .filter(|waypoint| {
+                let mut remaining = self.buildings.tracks_at(waypoint.tile()).track_types();
+                for track_id in track_ids {
+                    if track_id.tile == waypoint.tile() {
+                        remaining.remove(track_id.track_type);
+                    }
+                }
+                remaining.is_empty()
+            })
+            .map(WaypointInfo::id)
+            .collect()
     }
 
     pub fn remove_industry_building(
@@ -536,17 +1296,88 @@ impl GameState {
         requesting_player_id: PlayerId,
         industry_building_id: IndustryBuildingId,
     ) -> Result<(), ()> {
+        let building = self
+            .buildings
+            .find_industry_building(industry_building_id)
+            .cloned();
         self.buildings
-            .attempt_to_remove_industry_building(requesting_player_id, industry_building_id)
+            .attempt_to_remove_industry_building(requesting_player_id, industry_building_id)?;
+
+        // What is left of the deposit remains for the next mine built on this zoning
+        if let Some(building) = building {
+            if let Some(deposit) = building.deposit() {
+                let zoning = self.map_level.zoning_mut();
+                if let Some(zoning_id) = zoning
+                    .zoning_at_reference_tile(building.reference_tile())
+                    .map(ZoningInfo::id)
+                {
+                    zoning.update_deposit(zoning_id, deposit);
+                }
+            }
+        }
+
+        Ok(())
     }
 
+    /// Also cancels the trade offers of the cargo at the station, as there is nothing left to
+    /// exchange.
     pub fn remove_station(
         &mut self,
         requesting_player_id: PlayerId,
         station_id: StationId,
     ) -> Result<(), ()> {
         self.buildings
-            .attempt_to_remove_station(requesting_player_id, station_id)
+            .attempt_to_remove_station(requesting_player_id, station_id)?;
+        for trade_offer_id in self.trade_offers_from_station(station_id) {
+            self.remove_trade_offer(trade_offer_id);
+        }
+        Ok(())
+    }
+
+    pub fn remove_bridge(
+        &mut self,
+        requesting_player_id: PlayerId,
+        bridge_id: BridgeId,
+    ) -> Result<(), ()> {
+        // The bridge is occupied just like the tracks on its tiles would be
+        let bridge = self.buildings.find_bridge(bridge_id).ok_or(())?;
+        let track_ids = bridge
+            .tiles()
+            .into_iter()
+            .map(|tile| TrackId::new(tile, bridge.track_type()))
+            .collect::<Vec<_>>();
+        self.transports_blocking_track_removal(&track_ids)
+            .is_empty()
+            .then_ok_unit(|| ())?;
+        self.buildings
+            .attempt_to_remove_bridge(requesting_player_id, bridge_id)
+    }
+
+    pub fn remove_power_line(
+        &mut self,
+        requesting_player_id: PlayerId,
+        power_line_id: PowerLineId,
+    ) -> Result<(), ()> {
+        self.buildings
+            .attempt_to_remove_power_line(requesting_player_id, power_line_id)
+    }
+
+    // Waypoints that transports are ordered to pass cannot be removed until the orders change
+    pub fn remove_waypoint(
+        &mut self,
+        requesting_player_id: PlayerId,
+        waypoint_id: WaypointId,
+    ) -> Result<(), ()> {
+        let in_orders = self
+            .transports
+            .all_transports()
+            .iter()
+            .any(|transport| transport.movement_orders().contains_waypoint(waypoint_id));
+        if in_orders {
+            return Err(());
+        }
+        self.buildings
+            .attempt_to_remove_waypoint(requesting_player_id, waypoint_id)
     }
 
     pub fn remove_military_building(
@@ -555,7 +1386,10 @@ impl GameState {
         military_building_id: MilitaryBuildingId,
     ) -> Result<(), ()> {
         self.buildings
-            .attempt_to_remove_military_building(requesting_player_id, military_building_id)
+            .attempt_to_remove_military_building(requesting_player_id, military_building_id)?;
+        self.buildings
+            .dequeue_shot(ProjectileSource::MilitaryBuilding(military_building_id));
+        Ok(())
     }
 
     #[must_use]
@@ -568,14 +1402,17 @@ impl GameState {
         &mut self.buildings
     }
 
+    #[expect(clippy::too_many_arguments)]
     pub fn update_dynamic_infos(
         &mut self,
         server_time: GameTime,
         industry_building_dynamic_infos: &HashMap<IndustryBuildingId, BuildingDynamicInfo>,
         station_dynamic_infos: &HashMap<StationId, BuildingDynamicInfo>,
         military_building_dynamic_infos: &HashMap<MilitaryBuildingId, MilitaryBuildingDynamicInfo>,
+        construction_sites: &HashMap<ConstructionTarget, ConstructionSite>,
         transport_dynamic_infos: &HashMap<TransportId, TransportDynamicInfo>,
         projectile_dynamic_infos: &HashMap<ProjectileId, ProjectileDynamicInfo>,
+        treasuries: &HashMap<PlayerId, Money>,
     ) {
         let diff = server_time - self.time;
         trace!(
@@ -598,9 +1435,11 @@ impl GameState {
             industry_building_dynamic_infos,
             station_dynamic_infos,
             military_building_dynamic_infos,
+            construction_sites,
         );
         self.projectiles
             .update_dynamic_infos(projectile_dynamic_infos);
+        self.players.update_treasuries(treasuries);
     }
 
     #[must_use]
@@ -638,6 +1477,15 @@ impl GameState {
         }
     }
 
+    fn valid_trader(
+        &self,
+        requesting_player_id: PlayerId,
+        trader_id: PlayerId,
+    ) -> Result<(), TradeError> {
+        self.valid_owner(requesting_player_id, trader_id)
+            .map_err(|_| TradeError::InvalidOwner)
+    }
+
     #[must_use]
     pub fn supply_chain(&self) -> &SupplyChain {
         &self.supply_chain
@@ -43,6 +43,11 @@ impl GameTime {
     pub fn from_seconds(seconds: f32) -> Self {
         Self(seconds)
     }
+
+    #[must_use]
+    pub fn to_seconds(&self) -> f32 {
+        self.0
+    }
 }
 
 impl Add<GameTimeDiff> for GameTimeDiff {
@@ -8,9 +8,11 @@ use shared_util::random::generate_random_string;
 use transport::track_type::TrackType;
 use uuid::Uuid;
 
+use crate::military::ProjectileSource;
 use crate::tile_coords_xz::TileCoordsXZ;
 
 pub mod building;
+pub mod cargo_age;
 pub mod cargo_amount;
 pub mod cargo_map;
 pub mod client_command;
@@ -21,10 +23,12 @@ pub mod game_time;
 pub mod map_level;
 pub mod metrics;
 pub mod military;
+pub mod money;
 pub mod players;
 pub mod resource_type;
 pub mod scenario;
 pub mod server_response;
+pub mod statistics;
 pub mod supply_chain;
 pub mod tile_coords_xz;
 pub mod tile_coverage;
@@ -152,23 +156,33 @@ newtype_uuid!(StationId, "S");
 newtype_uuid!(IndustryBuildingId, "IB");
 newtype_uuid!(MilitaryBuildingId, "MB");
 newtype_uuid!(TransportId, "T");
+newtype_uuid!(TransportGroupId, "TG");
 newtype_uuid!(ZoningId, "Z");
+newtype_uuid!(WaypointId, "W");
+newtype_uuid!(BridgeId, "BR");
+newtype_uuid!(PowerLineId, "PL");
+newtype_uuid!(TradeOfferId, "TO");
 
 // We have predictable projectile IDs, because we are generating them upon reload on both the client & the server, and want to - ideally - the IDs to match
 #[derive(Copy, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord, Debug)]
 pub struct ProjectileId {
-    fired_by:        MilitaryBuildingId,
+    fired_by:        ProjectileSource,
     sequence_number: usize,
 }
 
 impl ProjectileId {
     #[must_use]
-    pub fn new(fired_by: MilitaryBuildingId, sequence_number: usize) -> Self {
+    pub fn new(fired_by: ProjectileSource, sequence_number: usize) -> Self {
         Self {
             fired_by,
             sequence_number,
         }
     }
+
+    #[must_use]
+    pub fn fired_by(&self) -> ProjectileSource {
+        self.fired_by
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord, Debug)]
@@ -201,7 +215,7 @@ impl ScenarioId {
 #[derive(Clone, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord, Debug)]
 pub struct MapId(pub String);
 
-#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy)]
 pub struct TrackId {
     pub tile:       TileCoordsXZ,
     pub track_type: TrackType,
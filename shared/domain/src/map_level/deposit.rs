@@ -0,0 +1,88 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cargo_amount::CargoAmount;
+
+// Output starts declining once only this share of the deposit is left
+const DECLINE_STARTS_AT: f32 = 0.25;
+
+/// A finite amount of a resource in the ground, extracted by the mine built on the zoning.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Deposit {
+    size:      CargoAmount,
+    remaining: CargoAmount,
+}
+
+impl Debug for Deposit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.0} / {:.0}",
+            self.remaining.as_f32(),
+            self.size.as_f32()
+        )
+    }
+}
+
+impl Deposit {
+    #[must_use]
+    pub fn new(size: CargoAmount) -> Self {
+        Self {
+            size,
+            remaining: size,
+        }
+    }
+
+    #[must_use]
+    pub fn size(&self) -> CargoAmount {
+        self.size
+    }
+
+    #[must_use]
+    pub fn remaining(&self) -> CargoAmount {
+        self.remaining
+    }
+
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining <= CargoAmount::ZERO
+    }
+
+    /// The share of the full production rate that the deposit can still sustain.
+    #[must_use]
+    pub fn output_coef(&self) -> f32 {
+        if self.size <= CargoAmount::ZERO {
+            0.0
+        } else {
+            let remaining_share = self.remaining / self.size;
+            (remaining_share / DECLINE_STARTS_AT).clamp(0.0, 1.0)
+        }
+    }
+
+    pub(crate) fn extract(&mut self, amount: CargoAmount) {
+        self.remaining = (self.remaining - amount).max(CargoAmount::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_declines_as_deposit_is_exhausted() {
+        let mut deposit = Deposit::new(CargoAmount::new(1000.0));
+        assert!((deposit.output_coef() - 1.0).abs() < f32::EPSILON);
+
+        deposit.extract(CargoAmount::new(750.0));
+        assert!((deposit.output_coef() - 1.0).abs() < f32::EPSILON);
+
+        deposit.extract(CargoAmount::new(125.0));
+        assert!((deposit.output_coef() - 0.5).abs() < f32::EPSILON);
+
+        deposit.extract(CargoAmount::new(500.0));
+        assert!(deposit.is_exhausted());
+        assert_eq!(deposit.remaining(), CargoAmount::ZERO);
+        assert!(deposit.output_coef().abs() < f32::EPSILON);
+    }
+}
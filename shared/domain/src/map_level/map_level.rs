@@ -8,9 +8,11 @@ use shared_util::bool_ops::BoolResultOps;
 
 use crate::MapId;
 use crate::building::BuildError;
+use crate::building::bridge_info::{BridgeInfo, BridgeType};
 use crate::building::building_info::WithTileCoverage;
 use crate::building::industry_building_info::IndustryBuildingInfo;
 use crate::building::military_building_info::MilitaryBuildingInfo;
+use crate::building::power_line_info::PowerLineInfo;
 use crate::building::station_info::StationInfo;
 use crate::map_level::terrain::Terrain;
 use crate::map_level::zoning::{Zoning, ZoningFlattened};
@@ -129,6 +131,9 @@ impl Add for Height {
     }
 }
 
+const MAX_BRIDGE_PORTAL_HEIGHT_DIFF: Height = Height::from_u8(1);
+const MIN_TUNNEL_CLEARANCE: Height = Height::from_u8(1);
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct MapLevel {
     map_id:  MapId,
@@ -265,6 +270,59 @@ impl MapLevel {
         Ok(())
     }
 
+    // The portals at both ends have to be level and on dry land, and the terrain in between must be
+    // below a bridge's deck, or sufficiently above a tunnel
+    pub(crate) fn can_build_bridge(&self, bridge: &BridgeInfo) -> Result<(), BuildError> {
+        bridge
+            .bridge_type()
+            .length_in_tiles_range()
+            .contains(&bridge.length_in_tiles())
+            .then_ok_unit(|| BuildError::InvalidTerrain)?;
+
+        let tiles = bridge.tiles();
+        for tile in &tiles {
+            self.tile_in_bounds(*tile)
+                .then_ok_unit(|| BuildError::InvalidTerrain)?;
+            self.zoning.can_build_track(*tile)?;
+        }
+
+        let direction = bridge.direction();
+        let (entry_a, entry_b) = bridge
+            .start_tile()
+            .vertex_coords_clockwise(direction.reverse());
+        let (exit_a, exit_b) = bridge.end_tile().vertex_coords_clockwise(direction);
+        let portals = [entry_a, entry_b, exit_a, exit_b];
+
+        let entry_height = self.height_at(entry_a);
+        let exit_height = self.height_at(exit_a);
+        let portals_valid = entry_height == self.height_at(entry_b)
+            && exit_height == self.height_at(exit_b)
+            && entry_height.min(exit_height) + MAX_BRIDGE_PORTAL_HEIGHT_DIFF
+                >= entry_height.max(exit_height)
+            && !portals
+                .iter()
+                .any(|vertex| self.vertex_under_water(*vertex));
+        portals_valid.then_ok_unit(|| BuildError::InvalidTerrain)?;
+
+        let mut inner_vertices = tiles
+            .into_iter()
+            .flat_map(TileCoordsXZ::vertex_coords)
+            .filter(|vertex| !portals.contains(vertex));
+        let clearance_valid = match bridge.bridge_type() {
+            BridgeType::Bridge => {
+                let deck = entry_height.min(exit_height);
+                inner_vertices.all(|vertex| self.height_at(vertex) <= deck)
+            },
+            BridgeType::Tunnel => {
+                let ceiling = entry_height.max(exit_height) + MIN_TUNNEL_CLEARANCE;
+                inner_vertices.all(|vertex| {
+                    self.height_at(vertex) >= ceiling && !self.vertex_under_water(vertex)
+                })
+            },
+        };
+        clearance_valid.then_ok_unit(|| BuildError::InvalidTerrain)
+    }
+
     pub(crate) fn can_build_industry_building(
         &self,
         industry_building_info: &IndustryBuildingInfo,
@@ -293,6 +351,20 @@ impl MapLevel {
         Ok(())
     }
 
+    // Pylons can stand on slopes, just not in the water
+    pub(crate) fn can_build_power_line(
+        &self,
+        power_line: &PowerLineInfo,
+    ) -> Result<(), BuildError> {
+        let tile = power_line.tile();
+        let valid = self.tile_in_bounds(tile)
+            && !tile
+                .vertex_coords()
+                .into_iter()
+                .any(|vertex| self.vertex_under_water(vertex));
+        valid.then_ok_unit(|| BuildError::InvalidTerrain)
+    }
+
     #[expect(clippy::missing_errors_doc)]
     pub fn can_build_for_coverage(&self, tile_coverage: &TileCoverage) -> Result<(), BuildError> {
         let vertex_coords: Vec<_> = tile_coverage
@@ -1,5 +1,6 @@
 #![allow(clippy::module_inception)]
 
+pub mod deposit;
 pub mod map_level;
 pub mod terrain;
 pub mod zoning;
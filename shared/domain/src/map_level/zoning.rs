@@ -13,6 +13,7 @@ use crate::building::building_info::WithTileCoverage;
 use crate::building::industry_building_info::IndustryBuildingInfo;
 use crate::building::station_info::StationInfo;
 use crate::building::{BuildError, WithRelativeTileCoverage};
+use crate::map_level::deposit::Deposit;
 use crate::map_level::zoning::ZoningType::Source;
 use crate::resource_type::ResourceType;
 use crate::resource_type::ResourceType::{
@@ -60,15 +61,22 @@ pub struct ZoningInfo {
     id:             ZoningId,
     zoning_type:    ZoningType,
     reference_tile: TileCoordsXZ,
+    deposit:        Option<Deposit>,
 }
 
 impl ZoningInfo {
     #[must_use]
-    pub fn new(id: ZoningId, zoning_type: ZoningType, reference_tile: TileCoordsXZ) -> Self {
+    pub fn new(
+        id: ZoningId,
+        zoning_type: ZoningType,
+        reference_tile: TileCoordsXZ,
+        deposit: Option<Deposit>,
+    ) -> Self {
         Self {
             id,
             zoning_type,
             reference_tile,
+            deposit,
         }
     }
 
@@ -86,6 +94,12 @@ impl ZoningInfo {
     pub fn reference_tile(&self) -> TileCoordsXZ {
         self.reference_tile
     }
+
+    /// The resources left to extract, if this is a source zoning with a finite deposit.
+    #[must_use]
+    pub fn deposit(&self) -> Option<Deposit> {
+        self.deposit
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -163,6 +177,14 @@ impl Zoning {
         found.and_then(|id| self.infos.get(&id))
     }
 
+    pub(crate) fn update_deposit(&mut self, zoning_id: ZoningId, deposit: Deposit) {
+        if let Some(info) = self.infos.get_mut(&zoning_id) {
+            info.deposit = Some(deposit);
+        } else {
+            warn!("Tried to update deposit of missing zoning {zoning_id:?}");
+        }
+    }
+
     #[must_use]
     pub fn free_at_tile(&self, tile: TileCoordsXZ) -> bool {
         match self.grid.get(tile) {
@@ -5,6 +5,8 @@ use crate::transport::track_length::TrackLength;
 pub trait Metrics {
     fn track_pathfinding_duration(&self, duration: Duration, result: Option<(usize, TrackLength)>);
     fn track_planning_duration(&self, duration: Duration, result: Option<(usize, TrackLength)>);
+    fn route_cache_hit(&self);
+    fn route_cache_miss(&self);
 }
 
 #[expect(clippy::module_name_repetitions)]
@@ -20,4 +22,8 @@ impl Metrics for NoopMetrics {
     }
 
     fn track_planning_duration(&self, _duration: Duration, _result: Option<(usize, TrackLength)>) {}
+
+    fn route_cache_hit(&self) {}
+
+    fn route_cache_miss(&self) {}
 }
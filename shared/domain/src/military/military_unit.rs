@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ProjectileId;
+use crate::building::building_info::WithOwner;
+use crate::client_command::InternalGameCommand;
+use crate::game_state::GameState;
+use crate::game_time::{GameTime, GameTimeDiff};
+use crate::military::ProjectileSource;
+use crate::military::projectile_info::ProjectileInfo;
+use crate::tile_coverage::TileCoverage;
+use crate::transport::transport_info::TransportInfo;
+
+const RAILWAY_GUN_RELOAD_SECONDS: f32 = 15.0;
+
+/// The state of a transport that is a mobile military unit.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default, Debug)]
+pub struct MilitaryUnitDynamicInfo {
+    // Reached the location it was ordered to deploy at, and stays there
+    deployed: bool,
+    // The last upkeep was paid by a military base in supply range
+    supplied: bool,
+    last_fired_at: GameTime,
+    next_projectile_sequence_number: usize,
+}
+
+impl MilitaryUnitDynamicInfo {
+    #[must_use]
+    pub fn is_deployed(&self) -> bool {
+        self.deployed
+    }
+
+    pub(crate) fn set_deployed(&mut self, deployed: bool) {
+        self.deployed = deployed;
+    }
+
+    #[must_use]
+    pub fn is_supplied(&self) -> bool {
+        self.supplied
+    }
+
+    pub(crate) fn set_supplied(&mut self, supplied: bool) {
+        self.supplied = supplied;
+    }
+
+    #[must_use]
+    pub fn ready_to_fire_at(&self) -> GameTime {
+        self.last_fired_at + GameTimeDiff::from_seconds(RAILWAY_GUN_RELOAD_SECONDS)
+    }
+
+    pub(crate) fn update_projectile_fired(&mut self, projectile: &ProjectileInfo) {
+        self.last_fired_at = self.last_fired_at.max(projectile.fired_at());
+        self.next_projectile_sequence_number = projectile.projectile_id().sequence_number + 1;
+    }
+}
+
+// Only deployed units with railway guns fire, at the target of their deploy order, and only while
+// they are supplied. The ammunition comes from a military base in supply range of where the unit
+// is, and a shot waits in the queue of the base if the base has run out.
+#[must_use]
+pub(crate) fn generate_commands(
+    transport: &TransportInfo,
+    previous_game_time: GameTime,
+    new_game_time: GameTime,
+    game_state: &GameState,
+) -> Vec<InternalGameCommand> {
+    let Some(military_unit) = &transport.dynamic_info.military_unit else {
+        return vec![];
+    };
+    let Some(shell_type) = transport.transport_type().shell_type() else {
+        return vec![];
+    };
+    let Some(target) = transport.movement_orders().current_order().deploy_target() else {
+        return vec![];
+    };
+    let ready_to_fire = military_unit.ready_to_fire_at();
+    if !military_unit.is_deployed() || !military_unit.is_supplied() || new_game_time < ready_to_fire
+    {
+        return vec![];
+    }
+
+    let fired_at = ready_to_fire.max(previous_game_time);
+    let tile = transport.location().next_tile_in_path().tile;
+    let coverage = TileCoverage::Single(tile);
+    let source = ProjectileSource::MilitaryUnit(transport.transport_id());
+    let costs = game_state.can_pay_for_shot(transport.owner_id(), &coverage, shell_type, source);
+    let Ok(costs) = costs else {
+        return game_state
+            .queue_shot_command(transport.owner_id(), &coverage, source)
+            .into_iter()
+            .collect();
+    };
+
+    ProjectileInfo::fire_from_tile(
+        game_state,
+        ProjectileId::new(source, military_unit.next_projectile_sequence_number),
+        transport.owner_id(),
+        shell_type.projectile_type(),
+        tile,
+        target,
+        fired_at,
+    )
+    .map(|projectile_info| InternalGameCommand::SpawnProjectile(projectile_info, costs))
+    .into_iter()
+    .collect()
+}
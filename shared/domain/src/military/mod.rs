@@ -1,26 +1,38 @@
 use serde::{Deserialize, Serialize};
-use shared_physics::constants::METERS_PER_INCH;
 use shared_physics::projectile::ProjectileProperties;
 
 use crate::cargo_map::CargoMap;
 use crate::resource_type::ResourceType;
+use crate::{IndustryBuildingId, MilitaryBuildingId, TransportId};
 
+pub mod military_unit;
 pub mod projectile_info;
 pub mod projectile_state;
 
+/// What fired the projectile - a military building, or a mobile military unit, which is a
+/// transport.
+#[derive(Copy, Clone, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord, Debug)]
+pub enum ProjectileSource {
+    MilitaryBuilding(MilitaryBuildingId),
+    MilitaryUnit(TransportId),
+}
+
+/// Whether the artillery can fire, and which military base it draws its shots from.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AmmunitionStatus {
+    // There is no military base in supply range
+    NoSupplier,
+    // The supplying base is out of ammunition, so the next shot is queued until it has enough
+    OutOfAmmunition(IndustryBuildingId),
+    Supplied(IndustryBuildingId),
+}
+
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum ProjectileType {
     Standard,
 }
 
 impl ProjectileType {
-    #[must_use]
-    pub fn cost_per_shot(&self) -> CargoMap {
-        match self {
-            ProjectileType::Standard => CargoMap::single(ResourceType::Ammunition, 0.01),
-        }
-    }
-
     #[must_use]
     pub const fn projectile_properties(&self) -> ProjectileProperties {
         match self {
@@ -32,38 +44,38 @@ impl ProjectileType {
     }
 }
 
-// Later: Throw out unused code
-#[derive(Debug, Clone, Copy)]
-#[expect(dead_code, clippy::enum_variant_names)]
-enum ShellType {
+/// The shells the artillery fires, drawn from the stockpile of the supplying military base.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ShellType {
     Naval16Inch,
     Naval14Inch,
-    Naval5Inch,
 }
 
 impl ShellType {
+    // Heavier guns also wear out their barrels, which are replaced from the artillery weapons
     #[must_use]
-    #[expect(dead_code)]
-    pub const fn projectile_properties(self: ShellType) -> ProjectileProperties {
+    pub fn cost_per_shot(self) -> CargoMap {
         match self {
             ShellType::Naval16Inch => {
-                // From https://en.wikipedia.org/wiki/16-inch/50-caliber_Mark_7_gun
-                ProjectileProperties::create_from_inches(16.0, 1225.0, 1.829, 762.0)
+                CargoMap::from([
+                    (ResourceType::Ammunition, 0.01),
+                    (ResourceType::ArtilleryWeapons, 0.001),
+                ])
             },
             ShellType::Naval14Inch => {
-                // From http://www.navweaps.com/Weapons/WNJAP_14-45_t41.php
-                // Failed to find shell height, so just estimating it
-                ProjectileProperties::create_from_inches(
-                    14.0,
-                    673.5,
-                    4.0 * 14.0 * METERS_PER_INCH,
-                    772.5,
-                )
-            },
-            ShellType::Naval5Inch => {
-                // From http://www.navweaps.com/Weapons/WNGER_5-45_skc34.php
-                ProjectileProperties::create_from_inches(5.0, 28.0, 0.68, 830.0)
+                CargoMap::from([
+                    (ResourceType::Ammunition, 0.0075),
+                    (ResourceType::ArtilleryWeapons, 0.0005),
+                ])
             },
         }
     }
+
+    // All shells fly the same way for now, see `ProjectileType::projectile_properties`
+    #[must_use]
+    pub const fn projectile_type(self) -> ProjectileType {
+        match self {
+            ShellType::Naval16Inch | ShellType::Naval14Inch => ProjectileType::Standard,
+        }
+    }
 }
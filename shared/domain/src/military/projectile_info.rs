@@ -1,21 +1,26 @@
 use std::fmt::{Debug, Formatter};
 
+use bevy_math::Vec3;
+use log::info;
 use serde::{Deserialize, Serialize};
-use shared_physics::projectile::calculate_acceleration;
+use shared_physics::projectile::{
+    best_effort_start_velocity_vector_given_start_velocity, calculate_acceleration,
+};
 
 use crate::client_command::InternalGameCommand;
+use crate::game_state::GameState;
 use crate::game_time::{GameTime, GameTimeDiff};
-use crate::military::ProjectileType;
+use crate::military::{ProjectileSource, ProjectileType};
 use crate::tile_coords_xz::TileCoordsXZ;
 use crate::vector3::Vector3;
-use crate::{MilitaryBuildingId, PlayerId, ProjectileId};
+use crate::{PlayerId, ProjectileId};
 
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 struct ProjectileStaticInfo {
     projectile_id:   ProjectileId,
     owner_id:        PlayerId,
     projectile_type: ProjectileType,
-    fired_from:      MilitaryBuildingId,
+    fired_from:      ProjectileSource,
     fired_at:        GameTime,
     landing_at:      GameTime,
     landing_on:      TileCoordsXZ,
@@ -46,7 +51,7 @@ impl ProjectileInfo {
         projectile_id: ProjectileId,
         owner_id: PlayerId,
         projectile_type: ProjectileType,
-        fired_from: MilitaryBuildingId,
+        fired_from: ProjectileSource,
         fired_at: GameTime,
         landing_at: GameTime,
         landing_on: TileCoordsXZ,
@@ -67,6 +72,67 @@ impl ProjectileInfo {
         }
     }
 
+    // Aims a projectile from the tile where the artillery stands, if the target is within range
+    #[must_use]
+    pub(crate) fn fire_from_tile(
+        game_state: &GameState,
+        projectile_id: ProjectileId,
+        owner_id: PlayerId,
+        projectile_type: ProjectileType,
+        from_tile: TileCoordsXZ,
+        landing_on: TileCoordsXZ,
+        fired_at: GameTime,
+    ) -> Option<Self> {
+        let artillery_height = Vec3::new(0.0, 0.5, 0.0);
+        let from_position = game_state
+            .map_level()
+            .terrain()
+            .tile_center_coordinate(from_tile)
+            + artillery_height;
+        let location = from_position.into();
+
+        let target_position = game_state
+            .map_level()
+            .terrain()
+            .tile_center_coordinate(landing_on);
+
+        let projectile_properties = projectile_type.projectile_properties();
+
+        let velocity_and_time = best_effort_start_velocity_vector_given_start_velocity(
+            from_position,
+            target_position,
+            &projectile_properties,
+        );
+
+        info!("Calculated velocity and time: {velocity_and_time:?}");
+
+        match velocity_and_time {
+            None => {
+                info!(
+                    "Failed to create projectile from {from_position:?} to {target_position:?} - perhaps the target is too far?"
+                );
+                None
+            },
+            Some((velocity, time)) => {
+                let landing_at = fired_at + GameTimeDiff::from_seconds(time);
+
+                let projectile_info = Self::new(
+                    projectile_id,
+                    owner_id,
+                    projectile_type,
+                    projectile_id.fired_by(),
+                    fired_at,
+                    landing_at,
+                    landing_on,
+                    location,
+                    velocity.into(),
+                );
+                info!("Firing {projectile_info:?}",);
+                Some(projectile_info)
+            },
+        }
+    }
+
     #[must_use]
     pub fn owner_id(&self) -> PlayerId {
         self.static_info.owner_id
@@ -87,7 +153,12 @@ impl ProjectileInfo {
     }
 
     #[must_use]
-    pub fn fired_from(&self) -> MilitaryBuildingId {
+    pub fn landing_on(&self) -> TileCoordsXZ {
+        self.static_info.landing_on
+    }
+
+    #[must_use]
+    pub fn fired_from(&self) -> ProjectileSource {
         self.static_info.fired_from
     }
 
@@ -1 +1,2 @@
 pub mod player_state;
+pub mod trade_offer;
@@ -2,20 +2,30 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::PlayerId;
+use crate::building::BuildCosts;
+use crate::money::Money;
+use crate::players::trade_offer::TradeOffer;
 use crate::server_response::PlayerInfo;
+use crate::{PlayerId, TradeOfferId};
+
+const INITIAL_TREASURY: Money = Money::new(100_000.0);
 
 // TODO: The players are actually 'Nation'-s or 'Polity'-s, and the players just control them.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct PlayerState {
-    infos: HashMap<PlayerId, PlayerInfo>,
+    infos:        HashMap<PlayerId, PlayerInfo>,
+    treasuries:   HashMap<PlayerId, Money>,
+    // Waiting for the other player to accept them
+    trade_offers: HashMap<TradeOfferId, TradeOffer>,
 }
 
 impl PlayerState {
     #[must_use]
     pub fn from_infos(infos: Vec<PlayerInfo>) -> Self {
         let mut result = Self {
-            infos: HashMap::new(),
+            infos:        HashMap::new(),
+            treasuries:   HashMap::new(),
+            trade_offers: HashMap::new(),
         };
         for info in infos {
             result.insert(info);
@@ -39,6 +49,7 @@ impl PlayerState {
     }
 
     fn insert(&mut self, player_info: PlayerInfo) {
+        self.treasuries.insert(player_info.id, INITIAL_TREASURY);
         self.infos.insert(player_info.id, player_info);
     }
 
@@ -46,4 +57,62 @@ impl PlayerState {
     pub fn get(&self, player_id: PlayerId) -> Option<&PlayerInfo> {
         self.infos.get(&player_id)
     }
+
+    #[must_use]
+    pub fn treasury(&self, player_id: PlayerId) -> Money {
+        self.treasuries.get(&player_id).copied().unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn treasuries(&self) -> &HashMap<PlayerId, Money> {
+        &self.treasuries
+    }
+
+    pub(crate) fn update_treasuries(&mut self, treasuries: &HashMap<PlayerId, Money>) {
+        self.treasuries.clone_from(treasuries);
+    }
+
+    #[must_use]
+    pub(crate) fn can_pay_costs(&self, costs: &BuildCosts) -> bool {
+        costs
+            .money
+            .iter()
+            .all(|(player_id, money)| self.treasury(*player_id) >= *money)
+    }
+
+    pub(crate) fn pay_costs(&mut self, costs: &BuildCosts) {
+        for (player_id, money) in &costs.money {
+            self.spend(*player_id, *money);
+        }
+    }
+
+    #[must_use]
+    pub fn trade_offers(&self) -> impl IntoIterator<Item = &TradeOffer> {
+        self.trade_offers.values()
+    }
+
+    #[must_use]
+    pub fn find_trade_offer(&self, trade_offer_id: TradeOfferId) -> Option<&TradeOffer> {
+        self.trade_offers.get(&trade_offer_id)
+    }
+
+    pub(crate) fn upsert_trade_offer(&mut self, trade_offer: TradeOffer) {
+        self.trade_offers.insert(trade_offer.id(), trade_offer);
+    }
+
+    pub(crate) fn remove_trade_offer(
+        &mut self,
+        trade_offer_id: TradeOfferId,
+    ) -> Option<TradeOffer> {
+        self.trade_offers.remove(&trade_offer_id)
+    }
+
+    pub(crate) fn earn(&mut self, player_id: PlayerId, money: Money) {
+        *self.treasuries.entry(player_id).or_default() += money;
+    }
+
+    // Unlike building, running costs are spent even if it leaves the treasury in debt
+    pub(crate) fn spend(&mut self, player_id: PlayerId, money: Money) {
+        *self.treasuries.entry(player_id).or_default() -= money;
+    }
 }
@@ -0,0 +1,124 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cargo_amount::CargoAmount;
+use crate::cargo_map::CargoMap;
+use crate::resource_type::ResourceType;
+use crate::{PlayerId, StationId, TradeOfferId};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TradeError {
+    UnknownOffer,
+    DuplicateOffer,
+    InvalidOwner,
+    // Trading with yourself, or with a player that is not in the game
+    InvalidCounterparty,
+    EmptyOffer,
+    NotEnoughCargo,
+    NotEnoughStorage,
+    UnknownStation,
+}
+
+/// One player offering cargo from one of their stations, in exchange for cargo of another player.
+/// When the other player accepts, naming one of their own stations, the cargo is exchanged
+/// between the two stations.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct TradeOffer {
+    id:              TradeOfferId,
+    from_player_id:  PlayerId,
+    from_station_id: StationId,
+    offered:         CargoMap,
+    to_player_id:    PlayerId,
+    requested:       CargoMap,
+}
+
+impl Debug for TradeOffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {:?} {:?} {:?} for {:?} {:?}",
+            self.id,
+            self.from_player_id,
+            self.from_station_id,
+            self.offered,
+            self.to_player_id,
+            self.requested,
+        )
+    }
+}
+
+impl TradeOffer {
+    #[must_use]
+    pub fn new(
+        id: TradeOfferId,
+        from_player_id: PlayerId,
+        from_station_id: StationId,
+        offered: CargoMap,
+        to_player_id: PlayerId,
+        requested: CargoMap,
+    ) -> Self {
+        Self {
+            id,
+            from_player_id,
+            from_station_id,
+            offered,
+            to_player_id,
+            requested,
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> TradeOfferId {
+        self.id
+    }
+
+    #[must_use]
+    pub fn from_player_id(&self) -> PlayerId {
+        self.from_player_id
+    }
+
+    #[must_use]
+    pub fn from_station_id(&self) -> StationId {
+        self.from_station_id
+    }
+
+    #[must_use]
+    pub fn offered(&self) -> &CargoMap {
+        &self.offered
+    }
+
+    #[must_use]
+    pub fn to_player_id(&self) -> PlayerId {
+        self.to_player_id
+    }
+
+    #[must_use]
+    pub fn requested(&self) -> &CargoMap {
+        &self.requested
+    }
+
+    /// Whether the player is one of the two sides of the trade.
+    #[must_use]
+    pub fn involves(&self, player_id: PlayerId) -> bool {
+        self.from_player_id == player_id || self.to_player_id == player_id
+    }
+
+    // Giving cargo away for nothing is fine, but there has to be something to exchange
+    pub(crate) fn validate(&self) -> Result<(), TradeError> {
+        let non_negative = |cargo: &CargoMap| {
+            ResourceType::all()
+                .into_iter()
+                .all(|resource| cargo.get(resource) >= CargoAmount::ZERO)
+        };
+        if self.from_player_id == self.to_player_id {
+            Err(TradeError::InvalidCounterparty)
+        } else if !non_negative(&self.offered) || !non_negative(&self.requested) {
+            Err(TradeError::NotEnoughCargo)
+        } else if self.offered.total_amount() + self.requested.total_amount() <= CargoAmount::ZERO {
+            Err(TradeError::EmptyOffer)
+        } else {
+            Ok(())
+        }
+    }
+}
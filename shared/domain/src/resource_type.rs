@@ -2,10 +2,12 @@ use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
 
+use crate::game_time::GameTimeDiff;
+use crate::money::Money;
 use crate::resource_type::ResourceType::{
     Ammunition, ArtilleryWeapons, Cellulose, Cement, Clay, Coal, Concrete, Explosives,
-    FarmProducts, Food, Fuel, Iron, Limestone, Nitrates, Oil, SandAndGravel, Steel, Sulfur, Timber,
-    Wood,
+    FarmProducts, Food, Fuel, Iron, Limestone, Nitrates, Oil, RollingStock, SandAndGravel, Steel,
+    Sulfur, Timber, Wood,
 };
 
 /// In a way, it is also a "cargo type"
@@ -33,11 +35,13 @@ pub enum ResourceType {
     Steel,
     Timber,
     ArtilleryWeapons,
+    // Locomotives and wagons, used up when purchasing trains
+    RollingStock,
 }
 
 impl ResourceType {
     #[must_use]
-    pub const fn all() -> [Self; 20] {
+    pub const fn all() -> [Self; 21] {
         [
             // Raw
             Clay,
@@ -61,6 +65,40 @@ impl ResourceType {
             Steel,
             Timber,
             ArtilleryWeapons,
+            RollingStock,
         ]
     }
+
+    /// Whether the resource can be loaded onto transports, rather than only being used where it
+    /// was produced.
+    #[must_use]
+    pub const fn is_transportable(self) -> bool {
+        !matches!(self, RollingStock)
+    }
+
+    // Paid for delivering one unit of this resource over one tile of distance
+    #[must_use]
+    pub const fn value(self) -> Money {
+        match self {
+            Clay | Coal | FarmProducts | Iron | Limestone | Nitrates | Oil | SandAndGravel
+            | Sulfur | Wood => Money::new(1.0),
+            Cellulose | Cement | Concrete | Explosives | Food | Fuel | Steel | Timber => {
+                Money::new(2.0)
+            },
+            Ammunition | ArtilleryWeapons | RollingStock => Money::new(4.0),
+        }
+    }
+
+    /// For perishable resources, how long until most of the stockpile has spoiled - it decays
+    /// exponentially, and late deliveries are paid less in the same way.
+    #[must_use]
+    pub fn shelf_life(self) -> Option<GameTimeDiff> {
+        match self {
+            FarmProducts => Some(GameTimeDiff::from_seconds(900.0)),
+            Food => Some(GameTimeDiff::from_seconds(1800.0)),
+            Clay | Coal | Iron | Limestone | Nitrates | Oil | SandAndGravel | Sulfur | Wood
+            | Ammunition | Cellulose | Cement | Concrete | Explosives | Fuel | Steel | Timber
+            | ArtilleryWeapons | RollingStock => None,
+        }
+    }
 }
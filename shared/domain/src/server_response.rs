@@ -7,19 +7,28 @@ use uuid::Uuid;
 use web_time::Duration;
 
 use crate::building::BuildError;
+use crate::building::bridge_info::BridgeInfo;
 use crate::building::building_info::BuildingDynamicInfo;
+use crate::building::construction_site::{ConstructionSite, ConstructionTarget};
 use crate::building::industry_building_info::IndustryBuildingInfo;
 use crate::building::military_building_info::{MilitaryBuildingDynamicInfo, MilitaryBuildingInfo};
+use crate::building::power_line_info::PowerLineInfo;
 use crate::building::station_info::StationInfo;
 use crate::building::track_info::TrackInfo;
+use crate::building::waypoint_info::WaypointInfo;
 use crate::client_command::DemolishSelector;
 use crate::game_state::GameState;
 use crate::game_time::{GameTime, TimeFactor};
 use crate::military::projectile_info::{ProjectileDynamicInfo, ProjectileInfo};
+use crate::money::Money;
+use crate::players::trade_offer::{TradeError, TradeOffer};
+use crate::statistics::StatisticsState;
+use crate::transport::transport_group::TransportGroupInfo;
 use crate::transport::transport_info::{TransportDynamicInfo, TransportInfo};
 use crate::{
-    ClientId, GameId, IndustryBuildingId, MilitaryBuildingId, PlayerId, PlayerName, ProjectileId,
-    ScenarioId, StationId, TrackId, TransportId, UserId, UserName,
+    BridgeId, ClientId, GameId, IndustryBuildingId, MilitaryBuildingId, PlayerId, PlayerName,
+    PowerLineId, ProjectileId, ScenarioId, StationId, TrackId, TradeOfferId, TransportId, UserId,
+    UserName, WaypointId,
 };
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -94,25 +103,43 @@ pub enum GameResponse {
     // Later: Actually, many of these should be sending `GameTime` (if it's not already included in other structures such as `GameState`), and it should be handled on the client.
     PlayersUpdated(Vec<(UserId, PlayerId)>),
     IndustryBuildingAdded(IndustryBuildingInfo),
+    IndustryBuildingUpgraded(IndustryBuildingInfo),
+    PreferredSupplierSet(IndustryBuildingId),
     IndustryBuildingRemoved(IndustryBuildingId),
     MilitaryBuildingAdded(MilitaryBuildingInfo),
     MilitaryBuildingRemoved(MilitaryBuildingId),
     StationAdded(StationInfo),
+    StationExpanded(StationInfo),
     StationRemoved(StationId),
     TracksAdded(Vec<TrackInfo>),
+    TracksUpgraded(Vec<TrackInfo>),
     TracksRemoved(Vec<TrackId>),
+    WaypointAdded(WaypointInfo),
+    WaypointRemoved(WaypointId),
+    BridgeAdded(BridgeInfo),
+    BridgeRemoved(BridgeId),
+    PowerLineAdded(PowerLineInfo),
+    PowerLineRemoved(PowerLineId),
     TransportsAdded(Vec<TransportInfo>),
+    TransportsRemoved(Vec<TransportId>),
+    TransportGroupsUpdated(Vec<TransportGroupInfo>),
     ProjectilesAdded(Vec<ProjectileInfo>),
     ProjectilesRemoved(Vec<ProjectileId>),
+    TradeOffered(TradeOffer),
+    TradeOfferAccepted(TradeOfferId),
+    TradeOfferCancelled(TradeOfferId),
     DynamicInfosSync(
         GameTime,
         Option<TimeFactor>,
         HashMap<IndustryBuildingId, BuildingDynamicInfo>,
         HashMap<StationId, BuildingDynamicInfo>,
         HashMap<MilitaryBuildingId, MilitaryBuildingDynamicInfo>,
+        HashMap<ConstructionTarget, ConstructionSite>,
         HashMap<TransportId, TransportDynamicInfo>,
         HashMap<ProjectileId, ProjectileDynamicInfo>,
+        HashMap<PlayerId, Money>,
     ),
+    Statistics(StatisticsState),
     GameJoined(PlayerId, GameState),
     GameLeft,
 
@@ -124,10 +151,20 @@ pub enum GameError {
     GameNotFound,
     CannotBuildStation(StationId, BuildError),
     CannotBuildIndustryBuilding(IndustryBuildingId, BuildError),
+    CannotUpgradeIndustryBuilding(IndustryBuildingId, BuildError),
+    CannotSetPreferredSupplier(IndustryBuildingId, BuildError),
     CannotBuildMilitaryBuilding(MilitaryBuildingId, BuildError),
     CannotBuildTracks(Vec<TrackId>, BuildError),
+    CannotUpgradeTracks(Vec<TrackId>, BuildError),
+    CannotBuildWaypoint(WaypointId, BuildError),
+    CannotBuildBridge(BridgeId, BuildError),
+    CannotBuildPowerLine(PowerLineId, BuildError),
     CannotPurchaseTransport(TransportId, BuildError),
     CannotDemolish(DemolishSelector),
+    CannotDemolishOccupiedTracks(Vec<TrackId>, Vec<TransportId>),
+    CannotOfferTrade(TradeOfferId, TradeError),
+    CannotAcceptTradeOffer(TradeOfferId, TradeError),
+    CannotCancelTradeOffer(TradeOfferId, TradeError),
     UnspecifiedError,
 }
 
@@ -144,6 +181,18 @@ impl Debug for GameError {
                     "CannotBuildIndustryBuilding({industry_building_id:?}: {error:?})"
                 )
             },
+            GameError::CannotUpgradeIndustryBuilding(industry_building_id, error) => {
+                write!(
+                    f,
+                    "CannotUpgradeIndustryBuilding({industry_building_id:?}: {error:?})"
+                )
+            },
+            GameError::CannotSetPreferredSupplier(industry_building_id, error) => {
+                write!(
+                    f,
+                    "CannotSetPreferredSupplier({industry_building_id:?}: {error:?})"
+                )
+            },
             GameError::CannotBuildMilitaryBuilding(military_building_id, error) => {
                 write!(
                     f,
@@ -157,12 +206,44 @@ impl Debug for GameError {
                     track_ids.len()
                 )
             },
+            GameError::CannotUpgradeTracks(track_ids, error) => {
+                write!(
+                    f,
+                    "CannotUpgradeTracks({} tracks: {error:?})",
+                    track_ids.len()
+                )
+            },
+            GameError::CannotBuildWaypoint(waypoint_id, error) => {
+                write!(f, "CannotBuildWaypoint({waypoint_id:?}: {error:?})")
+            },
+            GameError::CannotBuildBridge(bridge_id, error) => {
+                write!(f, "CannotBuildBridge({bridge_id:?}: {error:?})")
+            },
+            GameError::CannotBuildPowerLine(power_line_id, error) => {
+                write!(f, "CannotBuildPowerLine({power_line_id:?}: {error:?})")
+            },
             GameError::CannotPurchaseTransport(transport_id, error) => {
                 write!(f, "CannotPurchase({transport_id:?}, {error:?})")
             },
             GameError::CannotDemolish(demolish_selector) => {
                 write!(f, "CannotDemolish({demolish_selector:?})")
             },
+            GameError::CannotDemolishOccupiedTracks(track_ids, transport_ids) => {
+                write!(
+                    f,
+                    "CannotDemolishOccupiedTracks({} tracks, blocked by {transport_ids:?})",
+                    track_ids.len()
+                )
+            },
+            GameError::CannotOfferTrade(trade_offer_id, error) => {
+                write!(f, "CannotOfferTrade({trade_offer_id:?}: {error:?})")
+            },
+            GameError::CannotAcceptTradeOffer(trade_offer_id, error) => {
+                write!(f, "CannotAcceptTradeOffer({trade_offer_id:?}: {error:?})")
+            },
+            GameError::CannotCancelTradeOffer(trade_offer_id, error) => {
+                write!(f, "CannotCancelTradeOffer({trade_offer_id:?}: {error:?})")
+            },
             GameError::UnspecifiedError => write!(f, "UnspecifiedError"),
         }
     }
@@ -186,6 +267,17 @@ impl Debug for GameResponse {
             GameResponse::IndustryBuildingAdded(building) => {
                 write!(f, "IndustryBuildingAdded({})", building.id())
             },
+            GameResponse::IndustryBuildingUpgraded(building) => {
+                write!(
+                    f,
+                    "IndustryBuildingUpgraded({}, L{})",
+                    building.id(),
+                    building.level()
+                )
+            },
+            GameResponse::PreferredSupplierSet(industry_building_id) => {
+                write!(f, "PreferredSupplierSet({industry_building_id:?})")
+            },
             GameResponse::IndustryBuildingRemoved(industry_building_id) => {
                 write!(f, "IndustryBuildingRemoved({industry_building_id:?})")
             },
@@ -201,6 +293,27 @@ impl Debug for GameResponse {
             GameResponse::TracksAdded(tracks) => {
                 write!(f, "TracksAdded({} tracks)", tracks.len())
             },
+            GameResponse::TracksUpgraded(tracks) => {
+                write!(f, "TracksUpgraded({} tracks)", tracks.len())
+            },
+            GameResponse::WaypointAdded(waypoint) => {
+                write!(f, "WaypointAdded({waypoint:?})")
+            },
+            GameResponse::WaypointRemoved(waypoint_id) => {
+                write!(f, "WaypointRemoved({waypoint_id:?})")
+            },
+            GameResponse::BridgeAdded(bridge) => {
+                write!(f, "BridgeAdded({bridge:?})")
+            },
+            GameResponse::BridgeRemoved(bridge_id) => {
+                write!(f, "BridgeRemoved({bridge_id:?})")
+            },
+            GameResponse::PowerLineAdded(power_line) => {
+                write!(f, "PowerLineAdded({power_line:?})")
+            },
+            GameResponse::PowerLineRemoved(power_line_id) => {
+                write!(f, "PowerLineRemoved({power_line_id:?})")
+            },
             GameResponse::TransportsAdded(transports) => {
                 write!(
                     f,
@@ -212,25 +325,36 @@ impl Debug for GameResponse {
                         .join(", ")
                 )
             },
+            GameResponse::TransportsRemoved(transport_ids) => {
+                write!(f, "TransportsRemoved({transport_ids:?})")
+            },
+            GameResponse::TransportGroupsUpdated(groups) => {
+                write!(f, "TransportGroupsUpdated({groups:?})")
+            },
             GameResponse::DynamicInfosSync(
                 game_time,
                 time_factor,
                 industry_buildings,
                 stations,
                 military_buildings,
+                construction_sites,
                 transports,
                 projectiles,
+                treasuries,
             ) => {
                 write!(
                     f,
-                    "DynamicInfosSync({game_time:?} time, {time_factor:?} time_factor, {} industry, {} stations, {} military {} transports, {} projectiles)",
+                    "DynamicInfosSync({game_time:?} time, {time_factor:?} time_factor, {} industry, {} stations, {} military, {} construction sites, {} transports, {} projectiles, {} treasuries)",
                     industry_buildings.len(),
                     stations.len(),
                     military_buildings.len(),
+                    construction_sites.len(),
                     transports.len(),
                     projectiles.len(),
+                    treasuries.len(),
                 )
             },
+            GameResponse::Statistics(statistics) => write!(f, "Statistics({statistics:?})"),
             GameResponse::GameJoined(player_id, _game_state) => {
                 write!(f, "GameJoined({player_id:?})")
             },
@@ -238,6 +362,9 @@ impl Debug for GameResponse {
             GameResponse::Error(error) => {
                 write!(f, "Error({error:?})")
             },
+            GameResponse::StationExpanded(station) => {
+                write!(f, "StationExpanded({})", station.id())
+            },
             GameResponse::StationRemoved(station_id) => {
                 write!(f, "StationRemoved({station_id:?})")
             },
@@ -254,6 +381,15 @@ impl Debug for GameResponse {
                     projectile_ids.len()
                 )
             },
+            GameResponse::TradeOffered(trade_offer) => {
+                write!(f, "TradeOffered({trade_offer:?})")
+            },
+            GameResponse::TradeOfferAccepted(trade_offer_id) => {
+                write!(f, "TradeOfferAccepted({trade_offer_id:?})")
+            },
+            GameResponse::TradeOfferCancelled(trade_offer_id) => {
+                write!(f, "TradeOfferCancelled({trade_offer_id:?})")
+            },
         }
     }
 }
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::PlayerId;
+use crate::building::resource_transform::ResourceTransform;
+use crate::cargo_amount::CargoAmount;
+use crate::cargo_map::CargoMap;
+use crate::game_time::GameTime;
+use crate::money::Money;
+use crate::players::player_state::PlayerState;
+
+// How often the statistics of each player are sampled
+const SAMPLE_EVERY_SECONDS: f32 = 10.0;
+// Older samples are dropped, so that the history does not grow without bounds
+const MAX_SAMPLES: usize = 360;
+
+/// What a player did during one sampling interval.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct StatisticsSample {
+    // The end of the interval
+    at:                GameTime,
+    produced:          CargoMap,
+    consumed:          CargoMap,
+    cargo_delivered:   CargoAmount,
+    buildings_built:   u32,
+    projectiles_fired: u32,
+    // At the end of the interval
+    treasury:          Money,
+}
+
+impl Debug for StatisticsSample {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} produced {:?} consumed {:?} delivered {:?} built {} fired {} treasury {:?}",
+            self.at,
+            self.produced,
+            self.consumed,
+            self.cargo_delivered,
+            self.buildings_built,
+            self.projectiles_fired,
+            self.treasury,
+        )
+    }
+}
+
+impl StatisticsSample {
+    #[must_use]
+    pub fn at(&self) -> GameTime {
+        self.at
+    }
+
+    #[must_use]
+    pub fn produced(&self) -> &CargoMap {
+        &self.produced
+    }
+
+    #[must_use]
+    pub fn consumed(&self) -> &CargoMap {
+        &self.consumed
+    }
+
+    #[must_use]
+    pub fn cargo_delivered(&self) -> CargoAmount {
+        self.cargo_delivered
+    }
+
+    #[must_use]
+    pub fn buildings_built(&self) -> u32 {
+        self.buildings_built
+    }
+
+    #[must_use]
+    pub fn projectiles_fired(&self) -> u32 {
+        self.projectiles_fired
+    }
+
+    #[must_use]
+    pub fn treasury(&self) -> Money {
+        self.treasury
+    }
+}
+
+/// The history of what each player has been doing, sampled at regular intervals of game time.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct StatisticsState {
+    history:         HashMap<PlayerId, VecDeque<StatisticsSample>>,
+    // Accumulated since the last sample was taken
+    current:         HashMap<PlayerId, StatisticsSample>,
+    last_sampled_at: GameTime,
+}
+
+impl Debug for StatisticsState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "StatisticsState({} players, {} samples)",
+            self.history.len(),
+            self.history.values().map(VecDeque::len).sum::<usize>()
+        )
+    }
+}
+
+impl StatisticsState {
+    /// The samples of the player, the oldest first.
+    #[must_use]
+    pub fn history(&self, player_id: PlayerId) -> impl IntoIterator<Item = &StatisticsSample> {
+        self.history.get(&player_id).into_iter().flatten()
+    }
+
+    pub(crate) fn record_transformed(
+        &mut self,
+        player_id: PlayerId,
+        transformed: &ResourceTransform,
+    ) {
+        let current = self.current.entry(player_id).or_default();
+        for item in &transformed.inputs {
+            if item.amount > CargoAmount::ZERO {
+                current.consumed.add(item.resource, item.amount);
+            }
+        }
+        for item in &transformed.outputs {
+            if item.amount > CargoAmount::ZERO {
+                current.produced.add(item.resource, item.amount);
+            }
+        }
+    }
+
+    pub(crate) fn record_delivered(&mut self, player_id: PlayerId, cargo: CargoAmount) {
+        self.current.entry(player_id).or_default().cargo_delivered += cargo;
+    }
+
+    pub(crate) fn record_building_built(&mut self, player_id: PlayerId) {
+        self.current.entry(player_id).or_default().buildings_built += 1;
+    }
+
+    pub(crate) fn record_projectile_fired(&mut self, player_id: PlayerId) {
+        self.current.entry(player_id).or_default().projectiles_fired += 1;
+    }
+
+    // Closes the current interval if it has run long enough
+    pub(crate) fn advance_time(&mut self, new_game_time: GameTime, players: &PlayerState) {
+        if (new_game_time - self.last_sampled_at).to_seconds() < SAMPLE_EVERY_SECONDS {
+            return;
+        }
+        for player_id in players.ids() {
+            let mut sample = self.current.remove(&player_id).unwrap_or_default();
+            sample.at = new_game_time;
+            sample.treasury = players.treasury(player_id);
+            let history = self.history.entry(player_id).or_default();
+            history.push_back(sample);
+            if history.len() > MAX_SAMPLES {
+                history.pop_front();
+            }
+        }
+        self.current.clear();
+        self.last_sampled_at = new_game_time;
+    }
+}
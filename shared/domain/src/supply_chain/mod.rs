@@ -3,6 +3,8 @@ use log::error;
 use crate::building::industry_type::IndustryType;
 use crate::resource_type::ResourceType;
 
+pub mod throughput;
+
 // TODO: Support custom supply chains - one WW1 one, and one peaceful "just build industry" one
 #[derive(Debug, PartialEq, Clone)]
 pub struct SupplyChain {}
@@ -56,6 +58,14 @@ impl SupplyChain {
                     IndustryType::ConstructionYard,
                 ]
             },
+            (ResourceType::Steel, IndustryType::TrainFactory) => {
+                vec![
+                    IndustryType::IronMine,
+                    IndustryType::CoalMine,
+                    IndustryType::SteelMill,
+                    IndustryType::TrainFactory,
+                ]
+            },
             (ResourceType::ArtilleryWeapons, IndustryType::MilitaryBase) => {
                 vec![
                     IndustryType::CoalMine,
@@ -0,0 +1,851 @@
+use std::collections::HashMap;
+
+use crate::building::building_state::BuildingState;
+use crate::building::industry_building_info::IndustryBuildingInfo;
+use crate::building::resource_transform::ResourceTransformItem;
+use crate::game_state::GameState;
+use crate::game_time::GameTimeDiff;
+use crate::metrics::NoopMetrics;
+use crate::resource_type::ResourceType;
+use crate::transport::advancement::tile_track_velocity;
+use crate::transport::cargo_processing::time_for_processing;
+use crate::transport::movement_orders::MovementOrderLocation;
+use crate::transport::tile_track::TileTrack;
+use crate::transport::track_pathfinding::find_route_to;
+use crate::transport::transport_type::TransportType;
+use crate::transport::transport_velocity::TransportVelocity;
+use crate::{IndustryBuildingId, PlayerId, StationId};
+
+// Enough for the flows to propagate through even the longest supply chains
+const MAX_PASSES: usize = 16;
+const CONVERGED_WHEN_CHANGE_BELOW: f32 = 0.001;
+
+type Link = (StationId, ResourceType, StationId);
+
+/// What limits the cargo flowing along a link.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Bottleneck {
+    /// The industries at the loading station do not produce more.
+    Supply,
+    /// The industries at the unloading station cannot use up more.
+    Demand,
+    /// The trains serving the link cannot carry more.
+    Trains,
+    /// The stations are not connected by tracks.
+    NoRoute,
+}
+
+/// The steady-state flow of one resource from one station to another, in cargo per second.
+#[derive(Clone, Debug)]
+pub struct LinkThroughput {
+    from_station_id:    StationId,
+    resource:           ResourceType,
+    to_station_id:      StationId,
+    // For a cargo train going there and back, including loading and unloading
+    round_trip:         Option<GameTimeDiff>,
+    supply_per_second:  f32,
+    // Infinite for warehouses, as they never stop accepting cargo
+    demand_per_second:  f32,
+    carried_per_second: f32,
+    trains:             usize,
+    recommended_trains: usize,
+}
+
+impl LinkThroughput {
+    #[must_use]
+    pub fn from_station_id(&self) -> StationId {
+        self.from_station_id
+    }
+
+    #[must_use]
+    pub fn resource(&self) -> ResourceType {
+        self.resource
+    }
+
+    #[must_use]
+    pub fn to_station_id(&self) -> StationId {
+        self.to_station_id
+    }
+
+    #[must_use]
+    pub fn round_trip(&self) -> Option<GameTimeDiff> {
+        self.round_trip
+    }
+
+    #[must_use]
+    pub fn supply_per_second(&self) -> f32 {
+        self.supply_per_second
+    }
+
+    #[must_use]
+    pub fn demand_per_second(&self) -> f32 {
+        self.demand_per_second
+    }
+
+    #[must_use]
+    pub fn carried_per_second(&self) -> f32 {
+        self.carried_per_second
+    }
+
+    #[must_use]
+    pub fn throughput_per_second(&self) -> f32 {
+        self.supply_per_second
+            .min(self.demand_per_second)
+            .min(self.carried_per_second)
+    }
+
+    #[must_use]
+    pub fn bottleneck(&self) -> Bottleneck {
+        if self.round_trip.is_none() {
+            Bottleneck::NoRoute
+        } else if self.carried_per_second < self.supply_per_second.min(self.demand_per_second) {
+            Bottleneck::Trains
+        } else if self.supply_per_second <= self.demand_per_second {
+            Bottleneck::Supply
+        } else {
+            Bottleneck::Demand
+        }
+    }
+
+    /// The trains currently serving the link.
+    #[must_use]
+    pub fn trains(&self) -> usize {
+        self.trains
+    }
+
+    /// How many cargo trains are needed so that they never limit the link, even once the
+    /// industries upstream are producing at full speed.
+    #[must_use]
+    pub fn recommended_trains(&self) -> usize {
+        self.recommended_trains
+    }
+}
+
+/// The steady-state throughput of the links in a player's network of industries, stations and
+/// transports.
+///
+/// Later: Industries that cannot ship out their outputs also stop consuming their inputs, which
+/// we do not propagate back upstream yet.
+#[derive(Clone, Debug, Default)]
+pub struct ThroughputAnalysis {
+    links: Vec<LinkThroughput>,
+}
+
+impl ThroughputAnalysis {
+    /// Analyses the links currently served by the player's transports.
+    #[must_use]
+    pub fn analyse(player_id: PlayerId, game_state: &GameState) -> Self {
+        Self::analyse_with_planned(player_id, game_state, [])
+    }
+
+    /// Like `analyse`, but also includes the `planned` links, even if no transports serve them
+    /// yet.
+    #[must_use]
+    pub fn analyse_with_planned(
+        player_id: PlayerId,
+        game_state: &GameState,
+        planned: impl IntoIterator<Item = Link>,
+    ) -> Self {
+        let buildings = game_state.building_state();
+        let mut routes = Routes::new(buildings);
+
+        // In the order the links were first seen, so that the results are stable
+        let mut links: Vec<Link> = vec![];
+        let mut carried: HashMap<Link, (f32, usize)> = HashMap::new();
+        for transport in game_state
+            .transport_state()
+            .find_players_transports(player_id)
+        {
+            let transport_type = transport.transport_type();
+            let stops = transport
+                .movement_orders()
+                .into_iter()
+                .filter(|order| !order.is_pass_through())
+                .filter_map(|order| {
+                    match order.go_to {
+                        MovementOrderLocation::Station(station_id) => Some(station_id),
+                        MovementOrderLocation::Waypoint(_) => None,
+                    }
+                })
+                .collect::<Vec<_>>();
+            if stops.len() < 2 {
+                continue;
+            }
+
+            let cycle = routes.cycle_time(transport_type, &stops);
+            let capacity = transport_type.cargo_capacity();
+            for (index, from_station_id) in stops.iter().enumerate() {
+                let to_station_id = stops[(index + 1) % stops.len()];
+                for resource in capacity.resource_types_present() {
+                    if !is_link(buildings, *from_station_id, resource, to_station_id) {
+                        continue;
+                    }
+                    let link = (*from_station_id, resource, to_station_id);
+                    let (carried_per_second, trains) = carried.entry(link).or_insert_with(|| {
+                        links.push(link);
+                        (0.0, 0)
+                    });
+                    *carried_per_second += cycle.map_or(0.0, |cycle| {
+                        capacity.get(resource).as_f32() / cycle.to_seconds()
+                    });
+                    *trains += 1;
+                }
+            }
+        }
+        for link in planned {
+            carried.entry(link).or_insert_with(|| {
+                links.push(link);
+                (0.0, 0)
+            });
+        }
+
+        let flows = Flows::new(player_id, buildings, &links, &carried);
+        let utilisation = flows.steady_state_utilisation();
+        let full_utilisation = flows.full_utilisation();
+
+        let links = links
+            .iter()
+            .map(|link| {
+                let (from_station_id, resource, to_station_id) = *link;
+                let (carried_per_second, trains) = carried[link];
+                let train_type = TransportType::cargo_train(resource);
+                let round_trip = routes.cycle_time(&train_type, &[from_station_id, to_station_id]);
+                let demand_per_second = flows.demand(link);
+                let recommended_trains = round_trip.map_or(0, |round_trip| {
+                    let per_train = train_type.cargo_capacity().get(resource).as_f32()
+                        / round_trip.to_seconds();
+                    let needed = flows.supply(link, &full_utilisation).min(demand_per_second);
+                    trains_needed(needed, per_train)
+                });
+                LinkThroughput {
+                    from_station_id,
+                    resource,
+                    to_station_id,
+                    round_trip,
+                    supply_per_second: flows.supply(link, &utilisation),
+                    demand_per_second,
+                    carried_per_second,
+                    trains,
+                    recommended_trains,
+                }
+            })
+            .collect();
+
+        Self { links }
+    }
+
+    #[must_use]
+    pub fn links(&self) -> &[LinkThroughput] {
+        &self.links
+    }
+
+    #[must_use]
+    pub fn find_link(
+        &self,
+        from_station_id: StationId,
+        resource: ResourceType,
+        to_station_id: StationId,
+    ) -> Option<&LinkThroughput> {
+        self.links.iter().find(|link| {
+            link.from_station_id == from_station_id
+                && link.resource == resource
+                && link.to_station_id == to_station_id
+        })
+    }
+
+    /// The links that carry less than their industries could exchange, the worst first.
+    #[must_use]
+    pub fn bottlenecks(&self) -> Vec<&LinkThroughput> {
+        let mut result = self
+            .links
+            .iter()
+            .filter(|link| matches!(link.bottleneck(), Bottleneck::Trains | Bottleneck::NoRoute))
+            .collect::<Vec<_>>();
+        result.sort_by(|a, b| {
+            let shortfall = |link: &LinkThroughput| {
+                link.supply_per_second.min(link.demand_per_second) - link.carried_per_second
+            };
+            shortfall(b).total_cmp(&shortfall(a))
+        });
+        result
+    }
+}
+
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn trains_needed(per_second: f32, per_train: f32) -> usize {
+    if per_second.is_finite() && per_train > 0.0 {
+        ((per_second / per_train).ceil() as usize).max(1)
+    } else {
+        1
+    }
+}
+
+fn producers(
+    buildings: &BuildingState,
+    station_id: StationId,
+    resource: ResourceType,
+) -> impl Iterator<Item = &IndustryBuildingInfo> {
+    buildings
+        .find_linked_industry_buildings(station_id)
+        .into_iter()
+        .filter(move |industry| industry.industry_type().produces(resource))
+}
+
+fn consumers(
+    buildings: &BuildingState,
+    station_id: StationId,
+    resource: ResourceType,
+) -> impl Iterator<Item = &IndustryBuildingInfo> {
+    buildings
+        .find_linked_industry_buildings(station_id)
+        .into_iter()
+        .filter(move |industry| industry.industry_type().consumes(resource))
+}
+
+fn is_link(
+    buildings: &BuildingState,
+    from_station_id: StationId,
+    resource: ResourceType,
+    to_station_id: StationId,
+) -> bool {
+    producers(buildings, from_station_id, resource)
+        .next()
+        .is_some()
+        && consumers(buildings, to_station_id, resource)
+            .next()
+            .is_some()
+}
+
+fn rate_of(items: &[ResourceTransformItem], resource: ResourceType) -> f32 {
+    items
+        .iter()
+        .filter(|item| item.resource == resource)
+        .map(|item| item.amount.as_f32())
+        .sum()
+}
+
+// How the cargo flows between the industries along the links, each link taking an equal share of
+// what is produced at its loading station and of what is needed at its unloading station
+struct Flows<'a> {
+    player_id: PlayerId,
+    buildings: &'a BuildingState,
+    links:     &'a [Link],
+    carried:   &'a HashMap<Link, (f32, usize)>,
+    sharing:   HashMap<(StationId, ResourceType, bool), usize>,
+}
+
+impl<'a> Flows<'a> {
+    fn new(
+        player_id: PlayerId,
+        buildings: &'a BuildingState,
+        links: &'a [Link],
+        carried: &'a HashMap<Link, (f32, usize)>,
+    ) -> Self {
+        let mut sharing = HashMap::new();
+        for (from_station_id, resource, to_station_id) in links {
+            *sharing
+                .entry((*from_station_id, *resource, true))
+                .or_default() += 1;
+            *sharing
+                .entry((*to_station_id, *resource, false))
+                .or_default() += 1;
+        }
+        Self {
+            player_id,
+            buildings,
+            links,
+            carried,
+            sharing,
+        }
+    }
+
+    fn industries(&self) -> impl Iterator<Item = &'a IndustryBuildingInfo> {
+        self.buildings
+            .find_industry_buildings_by_owner(self.player_id)
+            .into_iter()
+    }
+
+    #[expect(clippy::cast_precision_loss)]
+    fn supply(&self, link: &Link, utilisation: &HashMap<IndustryBuildingId, f32>) -> f32 {
+        let (from_station_id, resource, _) = *link;
+        // Industries split their output evenly between all the stations serving them
+        let produced: f32 = producers(self.buildings, from_station_id, resource)
+            .map(|industry| {
+                let stations = self.buildings.find_linked_stations(industry.id()).len();
+                rate_of(&industry.transform_per_second().outputs, resource)
+                    * self.buildings.power_grids().production_coef(industry.id())
+                    * utilisation.get(&industry.id()).copied().unwrap_or(0.0)
+                    / stations.max(1) as f32
+            })
+            .sum();
+        produced / self.sharing[&(from_station_id, resource, true)] as f32
+    }
+
+    #[expect(clippy::cast_precision_loss)]
+    fn demand(&self, link: &Link) -> f32 {
+        let (_, resource, to_station_id) = *link;
+        let needed: f32 = consumers(self.buildings, to_station_id, resource)
+            .map(|industry| {
+                // Warehouses do not use up their inputs, but store them
+                let rate = rate_of(&industry.transform_per_second().inputs, resource);
+                if rate > 0.0 { rate } else { f32::INFINITY }
+            })
+            .sum();
+        needed / self.sharing[&(to_station_id, resource, false)] as f32
+    }
+
+    fn flow(&self, link: &Link, utilisation: &HashMap<IndustryBuildingId, f32>) -> f32 {
+        let (carried_per_second, _) = self.carried[link];
+        self.supply(link, utilisation)
+            .min(self.demand(link))
+            .min(carried_per_second)
+    }
+
+    // As if every industry was producing at full speed
+    fn full_utilisation(&self) -> HashMap<IndustryBuildingId, f32> {
+        self.industries()
+            .map(|industry| (industry.id(), 1.0))
+            .collect()
+    }
+
+    // Industries without inputs run at full speed, while the others only as fast as their
+    // scarcest input arrives
+    #[expect(clippy::cast_precision_loss)]
+    fn steady_state_utilisation(&self) -> HashMap<IndustryBuildingId, f32> {
+        let is_source = |industry: &IndustryBuildingInfo| {
+            !industry.is_under_construction() && industry.transform_per_second().inputs.is_empty()
+        };
+        let mut utilisation = self
+            .industries()
+            .map(|industry| (industry.id(), if is_source(industry) { 1.0 } else { 0.0 }))
+            .collect::<HashMap<_, _>>();
+
+        for _ in 0 .. MAX_PASSES {
+            let mut inflow: HashMap<(IndustryBuildingId, ResourceType), f32> = HashMap::new();
+            for link in self.links {
+                let (_, resource, to_station_id) = *link;
+                let flow = self.flow(link, &utilisation);
+                let receiving =
+                    consumers(self.buildings, to_station_id, resource).collect::<Vec<_>>();
+                for industry in &receiving {
+                    *inflow.entry((industry.id(), resource)).or_default() +=
+                        flow / receiving.len() as f32;
+                }
+            }
+
+            let mut changed = false;
+            for industry in self.industries() {
+                let transform = industry.transform_per_second();
+                if is_source(industry) || industry.is_under_construction() {
+                    continue;
+                }
+                let new_utilisation = transform
+                    .inputs
+                    .iter()
+                    .filter(|item| item.amount.as_f32() > 0.0)
+                    .map(|item| {
+                        inflow
+                            .get(&(industry.id(), item.resource))
+                            .copied()
+                            .unwrap_or(0.0)
+                            / item.amount.as_f32()
+                    })
+                    .fold(1.0f32, f32::min);
+                let previous = utilisation.insert(industry.id(), new_utilisation);
+                if previous.is_none_or(|previous| {
+                    (previous - new_utilisation).abs() > CONVERGED_WHEN_CHANGE_BELOW
+                }) {
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        utilisation
+    }
+}
+
+// The shortest routes between the stations, looked up only once for each pair
+struct Routes<'a> {
+    buildings: &'a BuildingState,
+    routes:    HashMap<(StationId, StationId), Option<Vec<TileTrack>>>,
+}
+
+impl<'a> Routes<'a> {
+    fn new(buildings: &'a BuildingState) -> Self {
+        Self {
+            buildings,
+            routes: HashMap::new(),
+        }
+    }
+
+    fn route(&mut self, from: StationId, to: StationId) -> Option<&Vec<TileTrack>> {
+        let buildings = self.buildings;
+        self.routes
+            .entry((from, to))
+            .or_insert_with(|| {
+                buildings
+                    .find_station(from)?
+                    .station_exit_tile_tracks()
+                    .into_iter()
+                    .filter_map(|tile_track| {
+                        find_route_to(
+                            tile_track,
+                            MovementOrderLocation::Station(to),
+                            buildings,
+                            |_| false,
+                            &NoopMetrics::default(),
+                        )
+                    })
+                    .min_by(|a, b| route_length(a).total_cmp(&route_length(b)))
+            })
+            .as_ref()
+    }
+
+    fn travel_time(
+        &mut self,
+        transport_type: &TransportType,
+        from: StationId,
+        to: StationId,
+    ) -> Option<GameTimeDiff> {
+        let buildings = self.buildings;
+        let route = self.route(from, to)?;
+        let seconds = route
+            .iter()
+            .map(|tile_track| {
+                let velocity = transport_type.max_velocity().tiles_per_second().min(
+                    tile_track_velocity(transport_type, *tile_track, buildings).tiles_per_second(),
+                );
+                (tile_track.track_type.length() / TransportVelocity::new(velocity)).to_seconds()
+            })
+            .sum();
+        Some(GameTimeDiff::from_seconds(seconds))
+    }
+
+    // Visiting all the stops once, and loading & unloading a full load of cargo along the way
+    fn cycle_time(
+        &mut self,
+        transport_type: &TransportType,
+        stops: &[StationId],
+    ) -> Option<GameTimeDiff> {
+        let processing = time_for_processing(&transport_type.cargo_capacity()).to_seconds() * 2.0;
+        let mut seconds = processing;
+        for (index, from) in stops.iter().enumerate() {
+            let to = stops[(index + 1) % stops.len()];
+            seconds += self.travel_time(transport_type, *from, to)?.to_seconds();
+        }
+        Some(GameTimeDiff::from_seconds(seconds))
+    }
+}
+
+fn route_length(route: &[TileTrack]) -> f32 {
+    route
+        .iter()
+        .map(|tile_track| tile_track.track_type.length().to_f32())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use shared_util::direction_xz::DirectionXZ;
+
+    use super::*;
+    use crate::building::industry_type::IndustryType;
+    use crate::building::station_info::StationInfo;
+    use crate::building::station_type::StationType;
+    use crate::building::track_info::TrackInfo;
+    use crate::map_level::map_level::{Height, MapLevel, TerrainType};
+    use crate::map_level::terrain::Terrain;
+    use crate::map_level::zoning::Zoning;
+    use crate::scenario::Scenario;
+    use crate::tile_coords_xz::TileCoordsXZ;
+    use crate::transport::movement_orders::{MovementOrder, MovementOrders};
+    use crate::transport::progress_within_tile::ProgressWithinTile;
+    use crate::transport::track_type::TrackType;
+    use crate::transport::transport_info::TransportInfo;
+    use crate::transport::transport_location::TransportLocation;
+    use crate::water::Water;
+    use crate::{MapId, ScenarioId, TransportId};
+
+    struct SupplyChainScenario {
+        game_state: GameState,
+        player_id:  PlayerId,
+        // The coal mine, the coal-to-oil plant and the oil refinery
+        industries: [IndustryBuildingId; 3],
+        // The station serving each of the industries
+        stations:   [StationId; 3],
+    }
+
+    // Coal is mined in the west, turned into oil in the middle and refined in the east, with the
+    // stations along a straight line of tracks
+    fn supply_chain_scenario() -> SupplyChainScenario {
+        let size_x = 130;
+        let size_z = 10;
+        let terrain = Terrain::flat(size_x, size_z, Height::from_u8(1), TerrainType::Grass);
+        let water = Water::new(Height::from_u8(0), Height::from_u8(1));
+        let map_level = MapLevel::new(
+            MapId("test".to_string()),
+            terrain,
+            water.expect("valid water"),
+            Zoning::new(size_x, size_z),
+        );
+        let scenario = Scenario {
+            scenario_id: ScenarioId("test".to_string()),
+            players: vec![],
+            map_level,
+        };
+        let mut game_state = GameState::from_scenario(scenario, false);
+        let player_id = PlayerId::random();
+        let buildings = game_state.building_state_mut();
+
+        let mut industries = vec![];
+        let mut stations = vec![];
+        for (x, industry_type) in [
+            (5, IndustryType::CoalMine),
+            (65, IndustryType::CoalToOilPlant),
+            (125, IndustryType::OilRefinery),
+        ] {
+            let industry = IndustryBuildingInfo::new(
+                player_id,
+                IndustryBuildingId::random(),
+                TileCoordsXZ::new(x, 3),
+                industry_type,
+            );
+            industries.push(industry.id());
+            buildings.append_industry_building(industry);
+            let station = StationInfo::new(
+                player_id,
+                StationId::random(),
+                TileCoordsXZ::new(x - 2, 5),
+                StationType::WE_1_4,
+            );
+            stations.push(station.id());
+            buildings.append_station(station);
+        }
+        buildings.append_tracks(
+            (7 .. 63)
+                .chain(67 .. 123)
+                .map(|x| TrackInfo::new(player_id, TileCoordsXZ::new(x, 5), TrackType::WestEast))
+                .collect(),
+        );
+
+        SupplyChainScenario {
+            game_state,
+            player_id,
+            industries: industries.try_into().expect("three industries"),
+            stations: stations.try_into().expect("three stations"),
+        }
+    }
+
+    fn cargo_train(
+        player_id: PlayerId,
+        resource: ResourceType,
+        from: StationId,
+        to: StationId,
+    ) -> TransportInfo {
+        let location = TransportLocation::new(
+            vec![TileTrack {
+                tile:        TileCoordsXZ::new(10, 5),
+                track_type:  TrackType::WestEast,
+                pointing_in: DirectionXZ::East,
+            }],
+            ProgressWithinTile::just_entering(),
+        );
+        let mut movement_orders = MovementOrders::one(MovementOrder::stop_at_station(from));
+        movement_orders.push(MovementOrder::stop_at_station(to));
+        TransportInfo::new(
+            TransportId::random(),
+            player_id,
+            TransportType::cargo_train(resource),
+            location,
+            movement_orders,
+        )
+    }
+
+    fn rates(
+        buildings: &BuildingState,
+        industry_id: IndustryBuildingId,
+        resource: ResourceType,
+    ) -> (f32, f32) {
+        let transform = buildings
+            .find_industry_building(industry_id)
+            .expect("industry exists")
+            .transform_per_second();
+        (
+            rate_of(&transform.inputs, resource),
+            rate_of(&transform.outputs, resource),
+        )
+    }
+
+    fn link(round_trip: Option<f32>, supply: f32, demand: f32, carried: f32) -> LinkThroughput {
+        LinkThroughput {
+            from_station_id:    StationId::random(),
+            resource:           ResourceType::Coal,
+            to_station_id:      StationId::random(),
+            round_trip:         round_trip.map(GameTimeDiff::from_seconds),
+            supply_per_second:  supply,
+            demand_per_second:  demand,
+            carried_per_second: carried,
+            trains:             1,
+            recommended_trains: 1,
+        }
+    }
+
+    #[test]
+    fn test_bottleneck_is_the_scarcest_of_supply_demand_and_trains() {
+        assert_eq!(link(None, 1.0, 1.0, 1.0).bottleneck(), Bottleneck::NoRoute);
+        assert_eq!(
+            link(Some(60.0), 0.2, 0.4, 0.1).bottleneck(),
+            Bottleneck::Trains
+        );
+        assert_eq!(
+            link(Some(60.0), 0.2, 0.4, 0.3).bottleneck(),
+            Bottleneck::Supply
+        );
+        assert_eq!(
+            link(Some(60.0), 0.4, 0.2, 0.3).bottleneck(),
+            Bottleneck::Demand
+        );
+        assert_eq!(
+            link(Some(60.0), 0.2, f32::INFINITY, 0.3).bottleneck(),
+            Bottleneck::Supply
+        );
+        assert!((link(Some(60.0), 0.4, 0.2, 0.3).throughput_per_second() - 0.2).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_trains_needed() {
+        assert_eq!(trains_needed(0.2, 0.1), 2);
+        assert_eq!(trains_needed(0.25, 0.1), 3);
+        assert_eq!(trains_needed(0.0, 0.1), 1);
+        assert_eq!(trains_needed(f32::INFINITY, 0.1), 1);
+    }
+
+    #[test]
+    fn test_flows_share_supply_and_demand_between_links() {
+        let scenario = supply_chain_scenario();
+        let buildings = scenario.game_state.building_state();
+        let [mine, plant, refinery] = scenario.industries;
+        let [a, b, c] = scenario.stations;
+        let coal = (a, ResourceType::Coal, b);
+        let oil = (b, ResourceType::Oil, c);
+        // Nothing at the refinery uses up coal, but the link still takes its share of the supply
+        let coal_to_refinery = (a, ResourceType::Coal, c);
+        let links = vec![coal, oil, coal_to_refinery];
+        let carried = links
+            .iter()
+            .map(|link| (*link, (100.0, 1)))
+            .collect::<HashMap<_, _>>();
+        let flows = Flows::new(scenario.player_id, buildings, &links, &carried);
+        let full_utilisation = flows.full_utilisation();
+
+        let (_, mined) = rates(buildings, mine, ResourceType::Coal);
+        let (coal_needed, _) = rates(buildings, plant, ResourceType::Coal);
+        let (oil_needed, _) = rates(buildings, refinery, ResourceType::Oil);
+        assert!((flows.supply(&coal, &full_utilisation) - mined / 2.0).abs() < 0.001);
+        assert!((flows.demand(&coal) - coal_needed).abs() < 0.001);
+        assert!((flows.demand(&oil) - oil_needed).abs() < 0.001);
+        assert!(flows.demand(&coal_to_refinery).abs() < 0.001);
+        assert!(flows.supply(&coal, &HashMap::new()).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_steady_state_utilisation_propagates_along_the_chain() {
+        let scenario = supply_chain_scenario();
+        let buildings = scenario.game_state.building_state();
+        let [mine, plant, refinery] = scenario.industries;
+        let [a, b, c] = scenario.stations;
+        let coal = (a, ResourceType::Coal, b);
+        let oil = (b, ResourceType::Oil, c);
+        let links = vec![coal, oil];
+        let (_, mined) = rates(buildings, mine, ResourceType::Coal);
+        let (coal_needed, _) = rates(buildings, plant, ResourceType::Coal);
+        let (_, oil_produced) = rates(buildings, plant, ResourceType::Oil);
+        let (oil_needed, _) = rates(buildings, refinery, ResourceType::Oil);
+
+        // The coal trains carry half of what the plant needs, the oil trains all there is
+        let carried = HashMap::from([(coal, (coal_needed / 2.0, 1)), (oil, (100.0, 1))]);
+        let flows = Flows::new(scenario.player_id, buildings, &links, &carried);
+        let utilisation = flows.steady_state_utilisation();
+        let expected_plant = (coal_needed / 2.0).min(mined) / coal_needed;
+        let expected_refinery = (oil_produced * expected_plant).min(oil_needed) / oil_needed;
+        assert!((utilisation[&mine] - 1.0).abs() < 0.001);
+        assert!((utilisation[&plant] - expected_plant).abs() < 0.001);
+        assert!((utilisation[&refinery] - expected_refinery).abs() < 0.001);
+        assert!(expected_refinery > 0.0);
+
+        // Without any trains, only the mine is running
+        let carried = HashMap::from([(coal, (0.0, 0)), (oil, (0.0, 0))]);
+        let flows = Flows::new(scenario.player_id, buildings, &links, &carried);
+        let utilisation = flows.steady_state_utilisation();
+        assert!((utilisation[&mine] - 1.0).abs() < 0.001);
+        assert!(utilisation[&plant].abs() < 0.001);
+        assert!(utilisation[&refinery].abs() < 0.001);
+    }
+
+    #[test]
+    fn test_routes_follow_the_tracks_between_stations() {
+        let mut scenario = supply_chain_scenario();
+        let [a, b, c] = scenario.stations;
+        let train = TransportType::cargo_train(ResourceType::Coal);
+        let mut routes = Routes::new(scenario.game_state.building_state());
+        let to_b = routes.travel_time(&train, a, b).expect("route to b");
+        let to_c = routes.travel_time(&train, a, c).expect("route to c");
+        assert!(to_b.to_seconds() > 0.0);
+        assert!(to_c > to_b);
+        let round_trip = routes.cycle_time(&train, &[a, b]).expect("round trip");
+        assert!(round_trip.to_seconds() > 2.0 * to_b.to_seconds());
+
+        let gap = TrackInfo::new(
+            scenario.player_id,
+            TileCoordsXZ::new(90, 5),
+            TrackType::WestEast,
+        );
+        scenario
+            .game_state
+            .building_state_mut()
+            .attempt_to_remove_tracks(scenario.player_id, &[gap.id()])
+            .expect("track removed");
+        let mut routes = Routes::new(scenario.game_state.building_state());
+        assert!(routes.route(a, b).is_some());
+        assert!(routes.route(a, c).is_none());
+        assert!(routes.cycle_time(&train, &[a, c]).is_none());
+    }
+
+    #[test]
+    fn test_recommended_trains_for_a_supply_chain() {
+        let mut scenario = supply_chain_scenario();
+        let [a, b, c] = scenario.stations;
+        scenario.game_state.upsert_transport(cargo_train(
+            scenario.player_id,
+            ResourceType::Coal,
+            a,
+            b,
+        ));
+
+        let analysis = ThroughputAnalysis::analyse_with_planned(
+            scenario.player_id,
+            &scenario.game_state,
+            [(b, ResourceType::Oil, c)],
+        );
+        assert_eq!(analysis.links().len(), 2);
+        let coal = analysis
+            .find_link(a, ResourceType::Coal, b)
+            .expect("coal link");
+        // A single train carries only about half of what the mine produces
+        assert_eq!(coal.trains(), 1);
+        assert_eq!(coal.bottleneck(), Bottleneck::Trains);
+        assert_eq!(coal.recommended_trains(), 2);
+        let oil = analysis
+            .find_link(b, ResourceType::Oil, c)
+            .expect("oil link");
+        assert_eq!(oil.trains(), 0);
+        // The plant only makes as much oil as the coal train brings it
+        assert!((oil.supply_per_second() - coal.carried_per_second()).abs() < 0.001);
+        assert_eq!(oil.bottleneck(), Bottleneck::Trains);
+        // Recommended as if the plant was already getting all the coal it needs
+        assert_eq!(oil.recommended_trains(), 2);
+    }
+}
@@ -117,6 +117,36 @@ impl TileCoverage {
         }
     }
 
+    // The smallest rectangle that covers both of these
+    #[must_use]
+    pub fn bounding_box_with(&self, other: &Self) -> Self {
+        TileCoverage::Rectangular {
+            north_west_inclusive: TileCoordsXZ::new(
+                self.min_x().min(other.min_x()),
+                self.min_z().min(other.min_z()),
+            ),
+            south_east_inclusive: TileCoordsXZ::new(
+                self.max_x().max(other.max_x()),
+                self.max_z().max(other.max_z()),
+            ),
+        }
+    }
+
+    #[must_use]
+    pub const fn north_west_corner(&self) -> TileCoordsXZ {
+        TileCoordsXZ::new(self.min_x(), self.min_z())
+    }
+
+    #[must_use]
+    pub const fn size_x(&self) -> TileDistance {
+        self.max_x() - self.min_x() + 1
+    }
+
+    #[must_use]
+    pub const fn size_z(&self) -> TileDistance {
+        self.max_z() - self.min_z() + 1
+    }
+
     const fn min_x(&self) -> TileDistance {
         match self {
             TileCoverage::Single(tile) => tile.x,
@@ -157,6 +187,25 @@ impl TileCoverage {
         }
     }
 
+    // The tiles whose closest tile of this coverage is exactly `distance` away
+    #[must_use]
+    pub fn manhattan_ring(&self, distance: TileDistance) -> Vec<TileCoordsXZ> {
+        let mut result = vec![];
+        for x in self.min_x() - distance ..= self.max_x() + distance {
+            let gap_x = (self.min_x() - x).max(x - self.max_x()).max(0);
+            let remaining = distance - gap_x;
+            if remaining == 0 {
+                for z in self.min_z() ..= self.max_z() {
+                    result.push(TileCoordsXZ::new(x, z));
+                }
+            } else {
+                result.push(TileCoordsXZ::new(x, self.min_z() - remaining));
+                result.push(TileCoordsXZ::new(x, self.max_z() + remaining));
+            }
+        }
+        result
+    }
+
     #[must_use]
     pub const fn manhattan_distance_between_closest_tiles(
         a: &TileCoverage,
@@ -1,21 +1,34 @@
 use log::{debug, error};
 
+use crate::building::building_info::{WithBuildingDynamicInfo, WithBuildingDynamicInfoMut};
 use crate::building::building_state::BuildingState;
+use crate::cargo_amount::CargoAmount;
 use crate::cargo_map::CargoOps;
 use crate::game_time::GameTimeDiff;
 use crate::metrics::Metrics;
+use crate::money::Money;
 use crate::transport::cargo_processing::cargo_processing_advance;
 use crate::transport::movement_orders::MovementOrderLocation;
 use crate::transport::progress_within_tile::ProgressWithinTile;
+use crate::transport::tile_occupancy::TileOccupancy;
 use crate::transport::tile_track::TileTrack;
 use crate::transport::track_pathfinding::{find_location_tile_tracks, find_route_to};
 use crate::transport::transport_info::{TransportDynamicInfo, TransportInfo};
 use crate::transport::transport_location::TransportLocation;
 use crate::transport::transport_type::TransportType;
+use crate::transport::transport_velocity::TransportVelocity;
+
+// What a transport earned from delivering cargo, and how much cargo it delivered
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub(crate) struct Delivery {
+    pub(crate) income: Money,
+    pub(crate) cargo:  CargoAmount,
+}
 
 fn jump_tile(
     transport_info: &mut TransportInfo,
     building_state: &BuildingState,
+    occupancy: &TileOccupancy,
     metrics: &impl Metrics,
 ) -> Result<(), ()> {
     debug!("Jumping tile: {:?}", transport_info);
@@ -26,6 +39,7 @@ fn jump_tile(
         transport_info.dynamic_info.location.next_tile_in_path(),
         current_order.go_to,
         building_state,
+        occupancy.occupied_by_others(transport_info),
         metrics,
     );
 
@@ -71,7 +85,9 @@ fn at_location(
 fn advance_internal(
     transport_info: &mut TransportInfo,
     building_state: &mut BuildingState,
+    occupancy: &TileOccupancy,
     diff: GameTimeDiff,
+    delivery: &mut Delivery,
     metrics: &impl Metrics,
 ) -> GameTimeDiff {
     if transport_info
@@ -91,8 +107,29 @@ fn advance_internal(
             building_state,
         );
 
-        if at_location {
-            let MovementOrderLocation::Station(station_id) = current_orders.go_to;
+        if at_location && current_orders.is_deploy() {
+            // Deployed units stay where they were sent until they are given other orders
+            if let Some(military_unit) = &mut transport_info.dynamic_info.military_unit {
+                military_unit.set_deployed(true);
+            }
+            GameTimeDiff::ZERO
+        } else if at_location && current_orders.is_pass_through() {
+            // Non-stop orders are fulfilled by just reaching the location, so we continue onwards
+            // immediately, routing towards the next order
+            debug!(
+                "Passed through {current_orders:?}, advancing to next orders: {transport_info:?}"
+            );
+            transport_info
+                .dynamic_info
+                .movement_orders
+                .advance_to_next_order();
+            match jump_tile(transport_info, building_state, occupancy, metrics) {
+                Ok(()) => diff,
+                Err(()) => GameTimeDiff::ZERO,
+            }
+        } else if let (true, MovementOrderLocation::Station(station_id)) =
+            (at_location, current_orders.go_to)
+        {
             let resources_accepted_for_unloading =
                 building_state.resource_types_accepted_by_station(station_id);
             if let Some(station) = building_state.find_station_mut(station_id) {
@@ -105,12 +142,20 @@ fn advance_internal(
 
                 if let Some(ref cargo_to_load) = cargo_loading_result.cargo_to_load {
                     station.remove_cargo(cargo_to_load);
-                    transport_info.add_cargo(cargo_to_load);
+                    transport_info.load_cargo_at(
+                        cargo_to_load,
+                        station.dynamic_info().cargo_age(),
+                        station.reference_tile(),
+                    );
                 }
 
                 if let Some(ref cargo_to_unload) = cargo_loading_result.cargo_to_unload {
-                    station.add_cargo(cargo_to_unload);
-                    transport_info.remove_cargo(cargo_to_unload);
+                    station
+                        .dynamic_info_mut()
+                        .add_aged_cargo(cargo_to_unload, &transport_info.dynamic_info.cargo_age);
+                    delivery.income +=
+                        transport_info.unload_cargo_at(cargo_to_unload, station.reference_tile());
+                    delivery.cargo += cargo_to_unload.total_amount();
                 }
 
                 transport_info.dynamic_info.cargo_processing = cargo_loading_result.new_state;
@@ -131,29 +176,85 @@ fn advance_internal(
                 GameTimeDiff::ZERO
             }
         } else {
-            match jump_tile(transport_info, building_state, metrics) {
+            match jump_tile(transport_info, building_state, occupancy, metrics) {
                 Ok(()) => diff,
                 Err(()) => GameTimeDiff::ZERO,
             }
         }
     } else {
+        transport_info.dynamic_info.velocity = allowed_velocity(transport_info, building_state);
         advance_within_tile(transport_info, diff)
     }
 }
 
-pub fn advance(
+// Electric trains off the grid can only crawl along on their batteries
+const UNPOWERED_ELECTRIC_VELOCITY: f32 = 0.5;
+
+// The transport cannot go faster than the slowest track it is currently on allows
+fn allowed_velocity(
+    transport_info: &TransportInfo,
+    building_state: &BuildingState,
+) -> TransportVelocity {
+    let transport_type = transport_info.transport_type();
+    transport_info
+        .dynamic_info
+        .location
+        .tile_path
+        .iter()
+        .map(|tile_track| tile_track_velocity(transport_type, *tile_track, building_state))
+        .fold(transport_type.max_velocity(), |a, b| {
+            TransportVelocity::new(a.tiles_per_second().min(b.tiles_per_second()))
+        })
+}
+
+// How fast the track allows this type of transport to go, not considering the transport's own
+// maximum velocity
+pub(crate) fn tile_track_velocity(
+    transport_type: &TransportType,
+    tile_track: TileTrack,
+    building_state: &BuildingState,
+) -> TransportVelocity {
+    let grade = building_state.track_grade_at(tile_track.tile, tile_track.track_type);
+    if !transport_type.is_electric() {
+        grade.max_velocity()
+    } else if building_state.is_electrified(tile_track.tile) {
+        grade.electrified_max_velocity()
+    } else {
+        TransportVelocity::new(UNPOWERED_ELECTRIC_VELOCITY)
+    }
+}
+
+// Returns what was earned from delivering cargo
+pub(crate) fn advance(
     transport_info: &mut TransportInfo,
     building_state: &mut BuildingState,
+    occupancy: &TileOccupancy,
     diff: GameTimeDiff,
     metrics: &impl Metrics,
-) {
+) -> Delivery {
+    transport_info.age_cargo(diff);
+    let dynamic_info = &mut transport_info.dynamic_info;
+    // Only deployed if we end up staying at the deploy location during this tick
+    if let Some(military_unit) = &mut dynamic_info.military_unit {
+        military_unit.set_deployed(false);
+    }
+
     let mut remaining = diff;
+    let mut delivery = Delivery::default();
     loop {
-        remaining = advance_internal(transport_info, building_state, remaining, metrics);
+        remaining = advance_internal(
+            transport_info,
+            building_state,
+            occupancy,
+            remaining,
+            &mut delivery,
+            metrics,
+        );
         if remaining == GameTimeDiff::ZERO {
             break;
         }
     }
+    delivery
 }
 
 fn advance_within_tile(transport_info: &mut TransportInfo, diff: GameTimeDiff) -> GameTimeDiff {
@@ -8,8 +8,7 @@ use crate::building::station_info::StationInfo;
 use crate::cargo_map::CargoMap;
 use crate::game_time::GameTimeDiff;
 use crate::resource_type::ResourceType;
-use crate::transport::movement_orders::MovementOrderAction::UnloadAndLoad;
-use crate::transport::movement_orders::{LoadAction, UnloadAction};
+use crate::transport::movement_orders::{LoadAction, MovementOrderAction, UnloadAction};
 use crate::transport::transport_info::TransportInfo;
 
 #[derive(Serialize, Deserialize, PartialEq, Clone, Copy)]
@@ -74,7 +73,15 @@ pub(crate) fn cargo_processing_advance(
         .movement_orders
         .current_order()
         .action;
-    let UnloadAndLoad(unload_action, load_action) = movement_order_action;
+    let (unload_action, load_action) = match movement_order_action {
+        MovementOrderAction::UnloadAndLoad(unload_action, load_action) => {
+            (unload_action, load_action)
+        },
+        // Not expected to happen, as neither pass-through nor deploy orders start cargo processing
+        MovementOrderAction::PassThrough | MovementOrderAction::Deploy(_) => {
+            (UnloadAction::NoUnload, LoadAction::NoLoad)
+        },
+    };
 
     match transport_info.dynamic_info.cargo_processing {
         CargoProcessing::NotStarted => {
@@ -84,9 +91,11 @@ pub(crate) fn cargo_processing_advance(
             if unload_action == UnloadAction::NoUnload {
                 CargoProcessingResult::new(CargoProcessing::Loading, diff, None, None)
             } else {
+                // A full station does not accept more cargo, so it stays on the transport
                 let cargo_to_unload = transport_info
                     .cargo_loaded()
-                    .filter(|(resource, _)| resources_accepted_for_unloading.contains(&resource));
+                    .filter(|(resource, _)| resources_accepted_for_unloading.contains(&resource))
+                    .cap_at(&station.remaining_storage_capacity());
 
                 let (is_finished, remaining, cargo_to_unload) = time_helper(diff, cargo_to_unload);
                 let next_state = if is_finished {
@@ -147,6 +156,6 @@ fn time_helper(
 }
 
 const CARGO_PROCESSED_PER_SECOND: f32 = 1.0f32;
-fn time_for_processing(cargo: &CargoMap) -> GameTimeDiff {
+pub(crate) fn time_for_processing(cargo: &CargoMap) -> GameTimeDiff {
     GameTimeDiff::from_seconds(cargo.total_amount().as_f32() / CARGO_PROCESSED_PER_SECOND)
 }
@@ -3,12 +3,16 @@ pub mod advancement;
 pub mod cargo_processing;
 pub mod movement_orders;
 pub mod progress_within_tile;
+pub mod route_cache;
+pub mod tile_occupancy;
 pub mod tile_track;
+pub mod track_grade;
 pub mod track_length;
 pub mod track_pathfinding;
 pub mod track_planner;
 pub mod track_type;
 pub mod track_type_set;
+pub mod transport_group;
 pub mod transport_info;
 pub mod transport_location;
 pub mod transport_state;
@@ -7,17 +7,20 @@ use std::fmt::{Debug, Formatter};
 use serde::{Deserialize, Serialize};
 use shared_util::non_empty_circular_list::{NonEmptyCircularList, NonEmptyCircularListIterator};
 
-use crate::StationId;
+use crate::tile_coords_xz::TileCoordsXZ;
+use crate::{StationId, WaypointId};
 
 #[derive(Serialize, Deserialize, PartialEq, Copy, Clone)]
 pub enum MovementOrderLocation {
     Station(StationId),
+    Waypoint(WaypointId),
 }
 
 impl Debug for MovementOrderLocation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Station(station_id) => write!(f, "{station_id:?}"),
+            Self::Waypoint(waypoint_id) => write!(f, "{waypoint_id:?}"),
         }
     }
 }
@@ -55,6 +58,11 @@ impl Debug for UnloadAction {
 #[derive(Serialize, Deserialize, PartialEq, Copy, Clone)]
 pub enum MovementOrderAction {
     UnloadAndLoad(UnloadAction, LoadAction),
+    // Non-stop - the transport advances to the next order as soon as it reaches the location
+    PassThrough,
+    // For military units - stay at the location, firing at the target if there is one, until given
+    // other orders
+    Deploy(Option<TileCoordsXZ>),
 }
 
 impl Debug for MovementOrderAction {
@@ -63,6 +71,9 @@ impl Debug for MovementOrderAction {
             Self::UnloadAndLoad(unload_action, load_action) => {
                 write!(f, "{unload_action:?}-{load_action:?}")
             },
+            Self::PassThrough => write!(f, "Pass Through"),
+            Self::Deploy(None) => write!(f, "Deploy"),
+            Self::Deploy(Some(target)) => write!(f, "Deploy, firing at {target:?}"),
         }
     }
 }
@@ -87,6 +98,45 @@ impl MovementOrder {
             action: MovementOrderAction::UnloadAndLoad(UnloadAction::Unload, LoadAction::Load),
         }
     }
+
+    #[must_use]
+    pub fn pass_through_waypoint(waypoint_id: WaypointId) -> Self {
+        Self {
+            go_to:  MovementOrderLocation::Waypoint(waypoint_id),
+            action: MovementOrderAction::PassThrough,
+        }
+    }
+
+    // The target is usually chosen after the location, so it can be set later with `aim_last_order`
+    #[must_use]
+    pub fn deploy_at(go_to: MovementOrderLocation, target: Option<TileCoordsXZ>) -> Self {
+        Self {
+            go_to,
+            action: MovementOrderAction::Deploy(target),
+        }
+    }
+
+    // Waypoints cannot be stopped at, so reaching them always counts as passing through, unless
+    // a military unit deploys there
+    #[must_use]
+    pub fn is_pass_through(&self) -> bool {
+        self.action == MovementOrderAction::PassThrough
+            || (matches!(self.go_to, MovementOrderLocation::Waypoint(_)) && !self.is_deploy())
+    }
+
+    #[must_use]
+    pub fn is_deploy(&self) -> bool {
+        matches!(self.action, MovementOrderAction::Deploy(_))
+    }
+
+    /// What the military unit fires at once deployed, if anything.
+    #[must_use]
+    pub fn deploy_target(&self) -> Option<TileCoordsXZ> {
+        match self.action {
+            MovementOrderAction::Deploy(target) => target,
+            MovementOrderAction::UnloadAndLoad(..) | MovementOrderAction::PassThrough => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
@@ -140,16 +190,65 @@ impl MovementOrders {
         self.orders.next_index()
     }
 
+    pub fn set_next_index(&mut self, index: usize) {
+        self.orders.set_next_index(index);
+    }
+
+    #[must_use]
+    pub fn order_count(&self) -> usize {
+        self.orders.len()
+    }
+
     pub fn remove_by_index(&mut self, index: usize) {
         self.orders.remove_by_index(index);
     }
 
+    // Whether the order lists are the same, ignoring the current order and the force stop flag
+    #[must_use]
+    pub fn has_same_orders_as(&self, other: &Self) -> bool {
+        self.orders.len() == other.orders.len()
+            && self
+                .orders
+                .iter()
+                .zip(other.orders.iter())
+                .all(|(a, b)| a == b)
+    }
+
+    // Takes over the order list from `other`, keeping our own force stop flag and, if it is
+    // still valid, our own current order
+    pub fn replace_orders_from(&mut self, other: &Self) {
+        let next_index = self.next_index();
+        self.orders = other.orders.clone();
+        if next_index < self.orders.len() {
+            self.orders.set_next_index(next_index);
+        } else {
+            self.orders.set_next_index(0);
+        }
+    }
+
+    /// Sets the target of the last order, if it is a deploy order. Returns whether it was.
+    pub fn aim_last_order(&mut self, target: TileCoordsXZ) -> bool {
+        let order = self.orders.last_mut();
+        if order.is_deploy() {
+            order.action = MovementOrderAction::Deploy(Some(target));
+            true
+        } else {
+            false
+        }
+    }
+
     #[must_use]
     pub fn contains_station(&self, station_id: StationId) -> bool {
-        self.orders.iter().any(|order| {
-            let MovementOrderLocation::Station(order_station_id) = order.go_to;
-            order_station_id == station_id
-        })
+        self.orders
+            .iter()
+            .any(|order| order.go_to == MovementOrderLocation::Station(station_id))
+    }
+
+    #[must_use]
+    pub fn contains_waypoint(&self, waypoint_id: WaypointId) -> bool {
+        self.orders
+            .iter()
+            .any(|order| order.go_to == MovementOrderLocation::Waypoint(waypoint_id))
     }
 }
 
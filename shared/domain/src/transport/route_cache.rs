@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use pathfinding::num_traits::Zero;
+use pathfinding::prelude::dijkstra_all;
+
+use crate::building::building_state::BuildingState;
+use crate::metrics::Metrics;
+use crate::transport::tile_track::TileTrack;
+use crate::transport::track_length::TrackLength;
+use crate::transport::track_pathfinding::{predecessors, successors, with_bridge_tiles};
+
+// Shortest routes from every reachable `TileTrack` to one target `TileTrack`, found by running
+// Dijkstra backwards from the target.
+struct RouteTree {
+    // The next `TileTrack` on the route (`None` if this already is a target) and the remaining length
+    next_hops: HashMap<TileTrack, (Option<TileTrack>, TrackLength)>,
+}
+
+impl RouteTree {
+    fn build(target: TileTrack, building_state: &BuildingState) -> Self {
+        // `None` is a virtual node that is connected to the target, so that the target itself is
+        // also in the results
+        let reached = dijkstra_all(&None, |node: &Option<TileTrack>| {
+            match node {
+                None => vec![(Some(target), TrackLength::zero())],
+                Some(tile_track) => {
+                    predecessors(*tile_track, building_state)
+                        .map(|(predecessor, length)| (Some(predecessor), length))
+                        .collect()
+                },
+            }
+        });
+
+        let next_hops = reached
+            .into_iter()
+            .filter_map(|(node, (next_hop, length))| node.map(|node| (node, (next_hop, length))))
+            .collect();
+
+        Self { next_hops }
+    }
+
+    fn route_from(&self, start: TileTrack) -> Option<(Vec<TileTrack>, TrackLength)> {
+        let (mut next_hop, length) = *self.next_hops.get(&start)?;
+        let mut route = vec![start];
+        while let Some(tile_track) = next_hop {
+            route.push(tile_track);
+            (next_hop, _) = *self.next_hops.get(&tile_track)?;
+        }
+        Some((route, length))
+    }
+
+    fn is_affected_by(
+        &self,
+        added: &[TileTrack],
+        removed: &[TileTrack],
+        building_state: &BuildingState,
+    ) -> bool {
+        // A removed `TileTrack` can only lengthen the routes that went through it, while an added one
+        // can only shorten routes if it is a target or leads somewhere that is already in this tree
+        removed
+            .iter()
+            .any(|tile_track| self.next_hops.contains_key(tile_track))
+            || added.iter().any(|tile_track| {
+                self.next_hops.contains_key(tile_track)
+                    || successors(*tile_track, building_state)
+                        .any(|(successor, _)| self.next_hops.contains_key(&successor))
+            })
+    }
+}
+
+// Caches the routes towards each target (such as one of a station's exits), so that trains heading
+// to the same place share the work. The trees are kept per target rather than per set of targets,
+// as the trains are routed only to the free platforms of a station, and every combination of them
+// would otherwise get its own tree. Trees are dropped only when the track changes affect them.
+#[derive(Default)]
+pub(crate) struct RouteCache {
+    trees: Mutex<HashMap<TileTrack, Arc<RouteTree>>>,
+}
+
+impl Clone for RouteCache {
+    fn clone(&self) -> Self {
+        Self {
+            trees: Mutex::new(self.lock().clone()),
+        }
+    }
+}
+
+// The cache is derived from the tracks, so it should not affect equality
+impl PartialEq for RouteCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl RouteCache {
+    fn lock(&self) -> MutexGuard<'_, HashMap<TileTrack, Arc<RouteTree>>> {
+        // The cache is always consistent between operations, so it is safe to use after a panic
+        self.trees.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn find_route(
+        &self,
+        current_tile_track: TileTrack,
+        targets: &[TileTrack],
+        building_state: &BuildingState,
+        metrics: &impl Metrics,
+    ) -> Option<(Vec<TileTrack>, TrackLength)> {
+        let trees = targets
+            .iter()
+            .map(|target| {
+                let mut trees = self.lock();
+                if let Some(tree) = trees.get(target) {
+                    metrics.route_cache_hit();
+                    Arc::clone(tree)
+                } else {
+                    metrics.route_cache_miss();
+                    let tree = Arc::new(RouteTree::build(*target, building_state));
+                    trees.insert(*target, Arc::clone(&tree));
+                    tree
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // The closest of the targets, the earlier one if several are equally close
+        trees
+            .iter()
+            .filter_map(|tree| tree.route_from(current_tile_track))
+            .reduce(|best, other| if other.1 < best.1 { other } else { best })
+            .map(|(route, length)| (with_bridge_tiles(route), length))
+    }
+
+    // `building_state` is expected to already have the changes applied
+    pub(crate) fn invalidate(
+        &self,
+        added: &[TileTrack],
+        removed: &[TileTrack],
+        building_state: &BuildingState,
+    ) {
+        if added.is_empty() && removed.is_empty() {
+            return;
+        }
+
+        self.lock()
+            .retain(|_, tree| !tree.is_affected_by(added, removed, building_state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared_util::direction_xz::DirectionXZ;
+
+    use super::*;
+    use crate::building::bridge_info::{BridgeInfo, BridgeType};
+    use crate::building::track_info::TrackInfo;
+    use crate::metrics::NoopMetrics;
+    use crate::transport::track_type::TrackType;
+    use crate::{BridgeId, PlayerId, TileCoordsXZ, TrackId};
+
+    fn tile_track(x: i32) -> TileTrack {
+        TileTrack {
+            tile:        TileCoordsXZ::new(x, 0),
+            track_type:  TrackType::WestEast,
+            pointing_in: DirectionXZ::East,
+        }
+    }
+
+    #[test]
+    fn test_route_is_recalculated_when_tracks_change() {
+        let mut building_state = BuildingState::new(5, 1);
+        let owner_id = PlayerId::random();
+        let tracks = (0 .. 5)
+            .map(|x| TrackInfo::new(owner_id, TileCoordsXZ::new(x, 0), TrackType::WestEast))
+            .collect();
+        building_state.append_tracks(tracks);
+
+        let metrics = NoopMetrics::default();
+        let route = |building_state: &BuildingState| {
+            building_state
+                .find_route(tile_track(0), &[tile_track(4)], &metrics)
+                .map(|(route, _)| route)
+        };
+
+        let expected = (0 .. 5).map(tile_track).collect::<Vec<_>>();
+        assert_eq!(route(&building_state), Some(expected.clone()));
+
+        let removed = TrackId::new(TileCoordsXZ::new(2, 0), TrackType::WestEast);
+        building_state.remove_track(removed);
+        assert_eq!(route(&building_state), None);
+
+        building_state.append_tracks(vec![TrackInfo::new(
+            owner_id,
+            TileCoordsXZ::new(2, 0),
+            TrackType::WestEast,
+        )]);
+        assert_eq!(route(&building_state), Some(expected));
+    }
+
+    #[test]
+    fn test_bridge_is_crossed_on_a_single_edge() {
+        let mut building_state = BuildingState::new(7, 1);
+        let owner_id = PlayerId::random();
+        let tracks = [0, 1, 5, 6]
+            .into_iter()
+            .map(|x| TrackInfo::new(owner_id, TileCoordsXZ::new(x, 0), TrackType::WestEast))
+            .collect();
+        building_state.append_tracks(tracks);
+        let bridge = BridgeInfo::new(
+            BridgeId::random(),
+            owner_id,
+            BridgeType::Bridge,
+            TileCoordsXZ::new(2, 0),
+            DirectionXZ::East,
+            3,
+        );
+        building_state.append_bridge(bridge.clone());
+
+        let crossing = successors(tile_track(1), &building_state).collect::<Vec<_>>();
+        assert_eq!(crossing, vec![(tile_track(4), bridge.track_length())]);
+        let before_bridge = predecessors(tile_track(4), &building_state).collect::<Vec<_>>();
+        assert_eq!(
+            before_bridge,
+            vec![
+                (tile_track(3), TrackType::WestEast.length()),
+                (tile_track(1), bridge.track_length()),
+            ]
+        );
+
+        // The trains still move onto every tile of the bridge
+        let (route, length) = building_state
+            .find_route(tile_track(0), &[tile_track(6)], &NoopMetrics::default())
+            .unwrap();
+        assert_eq!(route, (0 .. 7).map(tile_track).collect::<Vec<_>>());
+        assert_eq!(length, TrackType::WestEast.length() * 6.0);
+
+        // Starting on the bridge itself
+        let (route, _) = building_state
+            .find_route(tile_track(3), &[tile_track(6)], &NoopMetrics::default())
+            .unwrap();
+        assert_eq!(route, (3 .. 7).map(tile_track).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_closest_target_is_chosen_and_trees_are_shared_between_target_sets() {
+        let mut building_state = BuildingState::new(5, 1);
+        let owner_id = PlayerId::random();
+        let tracks = (0 .. 5)
+            .map(|x| TrackInfo::new(owner_id, TileCoordsXZ::new(x, 0), TrackType::WestEast))
+            .collect();
+        building_state.append_tracks(tracks);
+
+        let metrics = NoopMetrics::default();
+        let cache = RouteCache::default();
+        let (route, _) = cache
+            .find_route(
+                tile_track(0),
+                &[tile_track(4), tile_track(2)],
+                &building_state,
+                &metrics,
+            )
+            .unwrap();
+        assert_eq!(route, (0 .. 3).map(tile_track).collect::<Vec<_>>());
+
+        // Such as when one of the platforms of a station is occupied
+        let (route, _) = cache
+            .find_route(tile_track(0), &[tile_track(4)], &building_state, &metrics)
+            .unwrap();
+        assert_eq!(route, (0 .. 5).map(tile_track).collect::<Vec<_>>());
+        assert_eq!(cache.lock().len(), 2);
+    }
+}
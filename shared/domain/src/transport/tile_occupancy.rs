@@ -0,0 +1,70 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::tile_coords_xz::TileCoordsXZ;
+use crate::transport::transport_info::TransportInfo;
+
+// How many transports are on each tile, so that trains can be routed to the station platforms that
+// are not yet taken. It is kept up to date as the transports move one by one during a tick.
+#[derive(Default)]
+pub struct TileOccupancy {
+    transports_on_tile: HashMap<TileCoordsXZ, usize>,
+}
+
+pub(crate) fn tiles_of(transport: &TransportInfo) -> HashSet<TileCoordsXZ> {
+    transport
+        .location()
+        .tile_path
+        .iter()
+        .map(|tile_track| tile_track.tile)
+        .collect()
+}
+
+impl TileOccupancy {
+    #[must_use]
+    pub fn new(transports: &[TransportInfo]) -> Self {
+        let mut transports_on_tile = HashMap::new();
+        for transport in transports {
+            for tile in tiles_of(transport) {
+                *transports_on_tile.entry(tile).or_default() += 1;
+            }
+        }
+        Self { transports_on_tile }
+    }
+
+    // Updates the occupancy after `transport` has moved away from `previous_tiles`, so that the
+    // transports moving after it in the same tick see the platform it has taken
+    pub(crate) fn moved(
+        &mut self,
+        previous_tiles: &HashSet<TileCoordsXZ>,
+        transport: &TransportInfo,
+    ) {
+        for tile in previous_tiles {
+            if let Some(count) = self.transports_on_tile.get_mut(tile) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    self.transports_on_tile.remove(tile);
+                }
+            }
+        }
+        for tile in tiles_of(transport) {
+            *self.transports_on_tile.entry(tile).or_default() += 1;
+        }
+    }
+
+    #[must_use]
+    pub fn is_occupied(&self, tile: TileCoordsXZ) -> bool {
+        self.transports_on_tile.contains_key(&tile)
+    }
+
+    // Whether any transport other than `transport` is on `tile`
+    pub fn occupied_by_others(
+        &self,
+        transport: &TransportInfo,
+    ) -> impl Fn(TileCoordsXZ) -> bool + '_ {
+        let own_tiles = tiles_of(transport);
+        move |tile| {
+            let own = usize::from(own_tiles.contains(&tile));
+            self.transports_on_tile.get(&tile).copied().unwrap_or(0) > own
+        }
+    }
+}
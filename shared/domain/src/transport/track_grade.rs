@@ -0,0 +1,67 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+use crate::transport::transport_velocity::TransportVelocity;
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash, Ord, PartialOrd, Default)]
+pub enum TrackGrade {
+    #[default]
+    LightRail,
+    Mainline,
+}
+
+impl Debug for TrackGrade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrackGrade::LightRail => write!(f, "Light Rail"),
+            TrackGrade::Mainline => write!(f, "Mainline"),
+        }
+    }
+}
+
+impl TrackGrade {
+    #[must_use]
+    pub const fn all() -> [TrackGrade; 2] {
+        [TrackGrade::LightRail, TrackGrade::Mainline]
+    }
+
+    #[must_use]
+    pub fn max_velocity(self) -> TransportVelocity {
+        match self {
+            TrackGrade::LightRail => TransportVelocity::new(2.0),
+            TrackGrade::Mainline => TransportVelocity::new(3.0),
+        }
+    }
+
+    // Electric trains run faster on electrified tracks of the same grade
+    #[must_use]
+    pub fn electrified_max_velocity(self) -> TransportVelocity {
+        TransportVelocity::new(self.max_velocity().tiles_per_second() * 1.5)
+    }
+
+    // Multiplies the cost of building the underlying `TrackType`
+    #[must_use]
+    pub fn cost_coef(self) -> f32 {
+        match self {
+            TrackGrade::LightRail => 1.0,
+            TrackGrade::Mainline => 3.0,
+        }
+    }
+
+    #[must_use]
+    pub fn running_cost_per_second(self) -> Money {
+        match self {
+            TrackGrade::LightRail => Money::new(0.01),
+            TrackGrade::Mainline => Money::new(0.03),
+        }
+    }
+
+    // Faster tracks are cheaper to travel along, so the planner prefers reusing them
+    #[must_use]
+    pub fn planning_cost_coef(self) -> f32 {
+        TrackGrade::LightRail.max_velocity().tiles_per_second()
+            / self.max_velocity().tiles_per_second()
+    }
+}
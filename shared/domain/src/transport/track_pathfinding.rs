@@ -1,23 +1,48 @@
 use log::debug;
-use pathfinding::prelude::dijkstra;
+use shared_util::bool_ops::BoolOptionOps;
 use web_time::Instant;
 
 use crate::building::building_state::BuildingState;
+use crate::building::construction_site::ConstructionTarget;
 use crate::metrics::Metrics;
+use crate::tile_coords_xz::TileCoordsXZ;
 use crate::transport::movement_orders::MovementOrderLocation;
 use crate::transport::tile_track::TileTrack;
 use crate::transport::track_length::TrackLength;
 
-fn successors(
+// Each bridge is a single edge from the `TileTrack` just before it to the far end of the bridge, so
+// the tiles on the bridge are only visited when starting from them
+fn bridge_crossing(
     tile_track: TileTrack,
     building_state: &BuildingState,
-) -> impl Iterator<Item = (TileTrack, TrackLength)> {
+) -> Option<(TileTrack, TrackLength)> {
+    let entry = tile_track.next_tile_coords();
+    let bridge = building_state.bridge_at(entry)?;
+    let on_bridge = building_state
+        .bridge_at(tile_track.tile)
+        .is_some_and(|current| current.id() == bridge.id());
+    on_bridge.then_none()?;
+    let far_end = bridge.far_end(entry, tile_track.pointing_in)?;
+    let crossed = TileTrack {
+        tile:        far_end,
+        track_type:  bridge.track_type(),
+        pointing_in: tile_track.pointing_in,
+    };
+    Some((crossed, bridge.track_length()))
+}
+
+pub(crate) fn successors(
+    tile_track: TileTrack,
+    building_state: &BuildingState,
+) -> impl Iterator<Item = (TileTrack, TrackLength)> + '_ {
     let next_tile_coords = tile_track.next_tile_coords();
     let needed_connection = tile_track.pointing_in.reverse();
+    let crossing = bridge_crossing(tile_track, building_state);
 
-    building_state
+    let regular = building_state
         .track_types_with_connection(next_tile_coords, needed_connection)
         .into_iter()
+        .filter(move |_| crossing.is_none())
         .map(move |track_type| {
             let pointing_in = track_type.other_end_unsafe(needed_connection);
             let tile_track = TileTrack {
@@ -26,7 +51,85 @@ fn successors(
                 pointing_in,
             };
             (tile_track, track_type.length())
+        });
+
+    crossing.into_iter().chain(regular)
+}
+
+// The reverse of `successors` - the `TileTrack`-s from which we can get to this `TileTrack`, with
+// the length of moving into it
+pub(crate) fn predecessors(
+    tile_track: TileTrack,
+    building_state: &BuildingState,
+) -> impl Iterator<Item = (TileTrack, TrackLength)> + '_ {
+    let entered_from = tile_track
+        .track_type
+        .other_end_unsafe(tile_track.pointing_in);
+    let previous_tile_coords = tile_track.tile + entered_from;
+    let pointing_in = entered_from.reverse();
+    let exists = building_state
+        .usable_track_types_at(tile_track.tile)
+        .contains(tile_track.track_type);
+    let length = tile_track.track_type.length();
+
+    let regular = building_state
+        .track_types_with_connection(previous_tile_coords, pointing_in)
+        .into_iter()
+        .filter(move |_| exists)
+        .map(move |track_type| {
+            let tile_track = TileTrack {
+                tile: previous_tile_coords,
+                track_type,
+                pointing_in,
+            };
+            (tile_track, length)
+        })
+        .filter(move |(predecessor, _)| bridge_crossing(*predecessor, building_state).is_none());
+
+    // Arriving at the far end of a bridge from the `TileTrack` just before its other end
+    let before_bridge = building_state
+        .bridge_at(tile_track.tile)
+        .filter(|bridge| exists && bridge.track_type() == tile_track.track_type)
+        .and_then(|bridge| bridge.far_end(tile_track.tile, entered_from))
+        .map(|entry| entry + entered_from);
+    let crossing = before_bridge
+        .into_iter()
+        .flat_map(move |before_bridge| {
+            building_state
+                .track_types_with_connection(before_bridge, pointing_in)
+                .into_iter()
+                .map(move |track_type| {
+                    TileTrack {
+                        tile: before_bridge,
+                        track_type,
+                        pointing_in,
+                    }
+                })
         })
+        .filter_map(move |predecessor| {
+            bridge_crossing(predecessor, building_state)
+                .filter(|(crossed, _)| *crossed == tile_track)
+                .map(|(_, length)| (predecessor, length))
+        });
+
+    regular.chain(crossing)
+}
+
+// The routes cross bridges on a single edge, but the trains still move tile by tile, so the tiles
+// on the bridges are added back
+pub(crate) fn with_bridge_tiles(route: Vec<TileTrack>) -> Vec<TileTrack> {
+    let mut result: Vec<TileTrack> = Vec::with_capacity(route.len());
+    for tile_track in route {
+        if let Some(previous) = result.last().copied() {
+            let mut tile = previous.tile;
+            for _ in 1 .. previous.tile.manhattan_distance(tile_track.tile) {
+                tile = tile + tile_track.pointing_in;
+                result.push(TileTrack { tile, ..tile_track });
+            }
+        }
+        result.push(tile_track);
+    }
+    result
 }
 
 #[must_use]
@@ -34,25 +137,58 @@ pub fn find_location_tile_tracks(
     location: MovementOrderLocation,
     building_state: &BuildingState,
 ) -> Option<Vec<TileTrack>> {
-    let MovementOrderLocation::Station(station_id) = location;
-    let building = building_state.find_station(station_id)?;
-    let targets = building
-        .station_exit_tile_tracks()
-        .into_iter()
-        .collect::<Vec<_>>();
-    Some(targets)
+    match location {
+        MovementOrderLocation::Station(station_id) => {
+            let building = building_state.find_station(station_id)?;
+            let targets = building
+                .station_exit_tile_tracks()
+                .into_iter()
+                .collect::<Vec<_>>();
+            Some(targets)
+        },
+        MovementOrderLocation::Waypoint(waypoint_id) => {
+            // Any track on the waypoint's tile, travelled in either direction, counts as passing through it
+            let waypoint = building_state.find_waypoint(waypoint_id)?;
+            building_state
+                .is_under_construction(ConstructionTarget::Waypoint(waypoint_id))
+                .then_none()?;
+            let tile = waypoint.tile();
+            let targets = building_state
+                .usable_track_types_at(tile)
+                .into_iter()
+                .flat_map(|track_type| {
+                    track_type.connections().map(|pointing_in| {
+                        TileTrack {
+                            tile,
+                            track_type,
+                            pointing_in,
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            Some(targets)
+        },
+    }
 }
 
-// Later:   We need to think how to handle station expansion. Can a station have multiple buildings?
-//          And thus we need `StationId` for pathfinding as `target_station`?
+// When heading to a station, we only route to the platforms that are free according to
+// `is_occupied`, while `find_location_tile_tracks` still accepts arriving at any platform
 #[must_use]
 pub fn find_route_to(
     current_tile_track: TileTrack,
     go_to: MovementOrderLocation,
     building_state: &BuildingState,
+    is_occupied: impl Fn(TileCoordsXZ) -> bool,
     metrics: &impl Metrics,
 ) -> Option<Vec<TileTrack>> {
-    let targets = find_location_tile_tracks(go_to, building_state)?;
+    let targets = match go_to {
+        MovementOrderLocation::Station(station_id) => {
+            building_state
+                .find_station(station_id)?
+                .free_platform_exit_tile_tracks(is_occupied)
+        },
+        MovementOrderLocation::Waypoint(_) => find_location_tile_tracks(go_to, building_state)?,
+    };
 
     debug!(
         "Doing pathfinding. Current: {current_tile_track:?}, Go to: {go_to:?}, Targets: {targets:?}"
@@ -75,18 +211,9 @@ pub fn find_route_to_tile_tracks(
         targets, current_tile_track
     );
 
-    // TODO:
-    //  Consider optimising by Floyd-Warshall, which gets continuously updated as the `GameState` changes?
-    //  But likely do this only after signals / collisions, to ensure it doesn't have to be redone.
-    //  Note that you have:
-    //      - Your own Scala version at https://github.com/jurisk/advent-of-code/blob/master/scala2/src/main/scala/jurisk/algorithms/pathfinding/FloydWarshall.scala
-    //      - A Rust version at https://github.com/petgraph/petgraph/blob/master/src/algo/floyd_warshall.rs
-    //      - Hints on updating the graph at https://stackoverflow.com/questions/2537870/how-to-recalculate-all-pairs-shorthest-paths-on-line-if-nodes-are-getting-remove
-    let result = dijkstra(
-        &current_tile_track,
-        |tile_track| successors(*tile_track, building_state),
-        |tile_track| targets.contains(tile_track),
-    );
+    // The routes towards the same targets are shared between all the trains, and only recalculated
+    // when the tracks change
+    let result = building_state.find_route(current_tile_track, targets, metrics);
 
     let elapsed = start.elapsed();
     let lengths = result
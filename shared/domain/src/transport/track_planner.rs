@@ -2,19 +2,36 @@ use itertools::Itertools;
 use log::{Level, debug, log, trace, warn};
 use pathfinding::prelude::dijkstra;
 use shared_util::bool_ops::BoolOptionOps;
+use shared_util::direction_xz::DirectionXZ;
+use uuid::Uuid;
 use web_time::{Duration, Instant};
 
-use crate::PlayerId;
+use crate::building::bridge_info::{BridgeInfo, BridgeType};
 use crate::building::building_state::CanBuildResponse;
 use crate::building::track_info::TrackInfo;
 use crate::directional_edge::DirectionalEdge;
 use crate::game_state::GameState;
 use crate::metrics::Metrics;
+use crate::tile_coords_xz::TileCoordsXZ;
 use crate::transport::track_length::TrackLength;
 use crate::transport::track_type::TrackType;
+use crate::{BridgeId, PlayerId};
 
 pub const DEFAULT_ALREADY_EXISTS_COEF: f32 = 0.8;
 
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlannedTracks {
+    pub tracks:  Vec<TrackInfo>,
+    pub bridges: Vec<BridgeInfo>,
+}
+
+impl PlannedTracks {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty() && self.bridges.is_empty()
+    }
+}
+
 fn successors(
     current: DirectionalEdge,
     player_id: PlayerId,
@@ -23,23 +40,104 @@ fn successors(
 ) -> Vec<(DirectionalEdge, TrackLength)> {
     let mut results = Vec::with_capacity(3);
     let tile = current.into_tile;
+    let mut straight_blocked = false;
     for track_type in TrackType::matching_direction(current.from_direction) {
         let response = game_state.can_build_track_internal(player_id, tile, track_type);
-        let coef = response_to_coef(&response, already_exists_coef);
+        // Existing faster tracks are preferred, as transports will travel along them quicker
+        let existing_grade = game_state.building_state().track_grade_at(tile, track_type);
+        let coef = response_to_coef(
+            &response,
+            already_exists_coef * existing_grade.planning_cost_coef(),
+        );
+        let exit_direction = track_type.other_end(current.from_direction);
         if let Some(coef) = coef {
             let adjusted_length = track_type.length() * coef;
-            if let Some(exit_direction) = track_type.other_end(current.from_direction) {
+            if let Some(exit_direction) = exit_direction {
                 let next_from_direction = exit_direction.reverse();
                 let next_tile = current.into_tile + exit_direction;
                 let next_edge = DirectionalEdge::new(next_tile, next_from_direction);
                 results.push((next_edge, adjusted_length));
             }
+        } else if exit_direction == Some(current.from_direction.reverse()) {
+            straight_blocked = true;
         }
     }
+
+    // Bridges are only considered to get past obstacles, as otherwise they would slow down planning
+    if straight_blocked {
+        results.extend(bridge_successors(current, player_id, game_state));
+    }
+
     trace!("current: {current:?}, successors: {results:?}");
     results
 }
 
+#[expect(clippy::cast_precision_loss)]
+fn bridge_successors(
+    current: DirectionalEdge,
+    player_id: PlayerId,
+    game_state: &GameState,
+) -> Vec<(DirectionalEdge, TrackLength)> {
+    let direction = current.from_direction.reverse();
+    let max_length = BridgeType::all()
+        .into_iter()
+        .map(|bridge_type| *bridge_type.length_in_tiles_range().end())
+        .max()
+        .unwrap_or_default();
+
+    (1 ..= max_length)
+        .filter_map(|length_in_tiles| {
+            let bridge = cheapest_bridge(
+                // Creating random IDs is relatively slow, and we do not need them while searching
+                BridgeId::new(Uuid::nil()),
+                player_id,
+                current.into_tile,
+                direction,
+                length_in_tiles,
+                game_state,
+            )?;
+            let next_edge =
+                DirectionalEdge::new(bridge.end_tile() + direction, current.from_direction);
+            let length = bridge.track_type().length()
+                * (length_in_tiles as f32 * bridge.bridge_type().planning_cost_coef());
+            Some((next_edge, length))
+        })
+        .collect()
+}
+
+fn cheapest_bridge(
+    bridge_id: BridgeId,
+    player_id: PlayerId,
+    start: TileCoordsXZ,
+    direction: DirectionXZ,
+    length_in_tiles: usize,
+    game_state: &GameState,
+) -> Option<BridgeInfo> {
+    BridgeType::all()
+        .into_iter()
+        .filter(|bridge_type| {
+            bridge_type
+                .length_in_tiles_range()
+                .contains(&length_in_tiles)
+        })
+        .map(|bridge_type| {
+            BridgeInfo::new(
+                bridge_id,
+                player_id,
+                bridge_type,
+                start,
+                direction,
+                length_in_tiles,
+            )
+        })
+        .filter(|bridge| game_state.can_build_bridge_internal(bridge).is_ok())
+        .min_by(|a, b| {
+            a.bridge_type()
+                .planning_cost_coef()
+                .total_cmp(&b.bridge_type().planning_cost_coef())
+        })
+}
+
 fn response_to_coef(
     can_build_response: &CanBuildResponse,
     already_exists_coef: f32,
@@ -59,20 +157,58 @@ pub fn plan_tracks(
     game_state: &GameState,
     already_exists_coef: f32,
     metrics: &dyn Metrics,
-) -> Option<(Vec<TrackInfo>, TrackLength)> {
+) -> Option<(PlannedTracks, TrackLength)> {
+    plan_tracks_cancellable(
+        player_id,
+        current,
+        targets,
+        game_state,
+        already_exists_coef,
+        metrics,
+        &|| false,
+    )
+}
+
+// `is_cancelled` is checked during the search, so that planning can be abandoned early (returning
+// `None`) when the result is no longer needed, e.g. because the user has moved the mouse elsewhere
+#[must_use]
+pub fn plan_tracks_cancellable(
+    player_id: PlayerId,
+    current: DirectionalEdge,
+    targets: &[DirectionalEdge],
+    game_state: &GameState,
+    already_exists_coef: f32,
+    metrics: &dyn Metrics,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Option<(PlannedTracks, TrackLength)> {
     targets.is_empty().then_none()?;
 
     let start = Instant::now();
 
     trace!("Planning tracks at {start:?} from {current:?} to {targets:?}");
 
-    // TODO: Consider optimising either by `dijkstra_all` or Floyd-Warshall, with caching / live updates. Also, it could be done async. See https://github.com/loopystudios/bevy_async_task .
+    // TODO: Consider optimising either by `dijkstra_all` or Floyd-Warshall, with caching / live updates.
     let path = dijkstra(
         &current,
-        |current| successors(*current, player_id, game_state, already_exists_coef),
+        |current| {
+            // Not expanding any further makes the search run out of nodes quickly
+            if is_cancelled() {
+                vec![]
+            } else {
+                successors(*current, player_id, game_state, already_exists_coef)
+            }
+        },
         |current| targets.contains(current),
     );
 
+    if is_cancelled() {
+        debug!(
+            "Cancelled planning tracks from {current:?} to {targets:?} after {:?}",
+            start.elapsed()
+        );
+        return None;
+    }
+
     let path_length = path.as_ref().map(|(path, _length)| path.len());
     debug!(
         "Found path from {current:?} to {targets:?} in {:?}: {:?} length",
@@ -82,9 +218,31 @@ pub fn plan_tracks(
 
     let result = path.map(|(path, length)| {
         let mut tracks = vec![];
+        let mut bridges = vec![];
 
         for (a, b) in path.into_iter().tuple_windows() {
-            if let Some(track_type) =
+            // Only bridges connect edges that are not on neighbouring tiles
+            let bridge_length = a.into_tile.manhattan_distance(b.into_tile);
+            if bridge_length > 1 {
+                let bridge = usize::try_from(bridge_length).ok().and_then(|length_in_tiles| {
+                    cheapest_bridge(
+                        BridgeId::random(),
+                        player_id,
+                        a.into_tile,
+                        a.from_direction.reverse(),
+                        length_in_tiles,
+                        game_state,
+                    )
+                });
+                match bridge {
+                    Some(bridge) => bridges.push(bridge),
+                    None => {
+                        warn!(
+                            "Unexpected state - our found path includes an invalid bridge: {a:?} -> {b:?}",
+                        );
+                    },
+                }
+            } else if let Some(track_type) =
                 TrackType::from_directions(a.from_direction, b.from_direction.reverse())
             {
                 let track_info = TrackInfo::new(player_id, a.into_tile, track_type);
@@ -105,7 +263,7 @@ pub fn plan_tracks(
             }
         }
 
-        (tracks, length)
+        (PlannedTracks { tracks, bridges }, length)
     });
 
     let elapsed = start.elapsed();
@@ -118,7 +276,7 @@ pub fn plan_tracks(
     };
     let lengths = result
         .as_ref()
-        .map(|(tracks, length)| (tracks.len(), *length));
+        .map(|(planned, length)| (planned.tracks.len(), *length));
     log!(
         level,
         "Planning tracks ({:?}) from {current:?} to {targets:?} took {:?}",
@@ -132,16 +290,15 @@ pub fn plan_tracks(
 
 #[cfg(test)]
 mod tests {
-    use shared_util::direction_xz::DirectionXZ;
+    use shared_util::grid_xz::GridXZ;
 
     use super::*;
     use crate::building::building_info::WithOwner;
     use crate::map_level::map_level::{Height, MapLevel, TerrainType};
-    use crate::map_level::terrain::Terrain;
+    use crate::map_level::terrain::{DEFAULT_Y_COEF, Terrain};
     use crate::map_level::zoning::Zoning;
     use crate::metrics::NoopMetrics;
     use crate::scenario::Scenario;
-    use crate::tile_coords_xz::TileCoordsXZ;
     use crate::water::Water;
     use crate::{MapId, ScenarioId};
 
@@ -168,7 +325,7 @@ mod tests {
         let game_state = GameState::from_scenario(scenario, false);
         let head = DirectionalEdge::new(tile, DirectionXZ::West);
         let tail = DirectionalEdge::new(tile + DirectionXZ::East, DirectionXZ::West);
-        let (results, length) = plan_tracks(
+        let (planned, length) = plan_tracks(
             player_id,
             head,
             &[tail],
@@ -178,12 +335,104 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(results.len(), 1);
+        assert!(planned.bridges.is_empty());
+        assert_eq!(planned.tracks.len(), 1);
         assert_eq!(length, TrackType::WestEast.length());
 
-        let track = &results[0];
+        let track = &planned.tracks[0];
         assert_eq!(track.owner_id(), player_id);
         assert_eq!(track.tile, tile);
         assert_eq!(track.track_type, TrackType::WestEast);
     }
+
+    #[test]
+    fn test_cancelled_planning_returns_nothing() {
+        let size_x = 3;
+        let size_z = 3;
+        let player_id = PlayerId::random();
+        let tile = TileCoordsXZ::new(1, 1);
+        let terrain = Terrain::flat(size_x, size_z, Height::from_u8(1), TerrainType::Grass);
+        let water = Water::new(Height::from_u8(0), Height::from_u8(1));
+        let zoning = Zoning::new(size_x, size_z);
+        let map_level = MapLevel::new(
+            MapId("test".to_string()),
+            terrain,
+            water.expect("valid water"),
+            zoning,
+        );
+        let scenario = Scenario {
+            scenario_id: ScenarioId("test".to_string()),
+            players: vec![],
+            map_level,
+        };
+        let game_state = GameState::from_scenario(scenario, false);
+        let head = DirectionalEdge::new(tile, DirectionXZ::West);
+        let tail = DirectionalEdge::new(tile + DirectionXZ::East, DirectionXZ::West);
+        let plan = |is_cancelled: &dyn Fn() -> bool| {
+            plan_tracks_cancellable(
+                player_id,
+                head,
+                &[tail],
+                &game_state,
+                DEFAULT_ALREADY_EXISTS_COEF,
+                &NoopMetrics::default(),
+                is_cancelled,
+            )
+        };
+
+        assert!(plan(&|| false).is_some());
+        assert!(plan(&|| true).is_none());
+    }
+
+    #[test]
+    fn test_plan_bridge_over_water() {
+        let size_x = 7;
+        let size_z = 1;
+        let player_id = PlayerId::random();
+        // Lower the vertices between tiles 2 and 4 into the water
+        let vertex_heights = GridXZ::filled_with(size_x + 1, size_z + 1, Height::from_u8(1))
+            .map_with_coords(|coords, height| {
+                if (3 ..= 4).contains(&coords.x) {
+                    Height::from_u8(0)
+                } else {
+                    *height
+                }
+            });
+        let vertex_terrains = GridXZ::filled_with(size_x + 1, size_z + 1, TerrainType::Grass);
+        let terrain = Terrain::new(DEFAULT_Y_COEF, vertex_heights, vertex_terrains);
+        let water = Water::new(Height::from_u8(0), Height::from_u8(1));
+        let zoning = Zoning::new(size_x, size_z);
+        let map_level = MapLevel::new(
+            MapId("test".to_string()),
+            terrain,
+            water.expect("valid water"),
+            zoning,
+        );
+        let scenario = Scenario {
+            scenario_id: ScenarioId("test".to_string()),
+            players: vec![],
+            map_level,
+        };
+        let game_state = GameState::from_scenario(scenario, false);
+        let head = DirectionalEdge::new(TileCoordsXZ::new(0, 0), DirectionXZ::West);
+        let tail = DirectionalEdge::new(TileCoordsXZ::new(7, 0), DirectionXZ::West);
+        let (planned, _length) = plan_tracks(
+            player_id,
+            head,
+            &[tail],
+            &game_state,
+            DEFAULT_ALREADY_EXISTS_COEF,
+            &NoopMetrics::default(),
+        )
+        .unwrap();
+
+        assert_eq!(planned.tracks.len(), 4);
+        assert_eq!(planned.bridges.len(), 1);
+
+        let bridge = &planned.bridges[0];
+        assert_eq!(bridge.owner_id(), player_id);
+        assert_eq!(bridge.bridge_type(), BridgeType::Bridge);
+        assert_eq!(bridge.start_tile(), TileCoordsXZ::new(2, 0));
+        assert_eq!(bridge.end_tile(), TileCoordsXZ::new(4, 0));
+    }
 }
@@ -0,0 +1,78 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::building::building_info::WithOwner;
+use crate::cargo_map::CargoMap;
+use crate::transport::movement_orders::MovementOrders;
+use crate::{PlayerId, TransportGroupId};
+
+// A line - a group of transports that share the same movement orders. Every transport keeps its
+// own current order and force stop flag, only the order list itself is shared.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct TransportGroupInfo {
+    group_id:        TransportGroupId,
+    owner_id:        PlayerId,
+    name:            String,
+    movement_orders: MovementOrders,
+}
+
+impl Debug for TransportGroupInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {} {:?}",
+            self.group_id, self.name, self.movement_orders
+        )
+    }
+}
+
+impl TransportGroupInfo {
+    #[must_use]
+    pub fn new(
+        group_id: TransportGroupId,
+        owner_id: PlayerId,
+        name: String,
+        movement_orders: MovementOrders,
+    ) -> Self {
+        Self {
+            group_id,
+            owner_id,
+            name,
+            movement_orders,
+        }
+    }
+
+    #[must_use]
+    pub fn group_id(&self) -> TransportGroupId {
+        self.group_id
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn movement_orders(&self) -> &MovementOrders {
+        &self.movement_orders
+    }
+
+    pub(crate) fn update_movement_orders(&mut self, movement_orders: &MovementOrders) {
+        self.movement_orders.replace_orders_from(movement_orders);
+    }
+}
+
+impl WithOwner for TransportGroupInfo {
+    fn owner_id(&self) -> PlayerId {
+        self.owner_id
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TransportGroupStatistics {
+    pub transports:     usize,
+    pub force_stopped:  usize,
+    pub cargo_loaded:   CargoMap,
+    pub cargo_capacity: CargoMap,
+}
@@ -1,18 +1,27 @@
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 
 use serde::{Deserialize, Serialize};
 
 use crate::building::building_info::{WithCostToBuild, WithOwner};
+use crate::building::building_state::BuildingState;
 use crate::building::industry_type::IndustryType;
+use crate::cargo_age::CargoAge;
 use crate::cargo_amount::CargoAmount;
 use crate::cargo_map::CargoMap;
+use crate::game_time::GameTimeDiff;
+use crate::military::AmmunitionStatus;
+use crate::military::military_unit::MilitaryUnitDynamicInfo;
+use crate::money::Money;
 use crate::resource_type::ResourceType;
+use crate::tile_coords_xz::TileCoordsXZ;
+use crate::tile_coverage::TileCoverage;
 use crate::transport::cargo_processing::CargoProcessing;
 use crate::transport::movement_orders::MovementOrders;
 use crate::transport::transport_location::TransportLocation;
 use crate::transport::transport_type::TransportType;
 use crate::transport::transport_velocity::TransportVelocity;
-use crate::{PlayerId, TransportId};
+use crate::{PlayerId, TransportGroupId, TransportId};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct TransportStaticInfo {
@@ -29,6 +38,13 @@ pub struct TransportDynamicInfo {
     pub movement_orders:  MovementOrders,
     pub cargo_processing: CargoProcessing,
     pub cargo_loaded:     CargoMap,
+    // Where each resource on board was loaded, used to calculate the income when it is delivered
+    pub cargo_loaded_at:  HashMap<ResourceType, TileCoordsXZ>,
+    // Perishable cargo delivered late pays less
+    pub cargo_age:        CargoAge,
+    pub group_id:         Option<TransportGroupId>,
+    // Only for military units, such as railway artillery
+    pub military_unit:    Option<MilitaryUnitDynamicInfo>,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
@@ -62,6 +78,9 @@ impl TransportInfo {
         movement_orders: MovementOrders,
     ) -> Self {
         let velocity = transport_type.max_velocity();
+        let military_unit = transport_type
+            .is_military_unit()
+            .then(MilitaryUnitDynamicInfo::default);
         Self {
             static_info:  TransportStaticInfo {
                 transport_id,
@@ -74,6 +93,10 @@ impl TransportInfo {
                 movement_orders,
                 cargo_loaded: CargoMap::new(),
                 cargo_processing: CargoProcessing::NotStarted,
+                cargo_loaded_at: HashMap::new(),
+                cargo_age: CargoAge::fresh(),
+                group_id: None,
+                military_unit,
             },
         }
     }
@@ -130,11 +153,37 @@ impl TransportInfo {
         &self.dynamic_info.movement_orders
     }
 
+    #[must_use]
+    pub fn group_id(&self) -> Option<TransportGroupId> {
+        self.dynamic_info.group_id
+    }
+
+    pub(crate) fn set_group_id(&mut self, group_id: Option<TransportGroupId>) {
+        self.dynamic_info.group_id = group_id;
+    }
+
     #[must_use]
     pub fn dynamic_info(&self) -> TransportDynamicInfo {
         self.dynamic_info.clone()
     }
 
+    #[must_use]
+    pub fn military_unit(&self) -> Option<&MilitaryUnitDynamicInfo> {
+        self.dynamic_info.military_unit.as_ref()
+    }
+
+    // Only for the military units that have railway guns
+    #[must_use]
+    pub fn ammunition_status(&self, building_state: &BuildingState) -> Option<AmmunitionStatus> {
+        self.transport_type().shell_type().map(|shell_type| {
+            building_state.ammunition_status(
+                self.owner_id(),
+                &TileCoverage::Single(self.location().next_tile_in_path().tile),
+                shell_type,
+            )
+        })
+    }
+
     #[must_use]
     pub fn transport_id(&self) -> TransportId {
         self.static_info.transport_id
@@ -162,6 +211,53 @@ impl TransportInfo {
     pub fn remove_cargo(&mut self, cargo: &CargoMap) {
         self.dynamic_info.cargo_loaded -= cargo;
     }
+
+    // Ages the cargo on board, with part of the perishable cargo spoiling
+    pub(crate) fn age_cargo(&mut self, diff: GameTimeDiff) {
+        let dynamic_info = &mut self.dynamic_info;
+        let spoiled = CargoAge::spoiled(&dynamic_info.cargo_loaded, diff);
+        dynamic_info.cargo_loaded -= &spoiled;
+        dynamic_info
+            .cargo_age
+            .advance(&dynamic_info.cargo_loaded, diff);
+    }
+
+    pub(crate) fn add_aged_cargo(&mut self, cargo: &CargoMap, age: &CargoAge) {
+        self.dynamic_info
+            .cargo_age
+            .mix(&self.dynamic_info.cargo_loaded, cargo, age);
+        self.add_cargo(cargo);
+    }
+
+    // More of a resource that is already on board still counts as loaded where it was first loaded
+    pub(crate) fn load_cargo_at(&mut self, cargo: &CargoMap, age: &CargoAge, tile: TileCoordsXZ) {
+        for resource in cargo.resource_types_present() {
+            self.dynamic_info
+                .cargo_loaded_at
+                .entry(resource)
+                .or_insert(tile);
+        }
+        self.add_aged_cargo(cargo, age);
+    }
+
+    // Returns what the cargo earned, depending on how far each resource was carried
+    pub(crate) fn unload_cargo_at(&mut self, cargo: &CargoMap, tile: TileCoordsXZ) -> Money {
+        let mut income = Money::ZERO;
+        for resource in cargo.resource_types_present() {
+            if let Some(loaded_at) = self.dynamic_info.cargo_loaded_at.get(&resource) {
+                let distance = loaded_at.manhattan_distance(tile);
+                let unloaded = CargoMap::single(resource, cargo.get(resource).as_f32());
+                income += self.dynamic_info.cargo_age.value_of(&unloaded) * f64::from(distance);
+            }
+        }
+        self.remove_cargo(cargo);
+
+        let cargo_loaded = &self.dynamic_info.cargo_loaded;
+        self.dynamic_info
+            .cargo_loaded_at
+            .retain(|resource, _| cargo_loaded.get(*resource) > CargoAmount::ZERO);
+        income
+    }
 }
 
 impl WithOwner for TransportInfo {
@@ -175,3 +271,98 @@ impl WithCostToBuild for TransportInfo {
         self.static_info.transport_type.cost_to_build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use shared_util::direction_xz::DirectionXZ;
+
+    use super::*;
+    use crate::transport::movement_orders::MovementOrder;
+    use crate::transport::progress_within_tile::ProgressWithinTile;
+    use crate::transport::tile_track::TileTrack;
+    use crate::transport::track_type::TrackType;
+    use crate::transport::transport_type::TrainComponentType;
+    use crate::{StationId, TransportId};
+
+    #[test]
+    fn test_income_is_paid_for_how_far_each_resource_was_carried() {
+        let a = TileCoordsXZ::new(0, 0);
+        let b = TileCoordsXZ::new(10, 0);
+        let c = TileCoordsXZ::new(30, 0);
+        let location = TransportLocation::new(
+            vec![TileTrack {
+                tile:        a,
+                track_type:  TrackType::WestEast,
+                pointing_in: DirectionXZ::East,
+            }],
+            ProgressWithinTile::about_to_exit(),
+        );
+        let mut transport = TransportInfo::new(
+            TransportId::random(),
+            PlayerId::random(),
+            TransportType::Train(vec![
+                TrainComponentType::Car(ResourceType::Coal),
+                TrainComponentType::Car(ResourceType::Iron),
+                TrainComponentType::Car(ResourceType::Steel),
+            ]),
+            location,
+            MovementOrders::one(MovementOrder::stop_at_station(StationId::random())),
+        );
+        let coal = CargoMap::single(ResourceType::Coal, 1.0);
+        let iron = CargoMap::single(ResourceType::Iron, 1.0);
+        let steel = CargoMap::single(ResourceType::Steel, 1.0);
+
+        let mut coal_and_iron = coal.clone();
+        coal_and_iron += &iron;
+        transport.load_cargo_at(&coal_and_iron, &CargoAge::fresh(), a);
+
+        // At `b` the iron is unloaded, and then the steel is loaded at the same station
+        let income = transport.unload_cargo_at(&iron, b);
+        assert_eq!(income, ResourceType::Iron.value() * 10.0);
+        transport.load_cargo_at(&steel, &CargoAge::fresh(), b);
+
+        // The coal still counts as loaded at `a`, while the steel was loaded at `b`
+        let mut coal_and_steel = coal;
+        coal_and_steel += &steel;
+        let income = transport.unload_cargo_at(&coal_and_steel, c);
+        assert_eq!(
+            income,
+            ResourceType::Coal.value() * 30.0 + ResourceType::Steel.value() * 20.0
+        );
+        assert!(transport.dynamic_info.cargo_loaded_at.is_empty());
+    }
+
+    #[test]
+    fn test_perishable_cargo_spoils_on_board() {
+        let location = TransportLocation::new(
+            vec![TileTrack {
+                tile:        TileCoordsXZ::new(0, 0),
+                track_type:  TrackType::WestEast,
+                pointing_in: DirectionXZ::East,
+            }],
+            ProgressWithinTile::just_entering(),
+        );
+        let mut transport = TransportInfo::new(
+            TransportId::random(),
+            PlayerId::random(),
+            TransportType::Train(vec![
+                TrainComponentType::Car(ResourceType::Food),
+                TrainComponentType::Car(ResourceType::Coal),
+            ]),
+            location,
+            MovementOrders::one(MovementOrder::stop_at_station(StationId::random())),
+        );
+        let cargo = CargoMap::from([(ResourceType::Food, 10.0), (ResourceType::Coal, 10.0)]);
+        transport.load_cargo_at(&cargo, &CargoAge::fresh(), TileCoordsXZ::new(0, 0));
+
+        transport.age_cargo(GameTimeDiff::from_seconds(600.0));
+        let cargo_loaded = transport.cargo_loaded();
+        assert!(cargo_loaded.get(ResourceType::Food) > CargoAmount::ZERO);
+        assert!(cargo_loaded.get(ResourceType::Food) < CargoAmount::new(10.0));
+        assert_eq!(cargo_loaded.get(ResourceType::Coal), CargoAmount::new(10.0));
+        assert_eq!(
+            transport.dynamic_info.cargo_age.get(ResourceType::Food),
+            GameTimeDiff::from_seconds(600.0)
+        );
+    }
+}
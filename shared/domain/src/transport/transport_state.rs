@@ -1,24 +1,39 @@
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 
 use serde::{Deserialize, Serialize};
 
 use crate::building::building_info::WithOwner;
 use crate::building::building_state::BuildingState;
-use crate::game_time::GameTimeDiff;
+use crate::building::industry_type::IndustryType;
+use crate::client_command::InternalGameCommand;
+use crate::game_state::GameState;
+use crate::game_time::{GameTime, GameTimeDiff};
 use crate::metrics::Metrics;
-use crate::transport::advancement::advance;
+use crate::military::military_unit;
+use crate::money::Money;
+use crate::tile_coverage::TileCoverage;
+use crate::transport::advancement::{Delivery, advance};
 use crate::transport::movement_orders::MovementOrders;
+use crate::transport::tile_occupancy::{TileOccupancy, tiles_of};
+use crate::transport::transport_group::{TransportGroupInfo, TransportGroupStatistics};
 use crate::transport::transport_info::{TransportDynamicInfo, TransportInfo};
-use crate::{PlayerId, TransportId};
+use crate::{PlayerId, TransportGroupId, TransportId};
 
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct TransportState {
     transports: Vec<TransportInfo>,
+    groups:     Vec<TransportGroupInfo>,
 }
 
 impl Debug for TransportState {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "TransportState({} transports)", self.transports.len())
+        write!(
+            f,
+            "TransportState({} transports, {} groups)",
+            self.transports.len(),
+            self.groups.len()
+        )
     }
 }
 
@@ -27,6 +42,7 @@ impl TransportState {
     pub fn empty() -> Self {
         Self {
             transports: Vec::new(),
+            groups:     Vec::new(),
         }
     }
 
@@ -54,17 +70,80 @@ impl TransportState {
         })
     }
 
+    // Returns what each player earned from delivering cargo
     pub(crate) fn advance_time_diff(
         &mut self,
         diff: GameTimeDiff,
         buildings: &mut BuildingState,
         metrics: &impl Metrics,
-    ) {
+    ) -> HashMap<PlayerId, Delivery> {
+        let mut occupancy = TileOccupancy::new(&self.transports);
+        let mut deliveries: HashMap<PlayerId, Delivery> = HashMap::new();
+        for transport in &mut self.transports {
+            let previous_tiles = tiles_of(transport);
+            let delivery = advance(transport, buildings, &occupancy, diff, metrics);
+            occupancy.moved(&previous_tiles, transport);
+            if delivery != Delivery::default() {
+                let total = deliveries.entry(transport.owner_id()).or_default();
+                total.income += delivery.income;
+                total.cargo += delivery.cargo;
+            }
+        }
+        deliveries
+    }
+
+    #[must_use]
+    pub(crate) fn generate_commands(
+        &self,
+        previous_game_time: GameTime,
+        new_game_time: GameTime,
+        game_state: &GameState,
+    ) -> Vec<InternalGameCommand> {
+        self.transports
+            .iter()
+            .flat_map(|transport| {
+                military_unit::generate_commands(
+                    transport,
+                    previous_game_time,
+                    new_game_time,
+                    game_state,
+                )
+            })
+            .collect()
+    }
+
+    // Military units are supplied by a military base in supply range of where they are, and
+    // cannot fire while they are not
+    pub(crate) fn supply_military_units(&mut self, buildings: &mut BuildingState, seconds: f32) {
         for transport in &mut self.transports {
-            advance(transport, buildings, diff, metrics);
+            let upkeep = transport.transport_type().upkeep_per_second() * seconds;
+            let tile = transport.location().next_tile_in_path().tile;
+            let owner_id = transport.owner_id();
+            if let Some(military_unit) = &mut transport.dynamic_info.military_unit {
+                let costs = buildings.can_pay_known_cost(
+                    owner_id,
+                    &TileCoverage::Single(tile),
+                    IndustryType::MilitaryBase,
+                    upkeep,
+                );
+                if let Ok(costs) = &costs {
+                    buildings.pay_costs(costs);
+                }
+                military_unit.set_supplied(costs.is_ok());
+            }
         }
     }
 
+    #[must_use]
+    pub(crate) fn running_costs_per_second(&self) -> HashMap<PlayerId, Money> {
+        let mut results = HashMap::new();
+        for transport in &self.transports {
+            *results.entry(transport.owner_id()).or_default() +=
+                transport.transport_type().running_cost_per_second();
+        }
+        results
+    }
+
     pub(crate) fn upsert(&mut self, transport: TransportInfo) {
         let transport_id = transport.transport_id();
         if let Some(existing_transport) = self
@@ -78,6 +157,16 @@ impl TransportState {
         }
     }
 
+    pub(crate) fn remove(&mut self, transport_id: TransportId) -> Result<(), ()> {
+        let index = self
+            .transports
+            .iter()
+            .position(|transport| transport.transport_id() == transport_id)
+            .ok_or(())?;
+        let _ = self.transports.remove(index);
+        Ok(())
+    }
+
     pub(crate) fn update_dynamic_info(
         &mut self,
         transport_id: TransportId,
@@ -91,11 +180,21 @@ impl TransportState {
         }
     }
 
+    // If the transport is in a group and the order list was changed, the whole group gets the new
+    // order list
     pub(crate) fn update_movement_orders(
         &mut self,
         transport_id: TransportId,
         movement_orders: &MovementOrders,
     ) -> Result<(), ()> {
+        let group_id = self.info_by_id(transport_id).ok_or(())?.group_id();
+        if let Some(group_id) = group_id {
+            let group = self.group_by_id(group_id).ok_or(())?;
+            if !group.movement_orders().has_same_orders_as(movement_orders) {
+                self.update_group_movement_orders(group_id, movement_orders)?;
+            }
+        }
+
         for transport in &mut self.transports {
             if transport.transport_id() == transport_id {
                 transport.update_movement_orders(movement_orders);
@@ -112,4 +211,363 @@ impl TransportState {
             .iter()
             .find(|transport| transport.transport_id() == transport_id)
     }
+
+    #[must_use]
+    pub fn all_groups(&self) -> &Vec<TransportGroupInfo> {
+        &self.groups
+    }
+
+    #[must_use]
+    pub fn find_players_groups(&self, player_id: PlayerId) -> Vec<&TransportGroupInfo> {
+        self.groups
+            .iter()
+            .filter(|group| group.owner_id() == player_id)
+            .collect()
+    }
+
+    #[must_use]
+    pub fn group_by_id(&self, group_id: TransportGroupId) -> Option<&TransportGroupInfo> {
+        self.groups
+            .iter()
+            .find(|group| group.group_id() == group_id)
+    }
+
+    #[must_use]
+    pub fn transports_in_group(&self, group_id: TransportGroupId) -> Vec<&TransportInfo> {
+        self.transports
+            .iter()
+            .filter(|transport| transport.group_id() == Some(group_id))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn group_statistics(&self, group_id: TransportGroupId) -> TransportGroupStatistics {
+        let mut result = TransportGroupStatistics::default();
+        for transport in self.transports_in_group(group_id) {
+            result.transports += 1;
+            if transport.movement_orders().is_force_stopped() {
+                result.force_stopped += 1;
+            }
+            result.cargo_loaded += &transport.cargo_loaded();
+            result.cargo_capacity += &transport.cargo_capacity();
+        }
+        result
+    }
+
+    pub(crate) fn upsert_group(&mut self, group: TransportGroupInfo) {
+        let group_id = group.group_id();
+        if let Some(existing_group) = self
+            .groups
+            .iter_mut()
+            .find(|group| group.group_id() == group_id)
+        {
+            existing_group.clone_from(&group);
+        } else {
+            self.groups.push(group);
+        }
+    }
+
+    // The order that the fewest other transports in the group are currently heading to, so that
+    // the transports end up spread evenly along the route
+    fn least_served_order_index(
+        &self,
+        group_id: TransportGroupId,
+        excluding_transport_id: TransportId,
+        order_count: usize,
+    ) -> usize {
+        let mut served = vec![0usize; order_count];
+        for transport in self.transports_in_group(group_id) {
+            if transport.transport_id() != excluding_transport_id {
+                if let Some(count) = served.get_mut(transport.movement_orders().next_index()) {
+                    *count += 1;
+                }
+            }
+        }
+        served
+            .iter()
+            .enumerate()
+            .min_by_key(|(index, count)| (**count, *index))
+            .map_or(0, |(index, _)| index)
+    }
+
+    pub(crate) fn transport_mut(
+        &mut self,
+        transport_id: TransportId,
+    ) -> Option<&mut TransportInfo> {
+        self.transports
+            .iter_mut()
+            .find(|transport| transport.transport_id() == transport_id)
+    }
+
+    pub(crate) fn assign_to_group(
+        &mut self,
+        transport_id: TransportId,
+        group_id: Option<TransportGroupId>,
+    ) -> Result<(), ()> {
+        match group_id {
+            None => {
+                self.transport_mut(transport_id)
+                    .ok_or(())?
+                    .set_group_id(None);
+            },
+            Some(group_id) => {
+                let group_orders = self
+                    .group_by_id(group_id)
+                    .ok_or(())?
+                    .movement_orders()
+                    .clone();
+                let next_index = self.least_served_order_index(
+                    group_id,
+                    transport_id,
+                    group_orders.order_count(),
+                );
+                let transport = self.transport_mut(transport_id).ok_or(())?;
+                transport
+                    .dynamic_info
+                    .movement_orders
+                    .replace_orders_from(&group_orders);
+                transport
+                    .dynamic_info
+                    .movement_orders
+                    .set_next_index(next_index);
+                transport.set_group_id(Some(group_id));
+            },
+        }
+        Ok(())
+    }
+
+    pub(crate) fn update_group_movement_orders(
+        &mut self,
+        group_id: TransportGroupId,
+        movement_orders: &MovementOrders,
+    ) -> Result<(), ()> {
+        let group = self
+            .groups
+            .iter_mut()
+            .find(|group| group.group_id() == group_id)
+            .ok_or(())?;
+        group.update_movement_orders(movement_orders);
+
+        let member_ids: Vec<TransportId> = self
+            .transports_in_group(group_id)
+            .iter()
+            .map(|transport| transport.transport_id())
+            .collect();
+        let order_count = movement_orders.order_count();
+        for transport_id in member_ids {
+            // Transports whose current order no longer exists get re-spread along the route
+            let keeps_current_order = self
+                .info_by_id(transport_id)
+                .is_some_and(|transport| transport.movement_orders().next_index() < order_count);
+            let next_index = if keeps_current_order {
+                None
+            } else {
+                Some(self.least_served_order_index(group_id, transport_id, order_count))
+            };
+            if let Some(transport) = self.transport_mut(transport_id) {
+                let orders = &mut transport.dynamic_info.movement_orders;
+                orders.replace_orders_from(movement_orders);
+                if let Some(next_index) = next_index {
+                    orders.set_next_index(next_index);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn set_group_force_stop(
+        &mut self,
+        group_id: TransportGroupId,
+        force_stop: bool,
+    ) -> Result<(), ()> {
+        self.group_by_id(group_id).ok_or(())?;
+        for transport in &mut self.transports {
+            if transport.group_id() == Some(group_id) {
+                transport
+                    .dynamic_info
+                    .movement_orders
+                    .set_force_stop(force_stop);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared_util::direction_xz::DirectionXZ;
+
+    use super::*;
+    use crate::StationId;
+    use crate::building::station_info::StationInfo;
+    use crate::building::station_type::{StationOrientation, StationType};
+    use crate::building::track_info::TrackInfo;
+    use crate::metrics::NoopMetrics;
+    use crate::resource_type::ResourceType;
+    use crate::tile_coords_xz::TileCoordsXZ;
+    use crate::transport::movement_orders::MovementOrder;
+    use crate::transport::progress_within_tile::ProgressWithinTile;
+    use crate::transport::tile_track::TileTrack;
+    use crate::transport::track_type::TrackType;
+    use crate::transport::transport_location::TransportLocation;
+    use crate::transport::transport_type::TransportType;
+
+    fn movement_orders(station_ids: &[StationId]) -> MovementOrders {
+        let mut result = MovementOrders::one(MovementOrder::stop_at_station(station_ids[0]));
+        for station_id in &station_ids[1 ..] {
+            result.push(MovementOrder::stop_at_station(*station_id));
+        }
+        result
+    }
+
+    fn transport(owner_id: PlayerId, movement_orders: MovementOrders) -> TransportInfo {
+        let location = TransportLocation::new(
+            vec![TileTrack {
+                tile:        TileCoordsXZ::new(0, 0),
+                track_type:  TrackType::NorthSouth,
+                pointing_in: DirectionXZ::North,
+            }],
+            ProgressWithinTile::just_entering(),
+        );
+        TransportInfo::new(
+            TransportId::random(),
+            owner_id,
+            TransportType::cargo_train(ResourceType::Coal),
+            location,
+            movement_orders,
+        )
+    }
+
+    #[test]
+    fn test_transports_joining_a_group_are_spread_along_the_route() {
+        let owner_id = PlayerId::random();
+        let station_ids = [
+            StationId::random(),
+            StationId::random(),
+            StationId::random(),
+        ];
+        let group_orders = movement_orders(&station_ids);
+        let group_id = TransportGroupId::random();
+
+        let mut state = TransportState::empty();
+        state.upsert_group(TransportGroupInfo::new(
+            group_id,
+            owner_id,
+            "Line 1".to_string(),
+            group_orders.clone(),
+        ));
+
+        let transport_ids: Vec<_> = (0 .. 3)
+            .map(|_| {
+                let transport = transport(owner_id, movement_orders(&station_ids[.. 1]));
+                let transport_id = transport.transport_id();
+                state.upsert(transport);
+                state.assign_to_group(transport_id, Some(group_id)).unwrap();
+                transport_id
+            })
+            .collect();
+
+        let next_index = |state: &TransportState, transport_id| {
+            state
+                .info_by_id(transport_id)
+                .unwrap()
+                .movement_orders()
+                .next_index()
+        };
+        let mut indices: Vec<_> = transport_ids
+            .iter()
+            .map(|transport_id| next_index(&state, *transport_id))
+            .collect();
+        indices.sort_unstable();
+        assert_eq!(indices, vec![0, 1, 2]);
+
+        // Changing the orders of one member changes them for the whole group
+        let shorter_orders = movement_orders(&station_ids[.. 2]);
+        state
+            .update_movement_orders(transport_ids[0], &shorter_orders)
+            .unwrap();
+        for transport_id in &transport_ids {
+            let transport = state.info_by_id(*transport_id).unwrap();
+            assert!(
+                transport
+                    .movement_orders()
+                    .has_same_orders_as(&shorter_orders)
+            );
+            assert!(next_index(&state, *transport_id) < 2);
+        }
+
+        state.set_group_force_stop(group_id, true).unwrap();
+        assert_eq!(state.group_statistics(group_id).force_stopped, 3);
+    }
+
+    #[test]
+    fn test_trains_arriving_together_take_different_platforms() {
+        let owner_id = PlayerId::random();
+        let station_id = StationId::random();
+        let mut buildings = BuildingState::new(8, 2);
+        // A switch at (2, 0) leads either straight to the first platform, or to the second one
+        let tracks = [
+            (0, 0, TrackType::WestEast),
+            (1, 0, TrackType::WestEast),
+            (2, 0, TrackType::WestEast),
+            (2, 0, TrackType::SouthWest),
+            (2, 1, TrackType::NorthEast),
+        ]
+        .into_iter()
+        .map(|(x, z, track_type)| TrackInfo::new(owner_id, TileCoordsXZ::new(x, z), track_type))
+        .collect();
+        buildings.append_tracks(tracks);
+        buildings.append_station(StationInfo::new(
+            owner_id,
+            station_id,
+            TileCoordsXZ::new(3, 0),
+            StationType {
+                orientation:     StationOrientation::WestToEast,
+                platforms:       2,
+                length_in_tiles: 4,
+            },
+        ));
+
+        // Both trains are about to move onto the next tile during the same tick
+        let arriving = |x| {
+            let location = TransportLocation::new(
+                vec![TileTrack {
+                    tile:        TileCoordsXZ::new(x, 0),
+                    track_type:  TrackType::WestEast,
+                    pointing_in: DirectionXZ::East,
+                }],
+                ProgressWithinTile::about_to_exit(),
+            );
+            TransportInfo::new(
+                TransportId::random(),
+                owner_id,
+                TransportType::cargo_train(ResourceType::Coal),
+                location,
+                movement_orders(&[station_id]),
+            )
+        };
+        let first = arriving(2);
+        let second = arriving(1);
+        let mut state = TransportState::empty();
+        state.upsert(first.clone());
+        state.upsert(second.clone());
+
+        state.advance_time_diff(
+            GameTimeDiff::from_seconds(0.01),
+            &mut buildings,
+            &NoopMetrics::default(),
+        );
+
+        let next_tile_track = |transport: &TransportInfo| {
+            state
+                .info_by_id(transport.transport_id())
+                .unwrap()
+                .location()
+                .next_tile_in_path()
+        };
+        assert_eq!(next_tile_track(&first).tile, TileCoordsXZ::new(3, 0));
+        // The first platform is taken by the first train, so the second one takes the switch
+        assert_eq!(next_tile_track(&second).tile, TileCoordsXZ::new(2, 0));
+        assert_eq!(next_tile_track(&second).track_type, TrackType::SouthWest);
+    }
 }
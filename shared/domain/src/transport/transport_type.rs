@@ -6,20 +6,28 @@ use crate::building::building_info::WithCostToBuild;
 use crate::building::industry_type::IndustryType;
 use crate::cargo_amount::CargoAmount;
 use crate::cargo_map::CargoMap;
+use crate::military::ShellType;
+use crate::money::Money;
 use crate::resource_type::ResourceType;
 use crate::transport::transport_velocity::TransportVelocity;
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash)]
 pub enum TrainComponentType {
     Engine,
+    // Faster, but only under power lines of a powered grid
+    ElectricEngine,
     Car(ResourceType),
+    // Mobile military units are trains built at military bases out of these
+    RailwayGun,
 }
 
 impl Debug for TrainComponentType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             TrainComponentType::Engine => write!(f, "E"),
+            TrainComponentType::ElectricEngine => write!(f, "EE"),
             TrainComponentType::Car(resource_type) => write!(f, "{resource_type:?}"),
+            TrainComponentType::RailwayGun => write!(f, "RG"),
         }
     }
 }
@@ -28,8 +36,40 @@ impl TrainComponentType {
     #[must_use]
     pub fn length_in_tiles(self) -> f32 {
         match self {
-            TrainComponentType::Engine => 0.8,
+            TrainComponentType::Engine | TrainComponentType::ElectricEngine => 0.8,
             TrainComponentType::Car(_) => 0.4,
+            TrainComponentType::RailwayGun => 1.2,
+        }
+    }
+
+    #[must_use]
+    pub fn is_military(self) -> bool {
+        self == TrainComponentType::RailwayGun
+    }
+
+    // Built at a military base, in addition to the engine
+    fn military_cost_to_build(self) -> CargoMap {
+        match self {
+            TrainComponentType::RailwayGun => {
+                CargoMap::from([
+                    (ResourceType::ArtilleryWeapons, 1.0),
+                    (ResourceType::Ammunition, 0.5),
+                ])
+            },
+            TrainComponentType::Engine
+            | TrainComponentType::ElectricEngine
+            | TrainComponentType::Car(_) => CargoMap::new(),
+        }
+    }
+
+    fn upkeep_per_second(self) -> CargoMap {
+        match self {
+            TrainComponentType::RailwayGun => {
+                CargoMap::from([(ResourceType::Food, 0.01), (ResourceType::Fuel, 0.02)])
+            },
+            TrainComponentType::Engine
+            | TrainComponentType::ElectricEngine
+            | TrainComponentType::Car(_) => CargoMap::new(),
         }
     }
 }
@@ -79,8 +119,17 @@ impl TransportType {
 
     #[must_use]
     pub fn cargo_train(resource_type: ResourceType) -> Self {
+        Self::cargo_train_with_engine(TrainComponentType::Engine, resource_type)
+    }
+
+    #[must_use]
+    pub fn electric_cargo_train(resource_type: ResourceType) -> Self {
+        Self::cargo_train_with_engine(TrainComponentType::ElectricEngine, resource_type)
+    }
+
+    fn cargo_train_with_engine(engine: TrainComponentType, resource_type: ResourceType) -> Self {
         TransportType::Train(vec![
-            TrainComponentType::Engine,
+            engine,
             TrainComponentType::Car(resource_type),
             TrainComponentType::Car(resource_type),
             TrainComponentType::Car(resource_type),
@@ -92,6 +141,67 @@ impl TransportType {
         ])
     }
 
+    #[must_use]
+    pub fn railway_artillery() -> Self {
+        TransportType::Train(vec![
+            TrainComponentType::Engine,
+            TrainComponentType::RailwayGun,
+        ])
+    }
+
+    /// Military units move along the tracks like any other train, but deploy at their destination
+    /// instead of carrying cargo.
+    #[must_use]
+    pub fn is_military_unit(&self) -> bool {
+        match self {
+            TransportType::Train(components) => {
+                components.iter().any(|component| component.is_military())
+            },
+            TransportType::RoadVehicle(_) | TransportType::Ship(_) => false,
+        }
+    }
+
+    #[must_use]
+    pub fn railway_guns(&self) -> usize {
+        match self {
+            TransportType::Train(components) => {
+                components
+                    .iter()
+                    .filter(|component| **component == TrainComponentType::RailwayGun)
+                    .count()
+            },
+            TransportType::RoadVehicle(_) | TransportType::Ship(_) => 0,
+        }
+    }
+
+    /// The shells fired by the railway guns, if there are any.
+    #[must_use]
+    pub fn shell_type(&self) -> Option<ShellType> {
+        (self.railway_guns() > 0).then_some(ShellType::Naval14Inch)
+    }
+
+    /// What the military unit needs from a military base to keep operating.
+    #[must_use]
+    pub fn upkeep_per_second(&self) -> CargoMap {
+        let mut result = CargoMap::new();
+        if let TransportType::Train(components) = self {
+            for component in components {
+                result += &component.upkeep_per_second();
+            }
+        }
+        result
+    }
+
+    #[must_use]
+    pub fn is_electric(&self) -> bool {
+        match self {
+            TransportType::Train(components) => {
+                components.contains(&TrainComponentType::ElectricEngine)
+            },
+            TransportType::RoadVehicle(_) | TransportType::Ship(_) => false,
+        }
+    }
+
     #[must_use]
     pub fn cargo_capacity(&self) -> CargoMap {
         let mut result = CargoMap::new();
@@ -99,7 +209,9 @@ impl TransportType {
             TransportType::Train(components) => {
                 for component in components {
                     match component {
-                        TrainComponentType::Engine => {},
+                        TrainComponentType::Engine
+                        | TrainComponentType::ElectricEngine
+                        | TrainComponentType::RailwayGun => {},
                         TrainComponentType::Car(resource_type) => {
                             result.add(*resource_type, CargoAmount::new(1.0));
                         },
@@ -116,17 +228,52 @@ impl TransportType {
         result
     }
 
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn running_cost_per_second(&self) -> Money {
+        match self {
+            TransportType::Train(components) => Money::new(0.2 * components.len() as f64),
+            TransportType::RoadVehicle(_) => Money::new(0.1),
+            TransportType::Ship(_) => Money::new(0.5),
+        }
+    }
+
     #[must_use]
     pub fn max_velocity(&self) -> TransportVelocity {
-        TransportVelocity::new(2.0)
+        match self {
+            // Trains are further limited by the `TrackGrade` of the tracks they are on, and electric
+            // trains also by whether these are electrified
+            TransportType::Train(_) if self.is_electric() => TransportVelocity::new(4.5),
+            TransportType::Train(_) => TransportVelocity::new(3.0),
+            TransportType::RoadVehicle(_) | TransportType::Ship(_) => TransportVelocity::new(2.0),
+        }
     }
 }
 
 impl WithCostToBuild for TransportType {
     fn cost_to_build(&self) -> (IndustryType, CargoMap) {
-        (
-            IndustryType::ConstructionYard,
-            CargoMap::single(ResourceType::Steel, 1.0),
-        )
+        match self {
+            TransportType::Train(components) if self.is_military_unit() => {
+                let mut cost = CargoMap::new();
+                for component in components {
+                    cost += &component.military_cost_to_build();
+                }
+                (IndustryType::MilitaryBase, cost)
+            },
+            TransportType::Train(_) => {
+                // Electric locomotives are more complex to build
+                let rolling_stock = if self.is_electric() { 2.0 } else { 1.0 };
+                (
+                    IndustryType::TrainFactory,
+                    CargoMap::single(ResourceType::RollingStock, rolling_stock),
+                )
+            },
+            TransportType::RoadVehicle(_) | TransportType::Ship(_) => {
+                (
+                    IndustryType::ConstructionYard,
+                    CargoMap::single(ResourceType::Steel, 1.0),
+                )
+            },
+        }
     }
 }
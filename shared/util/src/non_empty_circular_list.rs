@@ -53,6 +53,12 @@ impl<T: Clone> NonEmptyCircularList<T> {
         self.list[self.next].clone()
     }
 
+    // Never empty, so there is always a last item
+    pub fn last_mut(&mut self) -> &mut T {
+        let last = self.list.len() - 1;
+        &mut self.list[last]
+    }
+
     pub fn advance(&mut self) {
         self.next = (self.next + 1) % self.list.len();
     }
@@ -62,6 +68,17 @@ impl<T: Clone> NonEmptyCircularList<T> {
         self.next
     }
 
+    pub fn set_next_index(&mut self, index: usize) {
+        self.next = index % self.list.len();
+    }
+
+    // Never empty, so there is no `is_empty`
+    #[expect(clippy::len_without_is_empty)]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
     pub fn remove_by_index(&mut self, index: usize) {
         if self.list.len() > 1 {
             self.list.remove(index);